@@ -1,6 +1,9 @@
 use crate::models::utils::secret::Secret;
+use crate::models::utils::startup_window::{default_window, requested_window};
+use crate::models::utils::utils::lock_or_recover;
 use crate::views::main_page::dashboard::Dashboard;
 use crate::views::main_page::gantt::GanttChart;
+use crate::views::main_page::resources_panel::ResourcesPanel;
 use crate::views::menu::menu::Menu;
 use crate::views::menu::tools::Tools;
 use crate::views::view::View;
@@ -8,11 +11,21 @@ use crate::{
     models::data_structure::application_context::ApplicationContext,
     views::main_page::anthentification::Authentification,
 };
+use chrono::Local;
 use eframe::egui::{self, CentralPanel, TopBottomPanel};
+use std::time::Duration;
+
+/// Polling interval while a data refresh is in progress.
+const REFRESHING_REPAINT_INTERVAL: Duration = Duration::from_millis(100);
+/// Tick interval of the snake game.
+const GAME_REPAINT_INTERVAL: Duration = Duration::from_millis(100);
+/// Upper bound between two frames when nothing happens.
+const IDLE_REPAINT_INTERVAL: Duration = Duration::from_secs(1);
 
 pub struct App {
     pub dashboard_view: Dashboard,
     pub gantt_view: GanttChart,
+    pub resources_panel: ResourcesPanel,
     pub authentification_view: Authentification,
     pub menu: Menu,
     pub secret: Secret,
@@ -22,21 +35,54 @@ pub struct App {
 
 impl App {
     pub fn new() -> Self {
+        // Initial window from the command line or the environment, else now ± 1 hour
+        let now = Local::now();
+        let requested = requested_window(now);
+        let (start, end) = requested.unwrap_or_else(|| default_window(now));
+        let mut gantt_view = GanttChart::default();
+        // An explicit window is kept as is, the Gantt does not fit it to the first jobs loaded
+        if requested.is_some() {
+            gantt_view.keep_initial_window();
+        }
         let app = App {
             secret: Secret::default(),
             dashboard_view: Dashboard::default(),
-            gantt_view: GanttChart::default(),
+            gantt_view,
+            resources_panel: ResourcesPanel,
             authentification_view: Authentification::default(),
             menu: Menu::default(),
             tools: Tools::default(),
-            application_context: ApplicationContext::default(),
+            application_context: ApplicationContext::with_window(start, end),
         };
 
         app
     }
+
+    /// Schedule the next frame instead of repainting continuously.
+    /// egui already repaints on input, and the Gantt zoom animation requests its own frames:
+    /// here we only keep polling while a refresh is running (to pick the new data up quickly),
+    /// tick the snake game, and otherwise wake up periodically to check for data coming from
+    /// the background updater and move the "now" line.
+    fn request_next_repaint(&self, ctx: &egui::Context) {
+        if *lock_or_recover(&self.application_context.is_refreshing) {
+            ctx.request_repaint_after(REFRESHING_REPAINT_INTERVAL);
+        } else if self.secret.is_game_running() {
+            ctx.request_repaint_after(GAME_REPAINT_INTERVAL);
+        } else {
+            let refresh_rate = Duration::from_secs(*lock_or_recover(&self.application_context.refresh_rate));
+            ctx.request_repaint_after(refresh_rate.min(IDLE_REPAINT_INTERVAL));
+        }
+    }
 }
 
 impl eframe::App for App {
+    fn raw_input_hook(&mut self, ctx: &egui::Context, raw_input: &mut egui::RawInput) {
+        // Tab / Shift+Tab walk through the Gantt jobs: take them before egui moves the focus
+        if matches!(self.application_context.view_type, crate::views::view::ViewType::Gantt) {
+            self.gantt_view.intercept_job_navigation_keys(ctx, raw_input);
+        }
+    }
+
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         self.secret.update(ctx);
         self.secret.draw_snake_game(ctx);
@@ -67,13 +113,21 @@ impl eframe::App for App {
             .exact_height(18.0)
             .show(ctx, |ui| {
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                    if *self.application_context.is_refreshing.lock().unwrap() {
+                    if *lock_or_recover(&self.application_context.is_refreshing) {
                         ui.add(egui::Spinner::new().size(12.0));
                         ui.label(egui::RichText::new(t!("app.refreshing")).small());
                     }
                 });
             });
 
+        // Side panels also have to be shown before the central panel
+        egui::SidePanel::right("resources_panel")
+            .resizable(true)
+            .default_width(300.0)
+            .show_animated(ctx, self.application_context.resources_panel_open, |ui| {
+                self.resources_panel.render(ui, &mut self.application_context);
+            });
+
         CentralPanel::default().show(ctx, |ui| match self.application_context.view_type {
             crate::views::view::ViewType::Dashboard => {
                 self.dashboard_view.render(ui, &mut self.application_context);
@@ -86,6 +140,6 @@ impl eframe::App for App {
                     .render(ui, &mut self.application_context);
             }
         });
-        ctx.request_repaint();
+        self.request_next_repaint(ctx);
     }
 }
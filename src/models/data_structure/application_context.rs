@@ -1,18 +1,17 @@
 use super::cluster::Cluster;
-use super::filters::JobFilters;
+use super::filters::{job_matches, JobFilters};
 use super::job::Job;
-use super::resource::Resource;
 use super::strata::Strata;
-use crate::models::data_structure::cpu::Cpu;
-use crate::models::data_structure::host::Host;
-use crate::models::data_structure::resource::ResourceState;
-use crate::models::utils::utils::{get_clusters_for_job, get_hosts_for_job};
-use crate::views::components::dashboard_components::job_table_sorting::JobSortable;
+use crate::models::data_structure::resource::ResourceStateCounts;
+use crate::models::utils::resource_tree::ResourceUpdate;
+use crate::models::utils::startup_window::default_window;
+use crate::models::utils::utils::{
+    get_all_resources, get_clusters_for_job, get_hosts_for_job, lock_or_recover, site_for_cluster_name,
+};
 use crate::views::view::ViewType;
 use chrono::{DateTime, Local};
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::mpsc::{channel, Receiver, Sender};
 use std::sync::{Arc, Mutex};
 
@@ -21,6 +20,10 @@ use std::sync::{Arc, Mutex};
 It manages jobs, clusters, resources, and application state, including filtering mechanisms
 and communication channels for data updates.
 */
+/// Cluster and host name given to jobs whose resources are missing from the cluster tree
+/// (stale or cross-cluster resources, refresh races), when `include_orphan_jobs` is set
+pub const UNKNOWN_GROUP: &str = "unknown";
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ClusterPreset {
     pub name: String,
@@ -31,9 +34,20 @@ pub struct ApplicationContext {
     pub all_jobs: Vec<Job>,
     pub swap_all_jobs: Vec<Job>, // Used to store all jobs when refreshing (and swapped with all_jobs when refreshing is done)
     pub filtered_jobs: Vec<Job>, // Subset of all_jobs that match the filters
+    pub implausible_jobs: usize, // Jobs left out of filtered_jobs because of implausible timestamps
+    pub orphan_jobs: usize, // Jobs referencing resources missing from all_clusters
+    pub include_orphan_jobs: bool, // Show orphan jobs under UNKNOWN_GROUP instead of dropping them from the cluster views
+    pub last_data_update: Option<DateTime<Local>>, // Reception time of the last job list from the background fetch
 
     pub all_clusters: Vec<Cluster>,
     pub swap_all_clusters: Vec<Cluster>, // Used to store all clusters when refreshing (and swapped with all_clusters when refreshing is done)
+    resources_fingerprint: Option<u64>, // Fingerprint of the last resource list used to build the clusters
+    cluster_fingerprints: HashMap<String, u64>, // Same, per cluster (see `update_aggregate_states`)
+    jobs_pending: bool, // New jobs received in swap_all_jobs but not yet published to all_jobs
+    jobs_generation: u64, // Bumped whenever the content of all_jobs changes
+    filtered_jobs_source: u64, // jobs_generation that filtered_jobs was last built from
+    pub filtered_jobs_generation: u64, // Bumped whenever filtered_jobs changes, so views can cache what they derive from it
+    pub clusters_generation: u64, // Bumped whenever all_clusters is replaced
     pub cluster_presets: Vec<ClusterPreset>, // saved cluster presets (admin only)
 
     // Application view state
@@ -49,486 +63,161 @@ pub struct ApplicationContext {
     // Communication channels for background data updates
     pub jobs_receiver: Receiver<Vec<Job>>,
     pub jobs_sender: Sender<Vec<Job>>,
-    pub resources_receiver: Receiver<Vec<Strata>>,
-    pub resources_sender: Sender<Vec<Strata>>,
+    pub resources_receiver: Receiver<ResourceUpdate>,
+    pub resources_sender: Sender<ResourceUpdate>,
 
     // Latest resource metadata indexed by host (used for rich hover tooltips).
     pub strata_by_host: HashMap<String, Strata>,
+    // Number of distinct GPU devices per host (keyed like `Host::name`).
+    pub gpu_count_by_host: HashMap<String, usize>,
+
+    // Optional cluster -> site mapping (from `cluster_sites.json`), used when the site
+    // cannot be read from host names.
+    pub cluster_site_map: HashMap<String, String>,
 
     pub font_size: i32,
     pub see_all_jobs: bool,
 
+    // Job sélectionné, partagé entre le tableau des jobs et le Gantt
+    pub selected_job_id: Option<u32>,
+
+    // Command search typed in the filter window but not applied yet: the Gantt outlines the
+    // matching jobs as a preview
+    pub command_preview: Option<String>,
+
     // UI requests (set by views, consumed by Menu/Options)
     pub theme_toggle_requested: bool,
+    pub resources_panel_open: bool, // Cluster inventory side panel, toggled from the tool bar
 }
 
 impl ApplicationContext {
     pub fn check_job_update(&mut self) {
         if let Ok(new_jobs) = self.jobs_receiver.try_recv() {
             self.swap_all_jobs = new_jobs;
+            self.jobs_pending = true;
             self.is_loading = false;
+            self.last_data_update = Some(Local::now());
         }
     }
 
     /*
     Checks for and processes any new resource data received from the background thread.
-     This method builds the hierarchical structure of clusters, hosts, CPUs, and resources
-     from the flat resource data received.
+     The hierarchical structure of clusters, hosts, CPUs, and resources is built by that
+     thread (see `prepare_resource_update`); this method swaps it in and computes the
+     majority states of the clusters that changed.
      */
     pub fn check_ressource_update(&mut self) {
-        if let Ok(new_resources) = self.resources_receiver.try_recv() {
-            fn extract_ints_from_value(v: &Value) -> Vec<i32> {
-                fn extract_ints_from_str(s: &str) -> Vec<i32> {
-                    let mut out: Vec<i32> = Vec::new();
-                    let mut cur: i64 = 0;
-                    let mut in_num = false;
-                    for ch in s.chars() {
-                        if ch.is_ascii_digit() {
-                            in_num = true;
-                            cur = cur * 10 + (ch as i64 - '0' as i64);
-                        } else if in_num {
-                            if (0..=i32::MAX as i64).contains(&cur) {
-                                out.push(cur as i32);
-                            }
-                            cur = 0;
-                            in_num = false;
-                        }
-                    }
-                    if in_num && (0..=i32::MAX as i64).contains(&cur) {
-                        out.push(cur as i32);
-                    }
-                    out
-                }
-
-                match v {
-                    Value::Null => Vec::new(),
-                    Value::Bool(_) => Vec::new(),
-                    Value::Number(n) => n
-                        .as_i64()
-                        .filter(|i| (0..=i32::MAX as i64).contains(i))
-                        .map(|i| vec![i as i32])
-                        .unwrap_or_default(),
-                    Value::String(s) => extract_ints_from_str(s),
-                    Value::Array(arr) => {
-                        let mut all: Vec<i32> = Vec::new();
-                        for x in arr {
-                            all.extend(extract_ints_from_value(x));
-                        }
-                        all
-                    }
-                    Value::Object(_) => Vec::new(),
+        if let Ok(update) = self.resources_receiver.try_recv() {
+            // Skip the rebuild of the clusters when the resources did not change since the
+            // last refresh: only the newly received jobs (if any) are published.
+            if self.resources_fingerprint == Some(update.fingerprint) {
+                if self.jobs_pending {
+                    self.attach_jobs_to_clusters();
+                    self.publish_jobs();
                 }
+                return;
             }
-
-            // Build cpuset index list per host by aggregating resource-level cpuset values.
-            // OAR resources often provide a scalar cpuset per resource; Grid5000 displays the
-            // aggregated list at host level.
-            let mut cpuset_by_host: HashMap<String, Vec<i32>> = HashMap::new();
-            for r in new_resources.iter() {
-                let host = r.host.as_deref().unwrap_or("").trim();
-                if host.is_empty() {
-                    continue;
-                }
-                if let Some(v) = r.cpuset.as_ref() {
-                    let ints = extract_ints_from_value(v);
-                    if !ints.is_empty() {
-                        cpuset_by_host
-                            .entry(host.to_string())
-                            .or_default()
-                            .extend(ints);
+            self.resources_fingerprint = Some(update.fingerprint);
+
+            self.strata_by_host = update.strata_by_host;
+            self.gpu_count_by_host = update.gpu_count_by_host;
+
+            // Clusters whose resources did not change are kept as they are (with their
+            // computed states), the others are replaced by the newly built tree
+            let mut previous_clusters: HashMap<String, Cluster> =
+                std::mem::take(&mut self.swap_all_clusters)
+                    .into_iter()
+                    .map(|cluster| (cluster.name.clone(), cluster))
+                    .collect();
+            let mut updated_clusters: HashSet<String> = HashSet::new();
+            for cluster in update.clusters {
+                let unchanged = self.cluster_fingerprints.get(&cluster.name)
+                    == update.cluster_fingerprints.get(&cluster.name);
+                match previous_clusters.remove(&cluster.name) {
+                    Some(previous) if unchanged => self.swap_all_clusters.push(previous),
+                    _ => {
+                        updated_clusters.insert(cluster.name.clone());
+                        self.swap_all_clusters.push(cluster);
                     }
                 }
             }
+            self.cluster_fingerprints = update.cluster_fingerprints;
 
-            // Cache the latest metadata for tooltips. Use multiple keys per host to be robust
-            // (short host, FQDN, network_address).
-            self.strata_by_host.clear();
-            for r in new_resources.iter() {
-                let host = r.host.as_deref().unwrap_or("").trim();
-                let net = r.network_address.as_deref().unwrap_or("").trim();
-
-                if !host.is_empty() {
-                    self.strata_by_host
-                        .entry(host.to_string())
-                        .or_insert_with(|| r.clone());
-                    let short = host.split('.').next().unwrap_or(host).trim();
-                    if !short.is_empty() {
-                        self.strata_by_host
-                            .entry(short.to_string())
-                            .or_insert_with(|| r.clone());
-                    }
-                }
+            self.attach_jobs_to_clusters();
 
-                if !net.is_empty() {
-                    self.strata_by_host
-                        .entry(net.to_string())
-                        .or_insert_with(|| r.clone());
-                    let short = net.split('.').next().unwrap_or(net).trim();
-                    if !short.is_empty() {
-                        self.strata_by_host
-                            .entry(short.to_string())
-                            .or_insert_with(|| r.clone());
-                    }
-                }
+            // Majority states, only for the clusters whose resources changed
+            update_aggregate_states(&mut self.swap_all_clusters, &updated_clusters);
 
-                // Prefer a record that has more human-friendly fields filled.
-                // This updates an already-inserted entry if the new record is "better".
-                fn non_empty_value(v: &Value) -> bool {
-                    match v {
-                        Value::Null => false,
-                        Value::Bool(_) => true,
-                        Value::Number(_) => true,
-                        Value::String(s) => !s.trim().is_empty(),
-                        Value::Array(arr) => arr.iter().any(non_empty_value),
-                        Value::Object(obj) => !obj.is_empty(),
-                    }
-                }
-                for k in [host, net] {
-                    if k.is_empty() {
-                        continue;
-                    }
-                    if let Some(existing) = self.strata_by_host.get(k).cloned() {
-                        let existing_score = existing
-                            .comment
-                            .as_ref()
-                            .map(|s| !s.trim().is_empty())
-                            .unwrap_or(false) as i32
-                            + existing
-                                .cpuset
-                                .as_ref()
-                                .map(non_empty_value)
-                                .unwrap_or(false) as i32
-                            + existing
-                                .cputype
-                                .as_ref()
-                                .map(|s| !s.trim().is_empty())
-                                .unwrap_or(false) as i32
-                            + existing
-                                .nodemodel
-                                .as_ref()
-                                .map(|s| !s.trim().is_empty())
-                                .unwrap_or(false) as i32;
-                        let new_score = r
-                            .comment
-                            .as_ref()
-                            .map(|s| !s.trim().is_empty())
-                            .unwrap_or(false) as i32
-                            + r
-                                .cpuset
-                                .as_ref()
-                                .map(non_empty_value)
-                                .unwrap_or(false) as i32
-                            + r
-                                .cputype
-                                .as_ref()
-                                .map(|s| !s.trim().is_empty())
-                                .unwrap_or(false) as i32
-                            + r
-                                .nodemodel
-                                .as_ref()
-                                .map(|s| !s.trim().is_empty())
-                                .unwrap_or(false) as i32;
-                        if new_score > existing_score {
-                            self.strata_by_host.insert(k.to_string(), r.clone());
-                        }
-                    }
-                }
-            }
+            // Swap all_jobs and all_clusters with swap_all_jobs and swap_all_clusters
+            self.publish_jobs();
+            self.all_clusters = self.swap_all_clusters.clone();
+            self.clusters_generation += 1;
+        }
+    }
 
-            // Overwrite cached cpuset with the aggregated host-level cpuset list (when available).
-            for s in self.strata_by_host.values_mut() {
-                let host_key = s.host.as_deref().unwrap_or("").trim();
-                if host_key.is_empty() {
-                    continue;
-                }
-                if let Some(ints) = cpuset_by_host.get(host_key) {
-                    let mut ints = ints.clone();
-                    ints.sort_unstable();
-                    ints.dedup();
-                    if !ints.is_empty() {
-                        let arr: Vec<Value> = ints
-                            .into_iter()
-                            .map(|i| Value::Number(serde_json::Number::from(i)))
-                            .collect();
-                        s.cpuset = Some(Value::Array(arr));
-                    }
-                }
+    // Sets the clusters, hosts and majority resource state of the received jobs
+    fn attach_jobs_to_clusters(&mut self) {
+        let known_resources: HashSet<u32> = get_all_resources(&self.swap_all_clusters).into_iter().collect();
+        let mut orphan_jobs = 0;
+        let mut orphan_resources: HashSet<u32> = HashSet::new();
+        for job in self.swap_all_jobs.iter_mut() {
+            job.clusters = get_clusters_for_job(job, &self.swap_all_clusters);
+            job.hosts = get_hosts_for_job(job, &self.swap_all_clusters);
+            job.update_majority_resource_state(&self.swap_all_clusters);
+
+            let orphans = tag_orphan_job(job, &known_resources, self.include_orphan_jobs);
+            if !orphans.is_empty() {
+                orphan_jobs += 1;
+                orphan_resources.extend(orphans);
             }
+        }
+        if orphan_jobs != self.orphan_jobs && orphan_jobs > 0 {
+            println!(
+                "Warning: {} jobs reference {} resources missing from the clusters: {:?}",
+                orphan_jobs,
+                orphan_resources.len(),
+                orphan_resources.iter().take(10).collect::<Vec<_>>()
+            );
+        }
+        self.orphan_jobs = orphan_jobs;
+    }
 
-            // for every resources get the cluster name with resource.cluster and if there is no cluster with this name in all_clusters add it to all_clusters
-            for resource in new_resources.iter() {
-                let cluster_name = resource.cluster.as_ref().unwrap_or(&"".to_string()).clone();
-                if cluster_name == "" {
-                    continue;
-                }
-                if !self
-                    .swap_all_clusters
-                    .iter()
-                    .any(|cluster| cluster.name == cluster_name)
-                {
-                    // Add the cluster to all_clusters with one host being resource.host
-                    let new_cluster = Cluster {
-                        name: cluster_name.clone(),
-                        hosts: vec![Host {
-                            name: resource.host.as_ref().unwrap_or(&"".to_string()).clone(),
-                            cpus: vec![Cpu {
-                                name: resource.cputype.as_ref().unwrap_or(&"".to_string()).clone(),
-                                resources: vec![Resource {
-                                    id: resource.resource_id.unwrap_or(0),
-                                    state: match resource
-                                        .state
-                                        .as_ref()
-                                        .unwrap_or(&"".to_string())
-                                        .as_str()
-                                    {
-                                        "Dead" => super::resource::ResourceState::Dead,
-                                        "Alive" => super::resource::ResourceState::Alive,
-                                        "Absent" => super::resource::ResourceState::Absent,
-                                        _ => super::resource::ResourceState::Unknown,
-                                    },
-                                    thread_count: resource.thread_count.unwrap_or(0) as i32,
-                                }],
-                                core_count: resource.core_count.unwrap_or(0) as i32,
-                                cpufreq: resource
-                                    .cpufreq
-                                    .as_ref()
-                                    .unwrap_or(&"".to_string())
-                                    .parse::<f32>()
-                                    .unwrap_or(0.0),
-                                chassis: resource
-                                    .chassis
-                                    .as_ref()
-                                    .unwrap_or(&"".to_string())
-                                    .clone(),
-                                resource_ids: vec![resource.resource_id.unwrap_or(0)],
-                            }],
-                            network_address: resource
-                                .network_address
-                                .as_ref()
-                                .unwrap_or(&"".to_string())
-                                .clone(),
-                            resource_ids: vec![resource.resource_id.unwrap_or(0)],
-                            state: ResourceState::Unknown,
-                        }],
-                        resource_ids: vec![resource.resource_id.unwrap_or(0)],
-                        state: ResourceState::Unknown,
-                    };
-
-                    // Add the cluster to all_clusters
-                    self.swap_all_clusters.push(new_cluster);
-                } else {
-                    // if the cluster already exists, check if the host exists and add the host if it doesn't
-                    let cluster = self
-                        .swap_all_clusters
-                        .iter_mut()
-                        .find(|cluster| cluster.name == cluster_name)
-                        .unwrap();
-                    if !cluster.hosts.iter().any(|host| {
-                        host.name == resource.host.as_ref().unwrap_or(&"".to_string()).clone()
-                    }) {
-                        cluster.hosts.push(Host {
-                            name: resource.host.as_ref().unwrap_or(&"".to_string()).clone(),
-                            cpus: vec![Cpu {
-                                name: resource.cputype.as_ref().unwrap_or(&"".to_string()).clone(),
-                                resources: vec![Resource {
-                                    id: resource.resource_id.unwrap_or(0),
-                                    state: match resource
-                                        .state
-                                        .as_ref()
-                                        .unwrap_or(&"".to_string())
-                                        .as_str()
-                                    {
-                                        "Dead" => super::resource::ResourceState::Dead,
-                                        "Alive" => super::resource::ResourceState::Alive,
-                                        "Absent" => super::resource::ResourceState::Absent,
-                                        _ => super::resource::ResourceState::Unknown,
-                                    },
-                                    thread_count: resource.thread_count.unwrap_or(0) as i32,
-                                }],
-                                core_count: resource.core_count.unwrap_or(0) as i32,
-                                cpufreq: resource
-                                    .cpufreq
-                                    .as_ref()
-                                    .unwrap_or(&"".to_string())
-                                    .parse::<f32>()
-                                    .unwrap_or(0.0),
-                                chassis: resource
-                                    .chassis
-                                    .as_ref()
-                                    .unwrap_or(&"".to_string())
-                                    .clone(),
-                                resource_ids: vec![resource.resource_id.unwrap_or(0)],
-                            }],
-                            network_address: resource
-                                .network_address
-                                .as_ref()
-                                .unwrap_or(&"".to_string())
-                                .clone(),
-                            resource_ids: vec![resource.resource_id.unwrap_or(0)],
-                            state: ResourceState::Unknown,
-                        });
-                        // add the resource id to the cluster
-                        cluster.resource_ids.push(resource.resource_id.unwrap_or(0));
-                    } else {
-                        // if the host already exists, check if the cpu exists and add the cpu if it doesn't
-                        let host = cluster
-                            .hosts
-                            .iter_mut()
-                            .find(|host| {
-                                host.name
-                                    == resource.host.as_ref().unwrap_or(&"".to_string()).clone()
-                            })
-                            .unwrap();
-                        if !host.cpus.iter().any(|cpu| {
-                            cpu.name == resource.cputype.as_ref().unwrap_or(&"".to_string()).clone()
-                        }) {
-                            host.cpus.push(Cpu {
-                                name: resource.cputype.as_ref().unwrap_or(&"".to_string()).clone(),
-                                resources: vec![Resource {
-                                    id: resource.resource_id.unwrap_or(0),
-                                    state: match resource
-                                        .state
-                                        .as_ref()
-                                        .unwrap_or(&"".to_string())
-                                        .as_str()
-                                    {
-                                        "Dead" => super::resource::ResourceState::Dead,
-                                        "Alive" => super::resource::ResourceState::Alive,
-                                        "Absent" => super::resource::ResourceState::Absent,
-                                        _ => super::resource::ResourceState::Unknown,
-                                    },
-                                    thread_count: resource.thread_count.unwrap_or(0) as i32,
-                                }],
-                                core_count: resource.core_count.unwrap_or(0) as i32,
-                                cpufreq: resource
-                                    .cpufreq
-                                    .as_ref()
-                                    .unwrap_or(&"".to_string())
-                                    .parse::<f32>()
-                                    .unwrap_or(0.0),
-                                chassis: resource
-                                    .chassis
-                                    .as_ref()
-                                    .unwrap_or(&"".to_string())
-                                    .clone(),
-                                resource_ids: vec![resource.resource_id.unwrap_or(0)],
-                            });
-
-                            // add the resource id to the host and the cluster
-                            host.resource_ids.push(resource.resource_id.unwrap_or(0));
-                            cluster.resource_ids.push(resource.resource_id.unwrap_or(0));
-                        } else {
-                            // if the cpu already exists, add the resource to the cpu
-                            let cpu = host
-                                .cpus
-                                .iter_mut()
-                                .find(|cpu| {
-                                    cpu.name
-                                        == resource
-                                            .cputype
-                                            .as_ref()
-                                            .unwrap_or(&"".to_string())
-                                            .clone()
-                                })
-                                .unwrap();
-                            cpu.resources.push(Resource {
-                                id: resource.resource_id.unwrap_or(0),
-                                state: match resource
-                                    .state
-                                    .as_ref()
-                                    .unwrap_or(&"".to_string())
-                                    .as_str()
-                                {
-                                    "Dead" => super::resource::ResourceState::Dead,
-                                    "Alive" => super::resource::ResourceState::Alive,
-                                    "Absent" => super::resource::ResourceState::Absent,
-                                    _ => super::resource::ResourceState::Unknown,
-                                },
-                                thread_count: resource.thread_count.unwrap_or(0) as i32,
-                            });
-
-                            // add the resource id to the cpu, the host and the cluster
-                            cpu.resource_ids.push(resource.resource_id.unwrap_or(0));
-                            host.resource_ids.push(resource.resource_id.unwrap_or(0));
-                            cluster.resource_ids.push(resource.resource_id.unwrap_or(0));
-                        }
-                    }
-                }
-            }
-            for job in self.swap_all_jobs.iter_mut() {
-                job.clusters = get_clusters_for_job(job, &self.swap_all_clusters);
-                job.hosts = get_hosts_for_job(job, &self.swap_all_clusters);
-                job.update_majority_resource_state(&self.swap_all_clusters);
-            }
+    // Shows or hides the orphan jobs under UNKNOWN_GROUP, then refilters
+    pub fn set_include_orphan_jobs(&mut self, include: bool) {
+        self.include_orphan_jobs = include;
+        let known_resources: HashSet<u32> = get_all_resources(&self.all_clusters).into_iter().collect();
+        for job in self.all_jobs.iter_mut() {
+            tag_orphan_job(job, &known_resources, include);
+        }
+        self.jobs_generation += 1;
+        self.filter_jobs();
+    }
 
-            // For each host set is state to the state the most resources have
-            for cluster in self.swap_all_clusters.iter_mut() {
-                for host in cluster.hosts.iter_mut() {
-                    let mut dead_count = 0;
-                    let mut alive_count = 0;
-                    let mut absent_count = 0;
-                    for cpu in host.cpus.iter() {
-                        for resource in cpu.resources.iter() {
-                            match resource.state {
-                                ResourceState::Dead => dead_count += 1,
-                                ResourceState::Alive => alive_count += 1,
-                                ResourceState::Absent => absent_count += 1,
-                                _ => (),
-                            }
-                        }
-                    }
-                    if dead_count >= alive_count && dead_count >= absent_count {
-                        host.state = ResourceState::Dead;
-                    } else if absent_count >= dead_count && absent_count >= alive_count {
-                        host.state = ResourceState::Absent;
-                    } else if alive_count > dead_count && alive_count > absent_count {
-                        host.state = ResourceState::Alive;
-                    } else {
-                        host.state = ResourceState::Unknown;
-                    }
-                }
-            }
+    // Replaces all_jobs with swap_all_jobs
+    fn publish_jobs(&mut self) {
+        // If there is a job with id 0 in all_jobs, we keep it
+        let has_job_0 = self.all_jobs.iter().any(|job| job.id == 0);
+        if has_job_0 {
+            // Get the job with id 0
+            let job_0 = self
+                .all_jobs
+                .iter()
+                .find(|job| job.id == 0)
+                .unwrap()
+                .clone();
+            self.swap_all_jobs.push(job_0);
+        }
 
-            // For each cluster set is state to the state the most hosts have
-            for cluster in self.swap_all_clusters.iter_mut() {
-                let mut dead_count = 0;
-                let mut alive_count = 0;
-                let mut absent_count = 0;
-                for host in cluster.hosts.iter() {
-                    match host.state {
-                        ResourceState::Dead => dead_count += 1,
-                        ResourceState::Alive => alive_count += 1,
-                        ResourceState::Absent => absent_count += 1,
-                        _ => (),
-                    }
-                }
-                if dead_count >= alive_count && dead_count >= absent_count {
-                    cluster.state = ResourceState::Dead;
-                } else if absent_count >= dead_count && absent_count >= alive_count {
-                    cluster.state = ResourceState::Absent;
-                } else if alive_count > dead_count && alive_count > absent_count {
-                    cluster.state = ResourceState::Alive;
-                } else {
-                    cluster.state = ResourceState::Unknown;
-                }
-            }
-            // Swap all_jobs and all_clusters with swap_all_jobs and swap_all_clusters
-            // If there is a job with id 0 in all_jobs, we keep it
-            let has_job_0 = self.all_jobs.iter().any(|job| job.id == 0);
-            if has_job_0 {
-                // Get the job with id 0
-                let job_0 = self
-                    .all_jobs
-                    .iter()
-                    .find(|job| job.id == 0)
-                    .unwrap()
-                    .clone();
-                self.swap_all_jobs.push(job_0);
-            }
+        self.all_jobs = self.swap_all_jobs.clone();
+        self.jobs_pending = false;
+        self.jobs_generation += 1;
+    }
 
-            self.all_jobs = self.swap_all_jobs.clone();
-            self.all_clusters = self.swap_all_clusters.clone();
-        }
+    // Replaces the synthetic job 0 (all the resources shown, drawn as background) in all_jobs
+    pub fn replace_background_job(&mut self, job: Job) {
+        self.all_jobs.retain(|job| job.id != 0);
+        self.all_jobs.push(job);
+        self.jobs_generation += 1;
     }
 
     pub fn check_data_update(&mut self) {
@@ -537,9 +226,9 @@ impl ApplicationContext {
 
         // set filter date to the date of the app context
         self.filters
-            .set_scheduled_start_time(self.start_date.lock().unwrap().timestamp());
+            .set_scheduled_start_time(lock_or_recover(&self.start_date).timestamp());
         self.filters
-            .set_wall_time(self.end_date.lock().unwrap().timestamp());
+            .set_wall_time(lock_or_recover(&self.end_date).timestamp());
 
         self.filter_jobs();
     }
@@ -570,6 +259,19 @@ impl ApplicationContext {
         }
     }
 
+    /// Load the cluster -> site mapping from the given file (a JSON object such as
+    /// `{"dahu": "grenoble"}`), returning an empty map on error. The file is optional, but a
+    /// malformed one is reported since all the sites it maps would silently vanish.
+    fn load_cluster_site_map(file_path: &str) -> HashMap<String, String> {
+        match std::fs::read_to_string(file_path) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|error| {
+                println!("Warning: ignoring {} ({})", file_path, error);
+                HashMap::new()
+            }),
+            Err(_) => HashMap::new(),
+        }
+    }
+
     /// Add a new cluster preset or update an existing one with the same name.
     /// If the operation succeeds we also write the updated list to disk.
     pub fn add_or_update_preset(&mut self, preset: ClusterPreset) {
@@ -611,13 +313,27 @@ impl ApplicationContext {
         owners
     }
 
+    /// Sorted Grid5000 sites of the known clusters (see `site_for_cluster_name`).
+    pub fn get_unique_sites(&self) -> Vec<String> {
+        let mut sites: Vec<String> = self
+            .all_clusters
+            .iter()
+            .filter_map(|cluster| {
+                site_for_cluster_name(&cluster.name, &self.all_clusters, &self.strata_by_host, &self.cluster_site_map)
+            })
+            .collect();
+        sites.sort();
+        sites.dedup();
+        sites
+    }
+
     /*
      * Applies the current filters to all_jobs and updates filtered_jobs
      * This method handles all filtering logic including:
      * - Job owner filtering
      * - Job state filtering
      * - Time range filtering
-     * - Cluster resource filtering
+     * - Cluster resource filtering (orphan jobs, see UNKNOWN_GROUP, are kept)
      */
     pub fn filter_jobs(&mut self) {
         // Determine the selected clusters from the preset, if any
@@ -625,69 +341,141 @@ impl ApplicationContext {
             .and_then(|preset_name| self.cluster_presets.iter().find(|p| p.name == *preset_name))
             .map(|preset| preset.clusters.clone());
 
-        self.filtered_jobs = self
+        // Malformed jobs (timestamps at epoch 0 or far in the future) would wreck the Gantt
+        // scale: they are left out and counted. The synthetic job 0 is exempt.
+        let implausible_jobs = self
+            .all_jobs
+            .iter()
+            .filter(|job| job.id != 0 && !job.has_plausible_times())
+            .count();
+        if implausible_jobs != self.implausible_jobs && implausible_jobs > 0 {
+            println!("Warning: {} jobs ignored (implausible timestamps)", implausible_jobs);
+        }
+        self.implausible_jobs = implausible_jobs;
+
+        // Clusters of the selected sites: a job spanning several sites is kept if any matches
+        let site_cluster_names: Option<HashSet<&str>> = self.filters.sites.as_ref().map(|sites| {
+            self.all_clusters
+                .iter()
+                .filter(|cluster| {
+                    site_for_cluster_name(&cluster.name, &self.all_clusters, &self.strata_by_host, &self.cluster_site_map)
+                        .is_some_and(|site| sites.contains(&site))
+                })
+                .map(|cluster| cluster.name.as_str())
+                .collect()
+        });
+
+        let now_s = Local::now().timestamp();
+        let filtered_jobs: Vec<Job> = self
             .all_jobs
             .iter()
+            .filter(|job| job.id == 0 || job.has_plausible_times())
+            .filter(|job| job_matches(job, &self.filters, now_s))
+            .filter(|job| {
+                job.id == 0
+                    || is_orphan_job(job)
+                    || selected_cluster_names.as_ref().is_none_or(|cluster_names| {
+                        cluster_names.iter().any(|cluster_name| job.clusters.contains(cluster_name))
+                    })
+            })
             .filter(|job| {
                 job.id == 0
-                    || (self
-                        .filters
-                        .owners
-                        .as_ref()
-                        .map_or(true, |owners| owners.contains(&job.owner)))
-                        && (self
-                            .filters
-                            .states
-                            .as_ref()
-                            .map_or(true, |states| states.contains(&job.state)))
-                        && (((self
-                            .filters
-                            .scheduled_start_time
-                            .map_or(true, |time| time <= job.scheduled_start))
-                            && (self
-                                .filters
-                                .wall_time
-                                .map_or(true, |time| time >= job.scheduled_start)))
-                            || ((self
-                                .filters
-                                .scheduled_start_time
-                                .map_or(true, |time| time <= job.get_end_date()))
-                                && (self
-                                    .filters
-                                    .wall_time
-                                    .map_or(true, |time| time >= job.get_end_date())))
-                            || ((self
-                                .filters
-                                .scheduled_start_time
-                                .map_or(true, |time| time >= job.start_time))
-                                && (self
-                                    .filters
-                                    .wall_time
-                                    .map_or(true, |time| time <= job.get_end_date()))))
-                        && (selected_cluster_names.is_none() || {
-                            let cluster_names = selected_cluster_names.as_ref().unwrap();
-                            cluster_names.iter().any(|cluster_name| job.clusters.contains(cluster_name))
-                        })
+                    || is_orphan_job(job)
+                    || site_cluster_names.as_ref().is_none_or(|cluster_names| {
+                        job.clusters.iter().any(|cluster_name| cluster_names.contains(cluster_name.as_str()))
+                    })
             })
             .cloned() // Clone filtred jobs here
             .collect();
+
+        // Called on every frame: the generation only moves when the kept jobs changed
+        let same_jobs = self.filtered_jobs_source == self.jobs_generation
+            && self.filtered_jobs.iter().map(|job| job.id).eq(filtered_jobs.iter().map(|job| job.id));
+        if !same_jobs {
+            self.filtered_jobs_source = self.jobs_generation;
+            self.filtered_jobs_generation += 1;
+        }
+        self.filtered_jobs = filtered_jobs;
+    }
+}
+
+/* Sets the state of each host, then of each cluster, to the state most of their resources
+ * (hosts) have. Only the clusters of `updated_clusters` are recomputed, the others keep the
+ * states computed at a previous refresh.
+ */
+fn update_aggregate_states(clusters: &mut [Cluster], updated_clusters: &HashSet<String>) {
+    for cluster in clusters
+        .iter_mut()
+        .filter(|cluster| updated_clusters.contains(&cluster.name))
+    {
+        for host in cluster.hosts.iter_mut() {
+            host.state = host.resource_state_counts().majority();
+        }
+
+        let mut host_states = ResourceStateCounts::default();
+        for host in cluster.hosts.iter() {
+            host_states.add(&host.state);
+        }
+        cluster.state = host_states.majority();
+    }
+}
+
+/* Returns the resources of a job missing from the cluster tree (`known_resources`)
+ * The job is tagged with UNKNOWN_GROUP as cluster and host when it has some and `include` is
+ * set, untagged otherwise. The synthetic job 0 is never an orphan.
+ */
+fn tag_orphan_job(job: &mut Job, known_resources: &HashSet<u32>, include: bool) -> Vec<u32> {
+    job.clusters.retain(|name| name != UNKNOWN_GROUP);
+    job.hosts.retain(|name| name != UNKNOWN_GROUP);
+    if job.id == 0 {
+        return Vec::new();
     }
+
+    let orphans: Vec<u32> = job
+        .assigned_resources
+        .iter()
+        .copied()
+        .filter(|id| !known_resources.contains(id))
+        .collect();
+    if include && !orphans.is_empty() {
+        job.clusters.push(UNKNOWN_GROUP.to_string());
+        job.hosts.push(UNKNOWN_GROUP.to_string());
+    }
+    orphans
+}
+
+fn is_orphan_job(job: &Job) -> bool {
+    job.clusters.iter().any(|name| name == UNKNOWN_GROUP)
 }
 
 impl Default for ApplicationContext {
-    // Creates a default ApplicationContext with initial values and sets up the background
-    // data refresh mechanism.
+    // Creates a default ApplicationContext on the default window (now ± 1 hour)
     fn default() -> Self {
+        let (start, end) = default_window(Local::now());
+        Self::with_window(start, end)
+    }
+}
+
+impl ApplicationContext {
+    // Creates an ApplicationContext on the [start, end] window with initial values and sets up
+    // the background data refresh mechanism.
+    pub fn with_window(start: DateTime<Local>, end: DateTime<Local>) -> Self {
         let (jobs_sender, jobs_receiver) = channel();
         let (resources_sender, resources_receiver) = channel();
 
-        let now: DateTime<Local> = Local::now();
         let mut context = Self {
             all_jobs: Vec::new(),
             all_clusters: Vec::new(),
 
             swap_all_jobs: Vec::new(),
             swap_all_clusters: Vec::new(),
+            resources_fingerprint: None,
+            cluster_fingerprints: HashMap::new(),
+            jobs_pending: false,
+            jobs_generation: 0,
+            filtered_jobs_source: 0,
+            filtered_jobs_generation: 0,
+            clusters_generation: 0,
 
             jobs_receiver: jobs_receiver,
             jobs_sender: jobs_sender,
@@ -696,11 +484,17 @@ impl Default for ApplicationContext {
             user_connected: None,
 
             strata_by_host: HashMap::new(),
+            gpu_count_by_host: HashMap::new(),
+            cluster_site_map: HashMap::new(),
 
             filtered_jobs: Vec::new(),
+            implausible_jobs: 0,
+            orphan_jobs: 0,
+            include_orphan_jobs: true,
+            last_data_update: None,
             filters: JobFilters::default(),
-            start_date: Arc::new(Mutex::new(now - chrono::Duration::hours(1))),
-            end_date: Arc::new(Mutex::new(now + chrono::Duration::hours(1))),
+            start_date: Arc::new(Mutex::new(start)),
+            end_date: Arc::new(Mutex::new(end)),
             view_type: ViewType::Gantt,
             is_loading: false,
             is_refreshing: Arc::new(Mutex::new(false)),
@@ -709,13 +503,131 @@ impl Default for ApplicationContext {
             font_size: 16,
             see_all_jobs: false,
 
+            selected_job_id: None,
+            command_preview: None,
+
             theme_toggle_requested: false,
+            resources_panel_open: false,
             cluster_presets: Vec::new(),
         };
         
         // populate presets from disk if available
         context.cluster_presets = ApplicationContext::load_presets_from_file("presets.json");
+        context.cluster_site_map = ApplicationContext::load_cluster_site_map("cluster_sites.json");
         context.update_periodically();
         context
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::data_structure::cluster::test_cluster;
+    use crate::models::data_structure::job::test_job;
+    use crate::models::data_structure::resource::ResourceState;
+
+    // Marks every resource of the cluster Dead, without touching the computed states
+    fn kill_resources(cluster: &mut Cluster) {
+        let resources = cluster
+            .hosts
+            .iter_mut()
+            .flat_map(|host| host.cpus.iter_mut())
+            .flat_map(|cpu| cpu.resources.iter_mut());
+        for resource in resources {
+            resource.state = ResourceState::Dead;
+        }
+    }
+
+    #[test]
+    fn update_aggregate_states_only_recomputes_updated_clusters() {
+        let mut clusters = vec![
+            test_cluster("dahu", &[("dahu-1", &[1, 2]), ("dahu-2", &[3])]),
+            test_cluster("gros", &[("gros-1", &[4])]),
+        ];
+        for cluster in clusters.iter_mut() {
+            kill_resources(cluster);
+        }
+
+        update_aggregate_states(&mut clusters, &HashSet::from(["dahu".to_string()]));
+
+        assert_eq!(clusters[0].state, ResourceState::Dead);
+        assert!(clusters[0].hosts.iter().all(|host| host.state == ResourceState::Dead));
+        // Unchanged cluster: the previously computed states are kept
+        assert_eq!(clusters[1].state, ResourceState::Alive);
+        assert_eq!(clusters[1].hosts[0].state, ResourceState::Alive);
+    }
+
+    #[test]
+    fn update_aggregate_states_takes_the_majority_of_the_hosts() {
+        let mut clusters = vec![test_cluster(
+            "dahu",
+            &[("dahu-1", &[1, 2]), ("dahu-2", &[3]), ("dahu-3", &[4])],
+        )];
+        let dead_host = &mut clusters[0].hosts[0];
+        dead_host.cpus[0].resources[0].state = ResourceState::Dead;
+        dead_host.cpus[0].resources[1].state = ResourceState::Dead;
+
+        update_aggregate_states(&mut clusters, &HashSet::from(["dahu".to_string()]));
+
+        assert_eq!(clusters[0].hosts[0].state, ResourceState::Dead);
+        assert_eq!(clusters[0].hosts[1].state, ResourceState::Alive);
+        assert_eq!(clusters[0].state, ResourceState::Alive);
+    }
+
+    // Job on dahu-1 holding the known resource 1 and the unknown resource 99
+    fn job_with_orphan_resource(id: u32) -> Job {
+        Job {
+            clusters: vec!["dahu".to_string()],
+            hosts: vec!["dahu-1".to_string()],
+            assigned_resources: vec![1, 99],
+            ..test_job(id, 0, 100)
+        }
+    }
+
+    #[test]
+    fn orphan_resources_tag_the_job_when_included() {
+        let mut job = job_with_orphan_resource(1);
+
+        let orphans = tag_orphan_job(&mut job, &HashSet::from([1]), true);
+
+        assert_eq!(orphans, vec![99]);
+        assert_eq!(job.clusters, vec!["dahu", UNKNOWN_GROUP]);
+        assert_eq!(job.hosts, vec!["dahu-1", UNKNOWN_GROUP]);
+        assert!(is_orphan_job(&job));
+    }
+
+    #[test]
+    fn orphan_resources_are_reported_without_tag_when_excluded() {
+        let mut job = job_with_orphan_resource(1);
+
+        let orphans = tag_orphan_job(&mut job, &HashSet::from([1]), false);
+
+        assert_eq!(orphans, vec![99]);
+        assert_eq!(job.clusters, vec!["dahu"]);
+        assert_eq!(job.hosts, vec!["dahu-1"]);
+        assert!(!is_orphan_job(&job));
+    }
+
+    #[test]
+    fn retagging_removes_the_previous_unknown_tag() {
+        let mut job = job_with_orphan_resource(1);
+        tag_orphan_job(&mut job, &HashSet::from([1]), true);
+
+        // Resource 99 showed up in the cluster tree since the last refresh
+        let orphans = tag_orphan_job(&mut job, &HashSet::from([1, 99]), true);
+
+        assert!(orphans.is_empty());
+        assert_eq!(job.clusters, vec!["dahu"]);
+        assert!(!is_orphan_job(&job));
+    }
+
+    #[test]
+    fn background_job_is_never_an_orphan() {
+        let mut job = job_with_orphan_resource(0);
+
+        let orphans = tag_orphan_job(&mut job, &HashSet::from([1]), true);
+
+        assert!(orphans.is_empty());
+        assert!(!is_orphan_job(&job));
+    }
+}
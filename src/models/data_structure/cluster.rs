@@ -1,6 +1,6 @@
 use crate::models::data_structure::host::Host;
 
-use super::resource::ResourceState;
+use super::resource::{ResourceState, ResourceStateCounts};
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct Cluster {
@@ -9,3 +9,49 @@ pub struct Cluster {
     pub resource_ids: Vec<u32>,
     pub state: ResourceState,
 }
+
+impl Cluster {
+    // Number of resources of the cluster in each state, over all its hosts
+    pub fn resource_state_counts(&self) -> ResourceStateCounts {
+        let mut counts = ResourceStateCounts::default();
+        for resource in self.hosts.iter().flat_map(|host| host.cpus.iter()).flat_map(|cpu| cpu.resources.iter()) {
+            counts.add(&resource.state);
+        }
+        counts
+    }
+}
+
+// Cluster for the unit tests: each host is given by its name and the ids of its OAR resources
+// (Alive, one thread each), all on a single cpu
+#[cfg(test)]
+pub fn test_cluster(name: &str, hosts: &[(&str, &[u32])]) -> Cluster {
+    use crate::models::data_structure::cpu::Cpu;
+    use crate::models::data_structure::resource::Resource;
+
+    let hosts: Vec<Host> = hosts
+        .iter()
+        .map(|(host_name, resource_ids)| Host {
+            name: host_name.to_string(),
+            cpus: vec![Cpu {
+                name: format!("{}-cpu0", host_name),
+                resources: resource_ids
+                    .iter()
+                    .map(|&id| Resource { id, state: ResourceState::Alive, thread_count: 1 })
+                    .collect(),
+                chassis: String::new(),
+                core_count: resource_ids.len() as i32,
+                cpufreq: 0.0,
+                resource_ids: resource_ids.to_vec(),
+            }],
+            network_address: host_name.to_string(),
+            resource_ids: resource_ids.to_vec(),
+            state: ResourceState::Alive,
+        })
+        .collect();
+    Cluster {
+        name: name.to_string(),
+        resource_ids: hosts.iter().flat_map(|host| host.resource_ids.clone()).collect(),
+        hosts,
+        state: ResourceState::Alive,
+    }
+}
@@ -1,10 +1,44 @@
-use super::job::JobState;
+use super::job::{Job, JobState};
+use crate::views::components::dashboard_components::job_table_sorting::JobSortable;
+use serde::{Deserialize, Serialize};
+
+// Besteffort (preemptible) jobs kept by the filters, see `Job::is_besteffort`
+#[derive(Default, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum BesteffortFilter {
+    #[default]
+    All,
+    Only,
+    Exclude,
+}
+
+impl BesteffortFilter {
+    pub fn matches(&self, job: &Job) -> bool {
+        match self {
+            BesteffortFilter::All => true,
+            BesteffortFilter::Only => job.is_besteffort(),
+            BesteffortFilter::Exclude => !job.is_besteffort(),
+        }
+    }
+
+    // Quick toggle: selecting the active value again goes back to all jobs
+    pub fn toggled(self, value: BesteffortFilter) -> BesteffortFilter {
+        if self == value {
+            BesteffortFilter::All
+        } else {
+            value
+        }
+    }
+}
 
 #[derive(Default, Debug, Clone)]
 
 pub struct JobFilters {
     pub owners: Option<Vec<String>>,
     pub states: Option<Vec<JobState>>,
+    pub hosts: Option<Vec<String>>,
+    pub sites: Option<Vec<String>>,
+    pub besteffort: BesteffortFilter,
+    pub command: Option<String>,
     pub scheduled_start_time: Option<i64>,
     pub wall_time: Option<i64>,
     pub selected_preset: Option<String>,
@@ -16,10 +50,26 @@ impl JobFilters {
         Self::default()
     }
 
+    /// True if both filters select the same jobs apart from the time window
+    /// (owners, states, hosts, sites, besteffort, command and cluster preset).
+    pub fn same_selection(&self, other: &JobFilters) -> bool {
+        self.owners == other.owners
+            && self.states == other.states
+            && self.hosts == other.hosts
+            && self.sites == other.sites
+            && self.besteffort == other.besteffort
+            && self.command == other.command
+            && self.selected_preset == other.selected_preset
+    }
+
     pub fn copy(filter: &JobFilters) -> Self {
         JobFilters {
             owners: filter.owners.clone(),
             states: filter.states.clone(),
+            hosts: filter.hosts.clone(),
+            sites: filter.sites.clone(),
+            besteffort: filter.besteffort,
+            command: filter.command.clone(),
             scheduled_start_time: filter.scheduled_start_time,
             wall_time: filter.wall_time,
             selected_preset: filter.selected_preset.clone(),
@@ -34,6 +84,22 @@ impl JobFilters {
         self.states = states;
     }
 
+    pub fn set_hosts(&mut self, hosts: Option<Vec<String>>) {
+        self.hosts = hosts;
+    }
+
+    pub fn set_sites(&mut self, sites: Option<Vec<String>>) {
+        self.sites = sites;
+    }
+
+    pub fn set_besteffort(&mut self, besteffort: BesteffortFilter) {
+        self.besteffort = besteffort;
+    }
+
+    pub fn set_command(&mut self, command: Option<String>) {
+        self.command = command;
+    }
+
     pub fn set_scheduled_start_time(&mut self, scheduled_start_time: i64) {
         self.scheduled_start_time = Some(scheduled_start_time);
     }
@@ -50,3 +116,127 @@ impl JobFilters {
         *self = Self::default();
     }
 }
+
+/*
+ * Returns true if the job passes the owner, state, host, besteffort, command and time range filters
+ * The synthetic job 0 ("all_resources") always matches. A job matches the time range
+ * [scheduled_start_time, wall_time] (each bound optional) when:
+ * - its scheduled start lies inside the range, or
+ * - its end date lies inside the range, or
+ * - it started before the range and ends after it (straddling job)
+ * The end date is the stop time once the job is over. A started job with no stop time is
+ * still running: its end is at least `now_s`, so a job overrunning its scheduled
+ * start + walltime stays in the current window.
+ * Cluster preset filtering depends on the presets and is applied by the caller.
+ */
+pub fn job_matches(job: &Job, filters: &JobFilters, now_s: i64) -> bool {
+    if job.id == 0 {
+        return true;
+    }
+
+    let after_start = |time: i64| filters.scheduled_start_time.is_none_or(|start| start <= time);
+    let before_end = |time: i64| filters.wall_time.is_none_or(|end| end >= time);
+    let end_date = if job.stop_time == 0 && job.start_time > 0 {
+        job.get_end_date().max(job.scheduled_start + job.walltime).max(now_s)
+    } else {
+        job.get_end_date()
+    };
+
+    let owner_matches = filters.owners.as_ref().is_none_or(|owners| owners.contains(&job.owner));
+    let state_matches = filters.states.as_ref().is_none_or(|states| states.contains(&job.state));
+    let command_matches = filters
+        .command
+        .as_ref()
+        .is_none_or(|command| command_contains(&job.command, command));
+    let host_matches = filters
+        .hosts
+        .as_ref()
+        .is_none_or(|hosts| job.hosts.iter().any(|host| hosts.contains(host)));
+    let besteffort_matches = filters.besteffort.matches(job);
+    let starts_inside = after_start(job.scheduled_start) && before_end(job.scheduled_start);
+    let ends_inside = after_start(end_date) && before_end(end_date);
+    let straddles = filters.scheduled_start_time.is_none_or(|start| start >= job.start_time)
+        && filters.wall_time.is_none_or(|end| end <= end_date);
+
+    owner_matches
+        && state_matches
+        && host_matches
+        && besteffort_matches
+        && command_matches
+        && (starts_inside || ends_inside || straddles)
+}
+
+// Case-insensitive substring test shared by the command filter and its live preview
+pub fn command_contains(command: &str, query: &str) -> bool {
+    command.to_lowercase().contains(&query.to_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::data_structure::job::test_job;
+
+    const NOW_S: i64 = 1_500;
+
+    // Time range filter on [1000, 2000]
+    fn window_filters() -> JobFilters {
+        JobFilters {
+            scheduled_start_time: Some(1_000),
+            wall_time: Some(2_000),
+            ..JobFilters::default()
+        }
+    }
+
+    // Job started on `start_s` and still running (no stop time yet)
+    fn running_job(start_s: i64, walltime: i64) -> Job {
+        Job {
+            state: JobState::Running,
+            stop_time: 0,
+            ..test_job(1, start_s, walltime)
+        }
+    }
+
+    #[test]
+    fn job_straddling_the_window_matches() {
+        assert!(job_matches(&test_job(1, 500, 2_000), &window_filters(), NOW_S));
+    }
+
+    #[test]
+    fn job_inside_the_window_matches() {
+        assert!(job_matches(&test_job(1, 1_200, 300), &window_filters(), NOW_S));
+        // Only the start, or only the end, inside the window
+        assert!(job_matches(&test_job(1, 1_800, 1_000), &window_filters(), NOW_S));
+        assert!(job_matches(&test_job(1, 500, 600), &window_filters(), NOW_S));
+    }
+
+    #[test]
+    fn job_outside_the_window_does_not_match() {
+        assert!(!job_matches(&test_job(1, 100, 400), &window_filters(), NOW_S));
+        assert!(!job_matches(&test_job(1, 3_000, 500), &window_filters(), NOW_S));
+    }
+
+    #[test]
+    fn running_job_without_stop_time_matches_the_current_window() {
+        assert!(job_matches(&running_job(500, 2_000), &window_filters(), NOW_S));
+        assert!(!job_matches(&running_job(2_500, 1_000), &window_filters(), NOW_S));
+    }
+
+    #[test]
+    fn background_job_always_matches() {
+        assert!(job_matches(&test_job(0, 0, 0), &window_filters(), NOW_S));
+    }
+
+    #[test]
+    fn running_job_past_its_walltime_is_kept_until_now() {
+        // Scheduled end (100 + 200) is long past, but the job is still running at NOW_S
+        let overrunning = running_job(100, 200);
+        assert!(job_matches(&overrunning, &window_filters(), NOW_S));
+
+        let window_before_start = JobFilters {
+            scheduled_start_time: Some(0),
+            wall_time: Some(50),
+            ..JobFilters::default()
+        };
+        assert!(!job_matches(&overrunning, &window_before_start, NOW_S));
+    }
+}
@@ -1,5 +1,5 @@
 use crate::models::data_structure::cpu::Cpu;
-use crate::models::data_structure::resource::ResourceState;
+use crate::models::data_structure::resource::{ResourceState, ResourceStateCounts};
 
 #[derive(Clone, Debug, PartialEq)]
 
@@ -10,3 +10,14 @@ pub struct Host {
     pub resource_ids: Vec<u32>,
     pub state: ResourceState,
 }
+
+impl Host {
+    // Number of resources of the host in each state
+    pub fn resource_state_counts(&self) -> ResourceStateCounts {
+        let mut counts = ResourceStateCounts::default();
+        for resource in self.cpus.iter().flat_map(|cpu| cpu.resources.iter()) {
+            counts.add(&resource.state);
+        }
+        counts
+    }
+}
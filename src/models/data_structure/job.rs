@@ -120,6 +120,13 @@ impl JobState {
 
 use crate::views::components::dashboard_components::job_table_sorting::JobSortable;
 
+// OAR queue of the preemptible jobs
+pub const BESTEFFORT_QUEUE: &str = "besteffort";
+
+// Plausible range for job timestamps: 2000-01-01 to 3000-01-01 (UTC)
+const MIN_PLAUSIBLE_TIMESTAMP: i64 = 946_684_800;
+const MAX_PLAUSIBLE_TIMESTAMP: i64 = 32_503_680_000;
+
 #[derive(Clone, Debug)]
 
 pub struct Job {
@@ -224,6 +231,30 @@ impl Job {
         println!("Host: {:?}", self.hosts);
     }
 
+    // False for malformed jobs whose times would put them at epoch 0 or far in the future:
+    // scheduled start (and end) outside 2000..3000, negative walltime, or a start / stop time
+    // set (non zero) outside that range.
+    pub fn has_plausible_times(&self) -> bool {
+        let plausible = |ts: i64| (MIN_PLAUSIBLE_TIMESTAMP..=MAX_PLAUSIBLE_TIMESTAMP).contains(&ts);
+        plausible(self.scheduled_start)
+            && self.walltime >= 0
+            && plausible(self.scheduled_start.saturating_add(self.walltime))
+            && (self.start_time == 0 || plausible(self.start_time))
+            && (self.stop_time == 0 || plausible(self.stop_time))
+    }
+
+    // Preemptible job, submitted to the besteffort queue
+    pub fn is_besteffort(&self) -> bool {
+        self.queue == BESTEFFORT_QUEUE
+    }
+
+    // Time between the scheduled start and the actual start (negative when the job started early).
+    // None when one of the two times is unknown (0).
+    pub fn scheduling_delay(&self) -> Option<i64> {
+        (self.start_time > 0 && self.scheduled_start > 0)
+            .then(|| self.start_time - self.scheduled_start)
+    }
+
     // Based on gantt color return a tuple of two colors (the second one is darker)
     pub fn get_gantt_color(&self) -> (egui::Color32, egui::Color32) {
         let r = self.gantt_color.r() as f32;
@@ -277,4 +308,28 @@ impl Job {
             ResourceState::Unknown
         };
     }
-}
\ No newline at end of file
+}
+// Minimal job for the unit tests: running on `scheduled_start` for `walltime` seconds, with no
+// resource, host or cluster
+#[cfg(test)]
+pub fn test_job(id: u32, scheduled_start: i64, walltime: i64) -> Job {
+    Job {
+        id,
+        owner: String::new(),
+        state: JobState::Terminated,
+        command: String::new(),
+        walltime,
+        message: None,
+        queue: String::new(),
+        assigned_resources: Vec::new(),
+        scheduled_start,
+        submission_time: 0,
+        start_time: scheduled_start,
+        stop_time: scheduled_start + walltime,
+        exit_code: None,
+        gantt_color: egui::Color32::TRANSPARENT,
+        clusters: Vec::new(),
+        hosts: Vec::new(),
+        main_resource_state: ResourceState::Unknown,
+    }
+}
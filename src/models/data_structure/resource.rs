@@ -19,6 +19,39 @@ impl Clone for ResourceState {
     }
 }
 
+/// Number of resources (or hosts) in each state.
+/// Gives the majority state of a host or cluster, and the state tallies of the Gantt tooltips.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ResourceStateCounts {
+    pub alive: usize,
+    pub dead: usize,
+    pub absent: usize,
+}
+
+impl ResourceStateCounts {
+    pub fn add(&mut self, state: &ResourceState) {
+        match state {
+            ResourceState::Dead => self.dead += 1,
+            ResourceState::Alive => self.alive += 1,
+            ResourceState::Absent => self.absent += 1,
+            ResourceState::Unknown => (),
+        }
+    }
+
+    /// State most counted. Ties go to Dead, then Absent; no count at all gives Dead.
+    pub fn majority(&self) -> ResourceState {
+        if self.dead >= self.alive && self.dead >= self.absent {
+            ResourceState::Dead
+        } else if self.absent >= self.dead && self.absent >= self.alive {
+            ResourceState::Absent
+        } else if self.alive > self.dead && self.alive > self.absent {
+            ResourceState::Alive
+        } else {
+            ResourceState::Unknown
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct Resource {
     pub id: u32,
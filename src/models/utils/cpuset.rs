@@ -0,0 +1,130 @@
+// Helpers for OAR `cpuset` values and resource id lists, shared by the resource cache,
+// the Gantt tooltips and the job details window.
+
+use range_set_blaze::RangeSetBlaze;
+use serde_json::Value;
+
+/// Extracts every non-negative integer found in a string ("0,2-3" => [0, 2, 3]).
+/// Numbers above `i32::MAX` are dropped.
+fn extract_ints_from_str(s: &str) -> Vec<i32> {
+    let mut out: Vec<i32> = Vec::new();
+    // None once the current digit run is out of range
+    let mut cur: Option<i32> = Some(0);
+    let mut in_num = false;
+    for ch in s.chars() {
+        if let Some(d) = ch.to_digit(10) {
+            in_num = true;
+            cur = cur.and_then(|v| v.checked_mul(10)).and_then(|v| v.checked_add(d as i32));
+        } else if in_num {
+            out.extend(cur);
+            cur = Some(0);
+            in_num = false;
+        }
+    }
+    if in_num {
+        out.extend(cur);
+    }
+    out
+}
+
+/// Lists the cpu indices of a cpuset value (number, string or nested arrays of those).
+/// Other JSON values and negative numbers are ignored.
+pub fn parse_cpuset(v: &Value) -> Vec<i32> {
+    match v {
+        Value::Number(n) => n
+            .as_i64()
+            .filter(|i| (0..=i32::MAX as i64).contains(i))
+            .map(|i| vec![i as i32])
+            .unwrap_or_default(),
+        Value::String(s) => extract_ints_from_str(s),
+        Value::Array(arr) => arr.iter().flat_map(parse_cpuset).collect(),
+        Value::Null | Value::Bool(_) | Value::Object(_) => Vec::new(),
+    }
+}
+
+/// Formats cpu indices as compact ranges, Grid5000 style ("0-3, 8, 10-11").
+/// The values are sorted and deduplicated in place; an empty list gives an empty string.
+pub fn format_cpuset_ranges(values: &mut Vec<i32>) -> String {
+    values.sort_unstable();
+    values.dedup();
+
+    let range_set: RangeSetBlaze<i32> = values.iter().copied().collect();
+    range_set
+        .ranges()
+        .map(|range| {
+            if range.start() == range.end() {
+                range.start().to_string()
+            } else {
+                format!("{}-{}", range.start(), range.end())
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Formats OAR resource ids as compact ranges ("1-4, 9"), see `format_cpuset_ranges`.
+pub fn format_resource_ids(ids: &[u32]) -> String {
+    let mut values: Vec<i32> = ids.iter().filter_map(|&id| i32::try_from(id).ok()).collect();
+    format_cpuset_ranges(&mut values)
+}
+
+/// Shortens a formatted range list to at most `max_chars` characters, cutting after a whole
+/// range and ending with "…". Short lists are returned unchanged.
+pub fn truncate_ranges(ranges: &str, max_chars: usize) -> String {
+    if ranges.chars().count() <= max_chars {
+        return ranges.to_string();
+    }
+    let head: String = ranges.chars().take(max_chars).collect();
+    match head.rfind(", ") {
+        Some(cut) => format!("{}, …", &head[..cut]),
+        None => format!("{}…", head),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parse_cpuset_reads_numbers_strings_and_arrays() {
+        assert_eq!(parse_cpuset(&json!(3)), vec![3]);
+        assert_eq!(parse_cpuset(&json!("0,2-3")), vec![0, 2, 3]);
+        assert_eq!(parse_cpuset(&json!([1, "4", [5, "6,7"]])), vec![1, 4, 5, 6, 7]);
+    }
+
+    #[test]
+    fn parse_cpuset_ignores_other_values() {
+        assert!(parse_cpuset(&json!(-1)).is_empty());
+        assert!(parse_cpuset(&json!(1.5)).is_empty());
+        assert!(parse_cpuset(&json!(null)).is_empty());
+        assert!(parse_cpuset(&json!(true)).is_empty());
+        assert!(parse_cpuset(&json!({"cpu": 1})).is_empty());
+        assert!(parse_cpuset(&json!("")).is_empty());
+    }
+
+    #[test]
+    fn parse_cpuset_drops_out_of_range_numbers() {
+        assert!(parse_cpuset(&json!("99999999999999999999")).is_empty());
+        assert_eq!(parse_cpuset(&json!("1,99999999999999999999,2")), vec![1, 2]);
+        assert_eq!(parse_cpuset(&json!("2147483647,2147483648")), vec![i32::MAX]);
+        assert!(parse_cpuset(&json!(i64::MAX)).is_empty());
+    }
+
+    #[test]
+    fn format_cpuset_ranges_compacts_sorted_values() {
+        let mut values = vec![11, 3, 0, 1, 2, 8, 10, 2];
+        assert_eq!(format_cpuset_ranges(&mut values), "0-3, 8, 10-11");
+        assert_eq!(values, vec![0, 1, 2, 3, 8, 10, 11]);
+        assert_eq!(format_cpuset_ranges(&mut vec![]), "");
+        assert_eq!(format_cpuset_ranges(&mut vec![5]), "5");
+    }
+
+    #[test]
+    fn format_resource_ids_and_truncate_ranges() {
+        assert_eq!(format_resource_ids(&[4, 1, 2, 3, 9]), "1-4, 9");
+        assert_eq!(truncate_ranges("1-4, 9", 20), "1-4, 9");
+        assert_eq!(truncate_ranges("1-4, 9, 12-20, 30", 10), "1-4, 9, …");
+        assert_eq!(truncate_ranges("123456789", 4), "1234…");
+    }
+}
@@ -12,4 +12,26 @@ pub fn format_timestamp(ts: i64) -> String {
             "Invalid timestamp".to_string()
         }
     }
-}
\ No newline at end of file
+}
+// Formats a duration in seconds as "2d 3h 05m 10s", leading zero units omitted ("42s", "5m 03s").
+// Negative durations get a leading "-".
+pub fn format_duration(seconds: i64) -> String {
+    let sign = if seconds < 0 { "-" } else { "" };
+    let total = seconds.unsigned_abs();
+    let (days, hours, minutes, secs) = (
+        total / 86_400,
+        total % 86_400 / 3600,
+        total % 3600 / 60,
+        total % 60,
+    );
+    let text = if days > 0 {
+        format!("{}d {}h {:02}m {:02}s", days, hours, minutes, secs)
+    } else if hours > 0 {
+        format!("{}h {:02}m {:02}s", hours, minutes, secs)
+    } else if minutes > 0 {
+        format!("{}m {:02}s", minutes, secs)
+    } else {
+        format!("{}s", secs)
+    };
+    format!("{}{}", sign, text)
+}
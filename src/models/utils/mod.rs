@@ -1,10 +1,16 @@
 // Module: utils
 
+pub mod cpuset;
 pub mod date_converter;
 pub mod parser;
+pub mod resource_tree;
+pub mod startup_window;
 pub mod updater;
 pub mod utils;
 pub mod secret;
 
 #[cfg(target_arch = "wasm32")]
-pub mod mocker;
\ No newline at end of file
+pub mod mocker;
+
+#[cfg(feature = "debug-tools")]
+pub mod synthetic;
\ No newline at end of file
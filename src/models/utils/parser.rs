@@ -10,6 +10,21 @@ use std::process::Command;
 #[cfg(not(target_arch = "wasm32"))]
 use chrono::{DateTime, Local};
 
+// OAR frontend queried over SSH by the background fetch
+pub const OAR_FRONTEND: &str = "grenoble.g5k";
+
+/**
+ * Short name of the data source used by the background fetch: the OAR frontend, or "mock"
+ * for the web build, which has no backend
+ */
+pub fn data_source_name() -> &'static str {
+    if cfg!(target_arch = "wasm32") {
+        "mock"
+    } else {
+        OAR_FRONTEND
+    }
+}
+
 /**
  * Test SSH connection to the specified host
  */
@@ -41,7 +56,7 @@ pub fn get_current_jobs_for_period(start_date: DateTime<Local>, end_date: DateTi
     let end_date = end_date + chrono::Duration::seconds(margin);
 
     // Test connection first
-    if test_connection("grenoble.g5k") != Ok(()) {
+    if test_connection(OAR_FRONTEND) != Ok(()) {
         return false;
     }
 
@@ -54,7 +69,7 @@ pub fn get_current_jobs_for_period(start_date: DateTime<Local>, end_date: DateTi
     // Execute SSH command to generate JSON file and redirect output
     let ssh_status = Command::new("ssh")
         .args([
-            "grenoble.g5k",
+            OAR_FRONTEND,
             &format!(
                 "oarstat -J -g \"{}, {}\"",
                 start_date.format("%Y-%m-%d %H:%M:%S"),
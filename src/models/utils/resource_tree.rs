@@ -0,0 +1,321 @@
+// Preparation of the resources received from OAR, done in the refresh thread (see
+// `updater.rs`) so that the UI thread only receives finished structures: the
+// `Cluster -> Host -> Cpu -> Resource` tree, the host metadata index and the fingerprints
+// used to detect unchanged data.
+
+use crate::models::data_structure::cluster::Cluster;
+use crate::models::data_structure::cpu::Cpu;
+use crate::models::data_structure::host::Host;
+use crate::models::data_structure::resource::{Resource, ResourceState};
+use crate::models::data_structure::strata::Strata;
+use crate::models::utils::cpuset::parse_cpuset;
+use crate::models::utils::utils::short_host_label;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+/// Resources of one refresh, ready to be swapped into the `ApplicationContext`.
+pub struct ResourceUpdate {
+    // Fingerprint of the whole resource list, and of the records of each cluster
+    pub fingerprint: u64,
+    pub cluster_fingerprints: HashMap<String, u64>,
+    // Cluster tree; host and cluster states are left to `Unknown` (majority states are
+    // computed by the context, only for the clusters that changed)
+    pub clusters: Vec<Cluster>,
+    // Latest resource metadata indexed by host (used for rich hover tooltips)
+    pub strata_by_host: HashMap<String, Strata>,
+    // Number of distinct GPU devices per host
+    pub gpu_count_by_host: HashMap<String, usize>,
+}
+
+/// Builds everything the UI needs from the flat resource list.
+pub fn prepare_resource_update(resources: Vec<Strata>) -> ResourceUpdate {
+    let (strata_by_host, gpu_count_by_host) = index_hosts(&resources);
+    ResourceUpdate {
+        fingerprint: resources_fingerprint(&resources),
+        cluster_fingerprints: cluster_fingerprints(&resources),
+        clusters: build_clusters(&resources),
+        strata_by_host,
+        gpu_count_by_host,
+    }
+}
+
+/*
+Host metadata index (several keys per host) and GPU count per host.
+ */
+fn index_hosts(resources: &[Strata]) -> (HashMap<String, Strata>, HashMap<String, usize>) {
+    // Build cpuset index list per host by aggregating resource-level cpuset values.
+    // OAR resources often provide a scalar cpuset per resource; Grid5000 displays the
+    // aggregated list at host level.
+    let mut cpuset_by_host: HashMap<String, Vec<i32>> = HashMap::new();
+    let mut gpu_devices_by_host: HashMap<String, Vec<i32>> = HashMap::new();
+    for r in resources.iter() {
+        let host = r.host.as_deref().unwrap_or("").trim();
+        if host.is_empty() {
+            continue;
+        }
+        if let Some(v) = r.cpuset.as_ref() {
+            let ints = parse_cpuset(v);
+            if !ints.is_empty() {
+                cpuset_by_host
+                    .entry(host.to_string())
+                    .or_default()
+                    .extend(ints);
+            }
+        }
+        // Same for GPUs: each resource only lists the device it belongs to
+        if let Some(v) = r.gpudevice.as_ref() {
+            gpu_devices_by_host
+                .entry(host.to_string())
+                .or_default()
+                .extend(parse_cpuset(v));
+        }
+    }
+    let gpu_count_by_host: HashMap<String, usize> = gpu_devices_by_host
+        .into_iter()
+        .map(|(host, mut devices)| {
+            devices.sort_unstable();
+            devices.dedup();
+            (host, devices.len())
+        })
+        .collect();
+
+    // Cache the latest metadata for tooltips. Use multiple keys per host to be robust
+    // (short host, FQDN, network_address).
+    let mut strata_by_host: HashMap<String, Strata> = HashMap::new();
+    for r in resources.iter() {
+        let host = r.host.as_deref().unwrap_or("").trim();
+        let net = r.network_address.as_deref().unwrap_or("").trim();
+
+        if !host.is_empty() {
+            strata_by_host
+                .entry(host.to_string())
+                .or_insert_with(|| r.clone());
+            let short = short_host_label(host);
+            if !short.is_empty() {
+                strata_by_host.entry(short).or_insert_with(|| r.clone());
+            }
+        }
+
+        if !net.is_empty() {
+            strata_by_host
+                .entry(net.to_string())
+                .or_insert_with(|| r.clone());
+            let short = short_host_label(net);
+            if !short.is_empty() {
+                strata_by_host.entry(short).or_insert_with(|| r.clone());
+            }
+        }
+
+        // Prefer a record that has more human-friendly fields filled.
+        // This updates an already-inserted entry if the new record is "better".
+        fn non_empty_value(v: &Value) -> bool {
+            match v {
+                Value::Null => false,
+                Value::Bool(_) => true,
+                Value::Number(_) => true,
+                Value::String(s) => !s.trim().is_empty(),
+                Value::Array(arr) => arr.iter().any(non_empty_value),
+                Value::Object(obj) => !obj.is_empty(),
+            }
+        }
+        for k in [host, net] {
+            if k.is_empty() {
+                continue;
+            }
+            if let Some(existing) = strata_by_host.get(k).cloned() {
+                let existing_score = existing
+                    .comment
+                    .as_ref()
+                    .map(|s| !s.trim().is_empty())
+                    .unwrap_or(false) as i32
+                    + existing
+                        .cpuset
+                        .as_ref()
+                        .map(non_empty_value)
+                        .unwrap_or(false) as i32
+                    + existing
+                        .cputype
+                        .as_ref()
+                        .map(|s| !s.trim().is_empty())
+                        .unwrap_or(false) as i32
+                    + existing
+                        .nodemodel
+                        .as_ref()
+                        .map(|s| !s.trim().is_empty())
+                        .unwrap_or(false) as i32;
+                let new_score = r
+                    .comment
+                    .as_ref()
+                    .map(|s| !s.trim().is_empty())
+                    .unwrap_or(false) as i32
+                    + r.cpuset.as_ref().map(non_empty_value).unwrap_or(false) as i32
+                    + r.cputype
+                        .as_ref()
+                        .map(|s| !s.trim().is_empty())
+                        .unwrap_or(false) as i32
+                    + r.nodemodel
+                        .as_ref()
+                        .map(|s| !s.trim().is_empty())
+                        .unwrap_or(false) as i32;
+                if new_score > existing_score {
+                    strata_by_host.insert(k.to_string(), r.clone());
+                }
+            }
+        }
+    }
+
+    // Overwrite cached cpuset with the aggregated host-level cpuset list (when available).
+    for s in strata_by_host.values_mut() {
+        let host_key = s.host.as_deref().unwrap_or("").trim();
+        if host_key.is_empty() {
+            continue;
+        }
+        if let Some(ints) = cpuset_by_host.get(host_key) {
+            let mut ints = ints.clone();
+            ints.sort_unstable();
+            ints.dedup();
+            if !ints.is_empty() {
+                let arr: Vec<Value> = ints
+                    .into_iter()
+                    .map(|i| Value::Number(serde_json::Number::from(i)))
+                    .collect();
+                s.cpuset = Some(Value::Array(arr));
+            }
+        }
+    }
+
+    (strata_by_host, gpu_count_by_host)
+}
+
+/*
+Builds the `Cluster -> Host -> Cpu -> Resource` tree. Clusters, hosts and cpus (one per
+cputype) keep the order of their first resource; the other fields of a host or a cpu come
+from that first resource. Resources without a cluster name are ignored.
+ */
+fn build_clusters(resources: &[Strata]) -> Vec<Cluster> {
+    let mut clusters: Vec<Cluster> = Vec::new();
+    // Positions of the clusters, of the hosts in their cluster and of the cpus in their host
+    let mut cluster_index: HashMap<&str, usize> = HashMap::new();
+    let mut host_index: HashMap<(usize, &str), usize> = HashMap::new();
+    let mut cpu_index: HashMap<(usize, usize, &str), usize> = HashMap::new();
+
+    for resource in resources {
+        let cluster_name = resource.cluster.as_deref().unwrap_or("");
+        if cluster_name.is_empty() {
+            continue;
+        }
+        let host_name = resource.host.as_deref().unwrap_or("");
+        let cpu_name = resource.cputype.as_deref().unwrap_or("");
+        let id = resource.resource_id.unwrap_or(0);
+
+        let c = *cluster_index.entry(cluster_name).or_insert_with(|| {
+            clusters.push(Cluster {
+                name: cluster_name.to_string(),
+                hosts: Vec::new(),
+                resource_ids: Vec::new(),
+                state: ResourceState::Unknown,
+            });
+            clusters.len() - 1
+        });
+        let cluster = &mut clusters[c];
+
+        let h = *host_index.entry((c, host_name)).or_insert_with(|| {
+            cluster.hosts.push(Host {
+                name: host_name.to_string(),
+                cpus: Vec::new(),
+                network_address: resource.network_address.clone().unwrap_or_default(),
+                resource_ids: Vec::new(),
+                state: ResourceState::Unknown,
+            });
+            cluster.hosts.len() - 1
+        });
+        let host = &mut cluster.hosts[h];
+
+        let u = *cpu_index.entry((c, h, cpu_name)).or_insert_with(|| {
+            host.cpus.push(Cpu {
+                name: cpu_name.to_string(),
+                resources: Vec::new(),
+                chassis: resource.chassis.clone().unwrap_or_default(),
+                core_count: resource.core_count.unwrap_or(0),
+                cpufreq: resource
+                    .cpufreq
+                    .as_deref()
+                    .unwrap_or("")
+                    .parse::<f32>()
+                    .unwrap_or(0.0),
+                resource_ids: Vec::new(),
+            });
+            host.cpus.len() - 1
+        });
+        let cpu = &mut host.cpus[u];
+
+        cpu.resources.push(Resource {
+            id,
+            state: match resource.state.as_deref().unwrap_or("") {
+                "Dead" => ResourceState::Dead,
+                "Alive" => ResourceState::Alive,
+                "Absent" => ResourceState::Absent,
+                _ => ResourceState::Unknown,
+            },
+            thread_count: resource.thread_count.unwrap_or(0),
+        });
+
+        // add the resource id to the cpu, the host and the cluster
+        cpu.resource_ids.push(id);
+        host.resource_ids.push(id);
+        cluster.resource_ids.push(id);
+    }
+
+    clusters
+}
+
+/*
+Fingerprint of a resource list, used to detect a refresh that brings the same resources.
+Every record is hashed (a single host going Dead must trigger a rebuild), on the fields
+read to build the clusters and the host metadata caches. This stays linear and much
+cheaper than the rebuild itself.
+ */
+fn resources_fingerprint(resources: &[Strata]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    resources.len().hash(&mut hasher);
+    for r in resources {
+        hash_strata(r, &mut hasher);
+    }
+    hasher.finish()
+}
+
+// Same fingerprint, computed separately for the records of each cluster
+fn cluster_fingerprints(resources: &[Strata]) -> HashMap<String, u64> {
+    let mut hashers: HashMap<String, DefaultHasher> = HashMap::new();
+    for r in resources {
+        let cluster_name = r.cluster.clone().unwrap_or_default();
+        hash_strata(r, hashers.entry(cluster_name).or_default());
+    }
+    hashers
+        .into_iter()
+        .map(|(cluster_name, hasher)| (cluster_name, hasher.finish()))
+        .collect()
+}
+
+fn hash_strata(r: &Strata, hasher: &mut DefaultHasher) {
+    r.resource_id.hash(hasher);
+    r.cluster.hash(hasher);
+    r.host.hash(hasher);
+    r.network_address.hash(hasher);
+    r.state.hash(hasher);
+    r.cputype.hash(hasher);
+    r.core_count.hash(hasher);
+    r.thread_count.hash(hasher);
+    r.cpufreq.hash(hasher);
+    r.chassis.hash(hasher);
+    r.comment.hash(hasher);
+    r.nodemodel.hash(hasher);
+    r.besteffort.hash(hasher);
+    r.deploy.hash(hasher);
+    r.drain.hash(hasher);
+    r.r#type.hash(hasher);
+    // `Value` is not hashable: hash its JSON text
+    r.cpuset.as_ref().map(Value::to_string).hash(hasher);
+    r.gpudevice.as_ref().map(Value::to_string).hash(hasher);
+}
@@ -52,6 +52,11 @@ impl Secret {
         }
     }
 
+    /// Whether the snake game window is open and needs to be animated.
+    pub fn is_game_running(&self) -> bool {
+        self.is_konami_active && self.show_game
+    }
+
     pub fn random_secret(&mut self, ctx: &egui::Context) {
         let input_state = ctx.input(|i| i.raw.clone());
 
@@ -0,0 +1,226 @@
+// Initial time window given at startup, to launch the tool pre-scoped to a period
+// (e.g. a kiosk showing the last 24 hours).
+//
+//   --since <duration>   start = now - duration ("90m", "24h", "7d", "1d12h"...)
+//   --start <date>       start of the window
+//   --end <date>         end of the window
+//
+// Dates are RFC 3339, "YYYY-MM-DD HH:MM[:SS]" (local time), "YYYY-MM-DD" (local midnight) or a
+// Unix timestamp. The same values can be given with the GOARD_SINCE, GOARD_START and GOARD_END
+// environment variables; command-line arguments win. A bound that is not given keeps its
+// default (now ± 1 hour). Bounds before 1970 or after year 9999 are rejected. Other arguments
+// are ignored.
+
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveDateTime, TimeZone};
+use std::fmt;
+
+const SINCE_ENV: &str = "GOARD_SINCE";
+const START_ENV: &str = "GOARD_START";
+const END_ENV: &str = "GOARD_END";
+// Window bounds are kept between the Unix epoch and the end of year 9999, so that the Gantt can
+// zoom and pan around them without leaving the range of chrono dates
+const MAX_TIMESTAMP_S: i64 = 253_402_300_799;
+
+#[derive(Debug, PartialEq)]
+pub enum WindowError {
+    MissingValue(String),
+    InvalidDuration(String),
+    InvalidDate(String),
+    SinceWithStart,
+    EmptyWindow,
+}
+
+impl fmt::Display for WindowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WindowError::MissingValue(arg) => write!(f, "missing value after {}", arg),
+            WindowError::InvalidDuration(value) => {
+                write!(f, "invalid duration {:?} (expected e.g. 90m, 24h, 7d)", value)
+            }
+            WindowError::InvalidDate(value) => write!(
+                f,
+                "invalid date {:?} (expected RFC 3339, YYYY-MM-DD HH:MM[:SS], YYYY-MM-DD or a Unix timestamp)",
+                value
+            ),
+            WindowError::SinceWithStart => write!(f, "--since and --start cannot be used together"),
+            WindowError::EmptyWindow => write!(f, "the start of the window must be before its end"),
+        }
+    }
+}
+
+// Raw values of the window bounds, before parsing
+#[derive(Debug, Default, PartialEq)]
+struct WindowSpec {
+    since: Option<String>,
+    start: Option<String>,
+    end: Option<String>,
+}
+
+impl WindowSpec {
+    fn is_empty(&self) -> bool {
+        self.since.is_none() && self.start.is_none() && self.end.is_none()
+    }
+
+    // Values of `self` win over the ones of `fallback`
+    fn or(self, fallback: WindowSpec) -> WindowSpec {
+        WindowSpec {
+            since: self.since.or(fallback.since),
+            start: self.start.or(fallback.start),
+            end: self.end.or(fallback.end),
+        }
+    }
+}
+
+// Default window: one hour around now
+pub fn default_window(now: DateTime<Local>) -> (DateTime<Local>, DateTime<Local>) {
+    (now - Duration::hours(1), now + Duration::hours(1))
+}
+
+/* Window requested on the command line or, failing that, in the environment
+ * None when nothing is requested, or with a warning when a value is invalid: the caller then
+ * uses the default window.
+ */
+pub fn requested_window(now: DateTime<Local>) -> Option<(DateTime<Local>, DateTime<Local>)> {
+    let spec = spec_from_args(std::env::args().skip(1)).map(|args| args.or(spec_from_env()));
+    match spec {
+        Ok(spec) if spec.is_empty() => None,
+        Ok(spec) => resolve_window(&spec, now).map_err(warn_default_window).ok(),
+        Err(error) => {
+            warn_default_window(error);
+            None
+        }
+    }
+}
+
+fn warn_default_window(error: WindowError) {
+    println!("Warning: {}; using the default time window", error);
+}
+
+fn spec_from_args(args: impl Iterator<Item = String>) -> Result<WindowSpec, WindowError> {
+    let mut spec = WindowSpec::default();
+    let mut args = args.peekable();
+    while let Some(arg) = args.next() {
+        // Both "--since 24h" and "--since=24h"
+        let (name, inline_value) = match arg.split_once('=') {
+            Some((name, value)) => (name.to_string(), Some(value.to_string())),
+            None => (arg.clone(), None),
+        };
+        let slot = match name.as_str() {
+            "--since" => &mut spec.since,
+            "--start" => &mut spec.start,
+            "--end" => &mut spec.end,
+            _ => continue,
+        };
+        let value = match inline_value {
+            Some(value) => value,
+            None => args
+                .next_if(|value| !value.starts_with("--"))
+                .ok_or_else(|| WindowError::MissingValue(name.clone()))?,
+        };
+        *slot = Some(value);
+    }
+    Ok(spec)
+}
+
+fn spec_from_env() -> WindowSpec {
+    let var = |name: &str| std::env::var(name).ok().filter(|value| !value.trim().is_empty());
+    WindowSpec {
+        since: var(SINCE_ENV),
+        start: var(START_ENV),
+        end: var(END_ENV),
+    }
+}
+
+fn resolve_window(spec: &WindowSpec, now: DateTime<Local>) -> Result<(DateTime<Local>, DateTime<Local>), WindowError> {
+    let (default_start, default_end) = default_window(now);
+    if spec.is_empty() {
+        return Ok((default_start, default_end));
+    }
+    if spec.since.is_some() && spec.start.is_some() {
+        return Err(WindowError::SinceWithStart);
+    }
+
+    let start = match (&spec.since, &spec.start) {
+        (Some(since), _) => now
+            .checked_sub_signed(parse_duration(since)?)
+            .filter(in_range)
+            .ok_or_else(|| WindowError::InvalidDuration(since.to_string()))?,
+        (None, Some(start)) => parse_date(start)?,
+        (None, None) => default_start,
+    };
+    let end = match &spec.end {
+        Some(end) => parse_date(end)?,
+        None => default_end,
+    };
+
+    if start >= end {
+        return Err(WindowError::EmptyWindow);
+    }
+    Ok((start, end))
+}
+
+// Parses "90m", "24h", "7d" or combined units like "1d12h" (s, m, h, d, w)
+fn parse_duration(value: &str) -> Result<Duration, WindowError> {
+    let invalid = || WindowError::InvalidDuration(value.to_string());
+    let text = value.trim();
+    if text.is_empty() {
+        return Err(invalid());
+    }
+
+    let mut total = Duration::zero();
+    let mut number = String::new();
+    for c in text.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+        let amount: i64 = number.parse().map_err(|_| invalid())?;
+        number.clear();
+        let unit = match c {
+            's' => Duration::try_seconds(amount),
+            'm' => Duration::try_minutes(amount),
+            'h' => Duration::try_hours(amount),
+            'd' => Duration::try_days(amount),
+            'w' => Duration::try_weeks(amount),
+            _ => None,
+        };
+        total = unit.and_then(|unit| total.checked_add(&unit)).ok_or_else(invalid)?;
+    }
+    // A trailing number without unit is ambiguous
+    if !number.is_empty() || total <= Duration::zero() {
+        return Err(invalid());
+    }
+    Ok(total)
+}
+
+fn parse_date(value: &str) -> Result<DateTime<Local>, WindowError> {
+    let text = value.trim();
+    let local = |naive: NaiveDateTime| Local.from_local_datetime(&naive).earliest();
+
+    let date = DateTime::parse_from_rfc3339(text)
+        .ok()
+        .map(|date| date.with_timezone(&Local))
+        .or_else(|| {
+            ["%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%dT%H:%M"]
+                .iter()
+                .find_map(|format| NaiveDateTime::parse_from_str(text, format).ok())
+                .and_then(local)
+        })
+        .or_else(|| {
+            NaiveDate::parse_from_str(text, "%Y-%m-%d")
+                .ok()
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+                .and_then(local)
+        })
+        .or_else(|| {
+            text.parse::<i64>()
+                .ok()
+                .and_then(|timestamp| DateTime::from_timestamp(timestamp, 0))
+                .map(|date| date.with_timezone(&Local))
+        });
+    date.filter(in_range).ok_or_else(|| WindowError::InvalidDate(value.to_string()))
+}
+
+fn in_range(date: &DateTime<Local>) -> bool {
+    (0..=MAX_TIMESTAMP_S).contains(&date.timestamp())
+}
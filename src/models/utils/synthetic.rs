@@ -0,0 +1,140 @@
+// Synthetic data set for profiling the Gantt view without an OAR backend.
+// Only compiled with the `debug-tools` feature.
+
+use chrono::Local;
+use rand::Rng;
+
+use crate::models::data_structure::{
+    job::{Job, JobState},
+    resource::ResourceState,
+    strata::Strata,
+};
+use crate::models::utils::utils::convert_id_to_color;
+
+/// Size of the generated data set
+#[derive(Clone, Copy)]
+pub struct SyntheticConfig {
+    pub jobs: usize,
+    pub clusters: usize,
+    pub hosts_per_cluster: usize,
+    pub cores_per_host: usize,
+}
+
+impl Default for SyntheticConfig {
+    fn default() -> Self {
+        Self {
+            jobs: 50_000,
+            clusters: 20,
+            hosts_per_cluster: 64,
+            cores_per_host: 16,
+        }
+    }
+}
+
+const OWNERS: [&str; 8] = [
+    "alice", "bob", "charlie", "david", "eva", "frank", "grace", "heidi",
+];
+const SITES: [&str; 4] = ["grenoble", "lyon", "nancy", "rennes"];
+
+fn host_name(cluster: usize, host: usize) -> String {
+    format!(
+        "synth{}-{}.{}.grid5000.fr",
+        cluster + 1,
+        host + 1,
+        SITES[cluster % SITES.len()]
+    )
+}
+
+/// Generates one strata per core, grouped in Grid5000-like clusters and hosts.
+/// Resource ids start at 1 and are contiguous per host.
+pub fn synthetic_stratas(config: &SyntheticConfig) -> Vec<Strata> {
+    let mut rng = rand::thread_rng();
+    let mut stratas = Vec::with_capacity(config.clusters * config.hosts_per_cluster * config.cores_per_host);
+    let mut resource_id = 1;
+
+    for cluster in 0..config.clusters {
+        for host in 0..config.hosts_per_cluster {
+            let name = host_name(cluster, host);
+            // Roughly 5% dead and 5% absent hosts, so hatching is exercised too
+            let state = match rng.gen_range(0..20) {
+                0 => "Dead",
+                1 => "Absent",
+                _ => "Alive",
+            };
+            for core in 0..config.cores_per_host {
+                stratas.push(Strata {
+                    cluster: Some(format!("synth{}", cluster + 1)),
+                    host: Some(name.clone()),
+                    network_address: Some(name.clone()),
+                    resource_id: Some(resource_id),
+                    cpuset: Some(serde_json::Value::from(core as i64)),
+                    core: Some(core as i32),
+                    core_count: Some(config.cores_per_host as i32),
+                    thread_count: Some(1),
+                    cputype: Some("Synthetic CPU".to_string()),
+                    r#type: Some("default".to_string()),
+                    state: Some(state.to_string()),
+                    ..Default::default()
+                });
+                resource_id += 1;
+            }
+        }
+    }
+
+    stratas
+}
+
+/// Generates jobs spread over [start_s, end_s] on the resources of `synthetic_stratas`.
+/// Each job runs on 1 to 4 consecutive hosts of a single cluster.
+pub fn synthetic_jobs(config: &SyntheticConfig, start_s: i64, end_s: i64) -> Vec<Job> {
+    let mut rng = rand::thread_rng();
+    let now = Local::now().timestamp();
+    let span = (end_s - start_s).max(1);
+    let cores_per_cluster = config.hosts_per_cluster * config.cores_per_host;
+
+    (1..=config.jobs as u32)
+        .map(|id| {
+            let walltime = rng.gen_range(600..=12 * 3600);
+            let scheduled_start = start_s - walltime / 2 + rng.gen_range(0..span);
+            let submission_time = scheduled_start - rng.gen_range(0..3600);
+
+            let cluster = rng.gen_range(0..config.clusters.max(1));
+            let host_count = rng.gen_range(1..=4).min(config.hosts_per_cluster.max(1));
+            let first_host = rng.gen_range(0..=config.hosts_per_cluster.saturating_sub(host_count));
+            let cores = rng.gen_range(1..=config.cores_per_host.max(1));
+            let mut assigned_resources = Vec::with_capacity(host_count * cores);
+            for host in first_host..first_host + host_count {
+                let first_id = 1 + cluster * cores_per_cluster + host * config.cores_per_host;
+                assigned_resources.extend((first_id..first_id + cores).map(|id| id as u32));
+            }
+
+            let (state, start_time, stop_time) = if scheduled_start + walltime < now {
+                (JobState::Terminated, scheduled_start, scheduled_start + walltime)
+            } else if scheduled_start <= now {
+                (JobState::Running, scheduled_start, 0)
+            } else {
+                (JobState::Waiting, 0, 0)
+            };
+
+            Job {
+                id,
+                owner: OWNERS[rng.gen_range(0..OWNERS.len())].to_string(),
+                state,
+                command: "./synthetic.sh".to_string(),
+                walltime,
+                message: None,
+                queue: "default".to_string(),
+                assigned_resources,
+                scheduled_start,
+                submission_time,
+                start_time,
+                stop_time,
+                exit_code: if stop_time > 0 { Some(0) } else { None },
+                gantt_color: convert_id_to_color(id),
+                clusters: Vec::new(),
+                hosts: Vec::new(),
+                main_resource_state: ResourceState::Unknown,
+            }
+        })
+        .collect()
+}
@@ -15,38 +15,43 @@ use crate::models::utils::parser::get_current_jobs_for_period;
 use std::thread;
 
 use super::parser::{get_jobs_from_json, get_resources_from_json};
+use super::resource_tree::prepare_resource_update;
+use super::utils::lock_or_recover;
+
+#[cfg(feature = "debug-tools")]
+use super::synthetic::{synthetic_jobs, synthetic_stratas, SyntheticConfig};
 
 impl ApplicationContext {
     pub fn update_refresh_rate(&mut self, new_rate: u64) {
-        let mut rate = self.refresh_rate.lock().unwrap();
+        let mut rate = lock_or_recover(&self.refresh_rate);
         *rate = new_rate;
     }
 
     #[allow(dead_code)]
     pub fn update_start_date(&mut self, new_start: DateTime<Local>) {
-        let mut start = self.start_date.lock().unwrap();
+        let mut start = lock_or_recover(&self.start_date);
         *start = new_start;
     }
 
     #[allow(dead_code)]
     pub fn update_end_date(&mut self, new_end: DateTime<Local>) {
-        let mut end = self.end_date.lock().unwrap();
+        let mut end = lock_or_recover(&self.end_date);
         *end = new_end;
     }
 
     #[allow(dead_code)]
     pub fn get_start_date(&self) -> DateTime<Local> {
-        *self.start_date.lock().unwrap()
+        *lock_or_recover(&self.start_date)
     }
 
     #[allow(dead_code)]
     pub fn get_end_date(&self) -> DateTime<Local> {
-        *self.end_date.lock().unwrap()
+        *lock_or_recover(&self.end_date)
     }
 
     pub fn set_localdate(&mut self, start: DateTime<Local>, end: DateTime<Local>) {
-        let mut start_date = self.start_date.lock().unwrap(); // Lock acquired
-        let mut end_date = self.end_date.lock().unwrap(); // Lock acquired
+        let mut start_date = lock_or_recover(&self.start_date); // Lock acquired
+        let mut end_date = lock_or_recover(&self.end_date); // Lock acquired
         *start_date = start; // Modify data
         *end_date = end; // Modify data
     } // Both locks are automatically released when MutexGuards go out of scope
@@ -55,16 +60,16 @@ impl ApplicationContext {
         let is_refreshing = self.is_refreshing.clone();
 
         // if the app is already refreshing, return
-        if *is_refreshing.lock().unwrap() {
+        if *lock_or_recover(&is_refreshing) {
             return;
         }
 
         // set refreshing to true
-        *is_refreshing.lock().unwrap() = true;
+        *lock_or_recover(&is_refreshing) = true;
 
         // get dates
-        let start = *self.start_date.lock().unwrap();
-        let end = *self.end_date.lock().unwrap();
+        let start = *lock_or_recover(&self.start_date);
+        let end = *lock_or_recover(&self.end_date);
 
         let jobs_sender = self.jobs_sender.clone();
         let resources_sender = self.resources_sender.clone();
@@ -81,13 +86,13 @@ impl ApplicationContext {
                         println!("Error while sending jobs: {}", e);
                     });
 
-                    resources_sender.send(resources).unwrap_or_else(|e| {
+                    resources_sender.send(prepare_resource_update(resources)).unwrap_or_else(|e| {
                         println!("Error while sending resources: {}", e);
                     });
                 }
 
                 // set refreshing to false
-                *is_refreshing_clone.lock().unwrap() = false;
+                *lock_or_recover(&is_refreshing_clone) = false;
             });
         }
 
@@ -99,16 +104,34 @@ impl ApplicationContext {
             jobs_sender.send(jobs).unwrap();
 
             let strata = mock_stratas();
-            resources_sender.send(strata).unwrap();
+            resources_sender.send(prepare_resource_update(strata)).unwrap();
             
             // set refreshing to false
-            *is_refreshing.lock().unwrap() = false;
+            *lock_or_recover(&is_refreshing) = false;
         }
     }
 
+    // Replace the current data with a synthetic data set covering the selected period.
+    // The next successful refresh from the backend overwrites it.
+    #[cfg(feature = "debug-tools")]
+    pub fn load_synthetic_data(&mut self, config: &SyntheticConfig) {
+        let start = lock_or_recover(&self.start_date).timestamp();
+        let end = lock_or_recover(&self.end_date).timestamp();
+
+        let jobs = synthetic_jobs(config, start, end);
+        self.jobs_sender.send(jobs).unwrap_or_else(|e| {
+            println!("Error while sending jobs: {}", e);
+        });
+
+        let stratas = synthetic_stratas(config);
+        self.resources_sender.send(prepare_resource_update(stratas)).unwrap_or_else(|e| {
+            println!("Error while sending resources: {}", e);
+        });
+    }
+
     // In a different thread, update the data every refresh_rate seconds
     pub fn update_periodically(&mut self) {
-        let rate = *self.refresh_rate.lock().unwrap();
+        let rate = *lock_or_recover(&self.refresh_rate);
         let jobs_sender = self.jobs_sender.clone();
         let resources_sender = self.resources_sender.clone();
         let is_refreshing = self.is_refreshing.clone();
@@ -121,20 +144,20 @@ impl ApplicationContext {
             thread::spawn(move || {
                 loop {
                     // Check if already refreshing
-                    if *is_refreshing.lock().unwrap() {
+                    if *lock_or_recover(&is_refreshing) {
                         thread::sleep(Duration::from_secs(rate));
                         continue;
                     }
 
                     // Set refreshing to true
-                    *is_refreshing.lock().unwrap() = true;
+                    *lock_or_recover(&is_refreshing) = true;
 
                     let start;
                     let end;
 
                     {
-                        start = *start_date.lock().unwrap();
-                        end = *end_date.lock().unwrap();
+                        start = *lock_or_recover(&start_date);
+                        end = *lock_or_recover(&end_date);
                     }
 
                     let res = get_current_jobs_for_period(start, end);
@@ -146,13 +169,13 @@ impl ApplicationContext {
                             println!("Error while sending jobs: {}", e);
                         });
 
-                        resources_sender.send(resources).unwrap_or_else(|e| {
+                        resources_sender.send(prepare_resource_update(resources)).unwrap_or_else(|e| {
                             println!("Error while sending resources: {}", e);
                         });
                     }
 
                     // Set refreshing to false
-                    *is_refreshing.lock().unwrap() = false;
+                    *lock_or_recover(&is_refreshing) = false;
 
                     thread::sleep(Duration::from_secs(rate));
                 }
@@ -167,7 +190,7 @@ impl ApplicationContext {
             jobs_sender.send(jobs).unwrap();
 
             let strata = mock_stratas();
-            resources_sender.send(strata).unwrap();
+            resources_sender.send(prepare_resource_update(strata)).unwrap();
         }
     }
 
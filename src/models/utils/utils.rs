@@ -3,11 +3,94 @@ use crate::models::data_structure::cpu::Cpu;
 use crate::models::data_structure::host::Host;
 use crate::models::data_structure::job::Job;
 use crate::models::data_structure::resource::ResourceState;
+use crate::models::data_structure::strata::Strata;
 use std::hash::DefaultHasher;
 use std::hash::Hash;
 //
 use std::cmp::Ordering;
+use std::collections::HashMap;
 use std::hash::Hasher;
+use std::net::IpAddr;
+use std::sync::{Mutex, MutexGuard};
+
+/// Short label of a host, shown in the Gantt gutter and used as a secondary key of
+/// `strata_by_host`.
+///
+/// Rule: the first DNS label, kept as is (dashes and suffixes are not rewritten):
+/// - "dahu-1.grenoble.grid5000.fr" => "dahu-1"
+/// - "gpu-node-12" => "gpu-node-12"
+/// - an IPv4 address ("192.168.1.10") is kept whole, its first octet is not a name
+/// - surrounding blanks are ignored; an empty first label (".x") falls back to the whole name
+pub fn short_host_label(host: &str) -> String {
+    let host = host.trim();
+    if host.parse::<std::net::Ipv4Addr>().is_ok() {
+        return host.to_string();
+    }
+
+    match host.split('.').next().map(str::trim) {
+        Some(first) if !first.is_empty() => first.to_string(),
+        _ => host.to_string(),
+    }
+}
+
+/// Grid5000 site encoded in a FQDN ("dahu-1.grenoble.grid5000.fr" => "grenoble").
+/// A short name or an IP address encodes no site.
+fn site_from_fqdn(host: &str) -> Option<String> {
+    let host = host.trim();
+    if host.parse::<IpAddr>().is_ok() {
+        return None;
+    }
+    let mut parts = host.split('.');
+    let _hostname = parts.next();
+    let site = parts.next().map(str::trim);
+    site.filter(|s| !s.is_empty()).map(|s| s.to_string())
+}
+
+/// Site of a host: its own FQDN first, then the `network_address` of its strata.
+pub fn site_for_host(host: &str, strata_by_host: &HashMap<String, Strata>) -> Option<String> {
+    site_from_fqdn(host).or_else(|| {
+        let key = host.trim();
+        strata_by_host
+            .get(key)
+            .or_else(|| strata_by_host.get(&short_host_label(key)))
+            .and_then(|s| s.network_address.as_deref())
+            .and_then(site_from_fqdn)
+    })
+}
+
+/// Site of a cluster: the `cluster_site_map` entry when there is one, otherwise the first host
+/// of the cluster whose site can be determined.
+pub fn site_for_cluster_name(
+    cluster_name: &str,
+    clusters: &[Cluster],
+    strata_by_host: &HashMap<String, Strata>,
+    cluster_site_map: &HashMap<String, String>,
+) -> Option<String> {
+    if let Some(site) = cluster_site_map
+        .get(cluster_name)
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+    {
+        return Some(site.to_string());
+    }
+
+    clusters
+        .iter()
+        .find(|c| c.name == cluster_name)?
+        .hosts
+        .iter()
+        .find_map(|h| {
+            site_from_fqdn(&h.network_address).or_else(|| site_for_host(&h.name, strata_by_host))
+        })
+}
+
+/// Locks a shared value, recovering it if another thread panicked while holding the lock.
+///
+/// The guarded values (refresh flag, refresh rate, period bounds) stay valid after such a
+/// panic, so a poisoned lock must not bring the UI down.
+pub fn lock_or_recover<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
 
 // Convert a job ID to a color (using hash)
 pub fn convert_id_to_color(id: u32) -> egui::Color32 {
@@ -35,24 +118,6 @@ pub fn get_all_hosts(clusters: &Vec<Cluster>) -> Vec<String> {
     result
 }
 
-pub fn cluster_contain_host(cluster: &Cluster, host_name: &str) -> bool {
-    for host in &cluster.hosts {
-        if host.name == host_name {
-            return true;
-        }
-    }
-    false
-}
-
-pub fn get_cluster_from_name(clusters: &Vec<Cluster>, name: &str) -> Option<Cluster> {
-    for cluster in clusters {
-        if cluster.name == name {
-            return Some(cluster.clone());
-        }
-    }
-    None
-}
-
 /// Extracts all cluster names from a collection of clusters
 pub fn get_all_clusters(clusters: &Vec<Cluster>) -> Vec<String> {
     let mut result: Vec<String> = Vec::new();
@@ -75,15 +140,30 @@ pub fn get_all_resources(clusters: &Vec<Cluster>) -> Vec<u32> {
     result
 }
 
-pub fn contains_host(cluster: &Vec<Cluster>, host_name: &str) -> bool {
-    for c in cluster {
-        for host in &c.hosts {
-            if host.name == host_name {
-                return true;
-            }
+/// Size of a cluster as (hosts, cores, threads, gpus).
+/// Each OAR resource of a cpu is a core; its `thread_count` gives the threads (1 when unknown).
+/// GPUs come from the resource metadata (`gpu_count_by_host`), hosts without it count 0.
+pub fn cluster_resource_summary(
+    cluster: &Cluster,
+    gpu_count_by_host: &HashMap<String, usize>,
+) -> (usize, usize, usize, usize) {
+    let mut cores = 0;
+    let mut threads = 0;
+    let mut gpus = 0;
+
+    for host in &cluster.hosts {
+        for cpu in &host.cpus {
+            cores += cpu.resources.len();
+            threads += cpu
+                .resources
+                .iter()
+                .map(|resource| resource.thread_count.max(1) as usize)
+                .sum::<usize>();
         }
+        gpus += gpu_count_by_host.get(&host.name).copied().unwrap_or(0);
     }
-    false
+
+    (cluster.hosts.len(), cores, threads, gpus)
 }
 
 pub fn get_cluster_state_from_name(cluster: &Vec<Cluster>, cluster_name: &String) -> ResourceState {
@@ -106,13 +186,17 @@ pub fn get_host_state_from_name(cluster: &Vec<Cluster>, host_name: &String) -> R
     ResourceState::Unknown
 }
 
-pub fn contains_cluster(cluster: &Vec<Cluster>, cluster_name: &str) -> bool {
+pub fn get_resource_state_from_id(cluster: &Vec<Cluster>, resource_id: u32) -> ResourceState {
     for c in cluster {
-        if c.name == cluster_name {
-            return true;
+        for host in &c.hosts {
+            for cpu in &host.cpus {
+                if let Some(resource) = cpu.resources.iter().find(|r| r.id == resource_id) {
+                    return resource.state;
+                }
+            }
         }
     }
-    false
+    ResourceState::Unknown
 }
 
 // Compare two strings that may contain numbers (natural sort)
@@ -392,3 +476,88 @@ pub fn get_tree_structure_for_job(job: &Job, clusters: &Vec<Cluster>) -> Vec<Clu
     }
     result
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::data_structure::cluster::test_cluster;
+    use crate::models::data_structure::resource::Resource;
+
+    #[test]
+    fn short_host_label_keeps_the_first_dns_label() {
+        assert_eq!(short_host_label("dahu-1.grenoble.grid5000.fr"), "dahu-1");
+        assert_eq!(short_host_label("dahu-1"), "dahu-1");
+        assert_eq!(short_host_label("  dahu-1.grenoble  "), "dahu-1");
+    }
+
+    #[test]
+    fn short_host_label_does_not_rewrite_dashes_or_suffixes() {
+        assert_eq!(short_host_label("gpu-node-12"), "gpu-node-12");
+        assert_eq!(short_host_label("gpu-node-12.lyon.grid5000.fr"), "gpu-node-12");
+        assert_eq!(short_host_label("node-"), "node-");
+        assert_eq!(short_host_label("node-a.nancy"), "node-a");
+    }
+
+    #[test]
+    fn short_host_label_edge_cases() {
+        assert_eq!(short_host_label(""), "");
+        assert_eq!(short_host_label("   "), "");
+        assert_eq!(short_host_label(".grenoble"), ".grenoble");
+        assert_eq!(short_host_label("192.168.1.10"), "192.168.1.10");
+    }
+
+    #[test]
+    fn site_from_fqdn_reads_the_second_label() {
+        assert_eq!(site_from_fqdn("dahu-1.grenoble.grid5000.fr"), Some("grenoble".to_string()));
+        assert_eq!(site_from_fqdn("dahu-1"), None);
+        assert_eq!(site_from_fqdn("192.168.1.10"), None);
+        assert_eq!(site_from_fqdn("fe80::1"), None);
+    }
+
+    #[test]
+    fn site_for_host_falls_back_to_the_strata_network_address() {
+        let strata = Strata {
+            network_address: Some("dahu-1.grenoble.grid5000.fr".to_string()),
+            ..Strata::default()
+        };
+        let strata_by_host = HashMap::from([("dahu-1".to_string(), strata)]);
+
+        assert_eq!(site_for_host("dahu-1", &strata_by_host), Some("grenoble".to_string()));
+        assert_eq!(site_for_host("gros-3.nancy.grid5000.fr", &strata_by_host), Some("nancy".to_string()));
+        assert_eq!(site_for_host("gros-3", &strata_by_host), None);
+        assert_eq!(site_for_host("10.0.0.3", &strata_by_host), None);
+    }
+
+    #[test]
+    fn site_for_cluster_name_prefers_the_site_map() {
+        let clusters = vec![
+            test_cluster("dahu", &[("dahu-1.grenoble.grid5000.fr", &[1])]),
+            test_cluster("bare", &[("bare-1", &[2])]),
+            test_cluster("ip", &[("10.0.0.3", &[3])]),
+        ];
+        let strata_by_host = HashMap::new();
+        let site_map = HashMap::from([("ip".to_string(), "lyon".to_string())]);
+
+        let site = |name: &str| site_for_cluster_name(name, &clusters, &strata_by_host, &site_map);
+        assert_eq!(site("dahu"), Some("grenoble".to_string()));
+        assert_eq!(site("bare"), None);
+        assert_eq!(site("ip"), Some("lyon".to_string()));
+        assert_eq!(site("missing"), None);
+    }
+
+    #[test]
+    fn cluster_resource_summary_counts_hosts_cores_threads_and_gpus() {
+        let mut cluster = test_cluster("dahu", &[("dahu-1", &[1, 2]), ("dahu-2", &[3, 4, 5])]);
+        // Second cpu on dahu-1, with hyperthreading and an unknown thread count
+        let mut second_cpu = cluster.hosts[0].cpus[0].clone();
+        second_cpu.resources = vec![
+            Resource { id: 6, state: ResourceState::Alive, thread_count: 2 },
+            Resource { id: 7, state: ResourceState::Dead, thread_count: 0 },
+        ];
+        cluster.hosts[0].cpus.push(second_cpu);
+        let gpu_count_by_host = HashMap::from([("dahu-2".to_string(), 4), ("other-1".to_string(), 8)]);
+
+        assert_eq!(cluster_resource_summary(&cluster, &gpu_count_by_host), (2, 7, 8, 4));
+        assert_eq!(cluster_resource_summary(&test_cluster("empty", &[]), &gpu_count_by_host), (0, 0, 0, 0));
+    }
+}
@@ -3,7 +3,12 @@ use super::job_table_sorting::SortKey;
 use crate::models::data_structure::application_context::ApplicationContext;
 use crate::models::utils::date_converter::format_timestamp;
 use crate::models::utils::utils::get_tree_structure_for_job;
-use crate::{models::data_structure::job::Job, views::components::job_details::JobDetailsWindow};
+use crate::{
+    models::data_structure::job::Job,
+    views::components::job_details::{
+        close_job_details_on_escape, open_or_focus_job_details, JobDetailsWindow,
+    },
+};
 use eframe::egui;
 use egui::{RichText, Sense, Ui};
 use egui_extras::{Column, TableBuilder};
@@ -21,6 +26,7 @@ pub struct JobTable {
     sort_key: SortKey, // Sort key for the jobs
     sort_ascending: bool, // True if the sorting is ascending
     column_selection: ColumnSelection, // Column selection for the table
+    synced_selected_job_id: Option<u32>, // Last shared selection seen, to follow selections made in the Gantt
 }
 
 /**
@@ -38,6 +44,7 @@ impl Default for JobTable {
             sort_key: SortKey::Id, // Default sort key is the job id
             sort_ascending: true, // Default sorting is ascending
             column_selection: ColumnSelection::default(), // Default column selection
+            synced_selected_job_id: None, // No selection by default
         }
     }
 }
@@ -51,6 +58,17 @@ impl JobTable {
         self.sort_key
             .sort_jobs(&mut self.displayed_jobs_per_page, self.sort_ascending);
 
+        // A job selected elsewhere (Gantt): show the page containing it
+        if app.selected_job_id != self.synced_selected_job_id {
+            self.synced_selected_job_id = app.selected_job_id;
+            if let Some(index) = app
+                .selected_job_id
+                .and_then(|id| self.displayed_jobs_per_page.iter().position(|job| job.id == id))
+            {
+                self.page = index / self.jobs_per_page;
+            }
+        }
+
         ui.add_space(10.0);
         ui.heading(RichText::new(t!("app.job_table.title")).strong().size(20.0));
         ui.add_space(8.0);
@@ -169,6 +187,8 @@ impl JobTable {
                 .body(|mut body| {
                     for job in self.displayed_jobs_per_page[self.start_idx..self.end_idx].iter() {
                         body.row(20.0, |mut row| {
+                            row.set_selected(app.selected_job_id == Some(job.id));
+
                             // Row index
                             let row_index = self.start_idx + row.index() + 1;
                             row.col(|ui| {
@@ -235,16 +255,16 @@ impl JobTable {
                             // Clickable row
                             let response = row.response().interact(Sense::click());
                             if response.clicked() {
-                                for window in self.details_window.iter_mut() {
-                                    if window.job.id == job.id {
-                                        window.open = true;
-                                        return;
-                                    }
-                                }
-                                self.details_window.push(JobDetailsWindow::new(
-                                    job.clone(),
-                                    get_tree_structure_for_job(job, &app.all_clusters),
-                                ));
+                                app.selected_job_id = Some(job.id);
+                                self.synced_selected_job_id = app.selected_job_id;
+                                open_or_focus_job_details(
+                                    &mut self.details_window,
+                                    JobDetailsWindow::new(
+                                        job.clone(),
+                                        get_tree_structure_for_job(job, &app.all_clusters),
+                                        &app.strata_by_host,
+                                    ),
+                                );
                             }
                         });
                     }
@@ -253,6 +273,7 @@ impl JobTable {
 
         ui.add_space(10.0);
 
+        close_job_details_on_escape(ui.ctx(), &mut self.details_window);
         for window in self.details_window.iter_mut() {
             window.ui(ui);
         }
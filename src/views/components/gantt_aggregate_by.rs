@@ -1,14 +1,18 @@
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum AggregateByLevel1Enum {
     Owner,
     Cluster,
     Host,
 }
 
-#[derive(PartialEq, Clone, Copy)]
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
 pub enum AggregateByLevel2Enum {
     Owner,
     Host,
+    /// One row per OAR resource of the host (only under Host)
+    Resource,
     None,
 }
 
@@ -31,6 +35,7 @@ impl Default for AggregateBy {
  * The rule for aggregation is:
  * The first level must be higher than the second level, the order is:
  * Cluster -> Host -> Owner -> None
+ * Host can also be split into its resources (Host -> Resource), the finest OAR granularity
  */
 impl AggregateBy {
     pub fn ui(&mut self, ui: &mut egui::Ui) {
@@ -95,6 +100,11 @@ impl AggregateBy {
                             AggregateByLevel2Enum::Owner,
                             t!("app.gantt.settings.owner"),
                         );
+                        ui.radio_value(
+                            &mut self.level_2,
+                            AggregateByLevel2Enum::Resource,
+                            t!("app.gantt.settings.resource"),
+                        );
                         ui.radio_value(
                             &mut self.level_2,
                             AggregateByLevel2Enum::None,
@@ -0,0 +1,57 @@
+#[derive(PartialEq, Clone, Copy)]
+pub enum GutterClickActionEnum {
+    None,
+    Filter,
+    Collapse,
+    ZoomToJobs,
+}
+
+/// Filter requested by a click on an owner or host label of the gutter
+pub enum GutterFilter {
+    Owner(String),
+    Host(String),
+}
+
+pub struct GutterClickAction {
+    pub action: GutterClickActionEnum,
+}
+
+impl Default for GutterClickAction {
+    fn default() -> Self {
+        Self {
+            action: GutterClickActionEnum::None, // Default value keeps labels as tooltips only
+        }
+    }
+}
+
+impl GutterClickAction {
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(format!("{}:", t!("app.gantt.settings.gutter_click")));
+            // A click on a gutter label can filter the jobs on it, collapse its group or fit the
+            // visible window to the jobs of the group
+            ui.horizontal(|ui| {
+                ui.radio_value(
+                    &mut self.action,
+                    GutterClickActionEnum::None,
+                    t!("app.gantt.settings.gutter_click_none"),
+                );
+                ui.radio_value(
+                    &mut self.action,
+                    GutterClickActionEnum::Filter,
+                    t!("app.gantt.settings.gutter_click_filter"),
+                );
+                ui.radio_value(
+                    &mut self.action,
+                    GutterClickActionEnum::Collapse,
+                    t!("app.gantt.settings.gutter_click_collapse"),
+                );
+                ui.radio_value(
+                    &mut self.action,
+                    GutterClickActionEnum::ZoomToJobs,
+                    t!("app.gantt.settings.gutter_click_zoom"),
+                );
+            });
+        });
+    }
+}
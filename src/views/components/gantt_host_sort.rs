@@ -0,0 +1,60 @@
+use crate::models::data_structure::resource::ResourceState;
+
+#[derive(PartialEq, Clone, Copy)]
+pub enum HostSortEnum {
+    Name,
+    StateThenName,
+}
+
+pub struct HostSort {
+    pub sort: HostSortEnum,
+}
+
+impl Default for HostSort {
+    fn default() -> Self {
+        Self {
+            sort: HostSortEnum::Name, // Default value keeps the natural sort on host names
+        }
+    }
+}
+
+impl HostSort {
+
+    /**
+     * Returns true if hosts must be grouped by resource state before being sorted by name
+     */
+    pub fn is_by_state(&self) -> bool {
+        self.sort == HostSortEnum::StateThenName
+    }
+
+    /**
+     * Rank of a resource state when sorting hosts: broken nodes come first
+     */
+    pub fn state_rank(state: &ResourceState) -> u8 {
+        match state {
+            ResourceState::Dead => 0,
+            ResourceState::Absent => 1,
+            ResourceState::Unknown => 2,
+            ResourceState::Alive => 3,
+        }
+    }
+
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(format!("{}:", t!("app.gantt.settings.host_sort")));
+            // Hosts can be sorted by name only, or grouped by state first
+            ui.horizontal(|ui| {
+                ui.radio_value(
+                    &mut self.sort,
+                    HostSortEnum::Name,
+                    t!("app.gantt.settings.host_sort_name"),
+                );
+                ui.radio_value(
+                    &mut self.sort,
+                    HostSortEnum::StateThenName,
+                    t!("app.gantt.settings.host_sort_state"),
+                );
+            });
+        });
+    }
+}
@@ -0,0 +1,84 @@
+use std::collections::HashSet;
+
+#[derive(PartialEq, Eq, Hash, Clone, Copy)]
+pub enum StrataFieldEnum {
+    Besteffort,
+    NetworkAddress,
+    Comment,
+    Cpuset,
+    Deploy,
+    Drain,
+    Gpudevice,
+    Type,
+    Cputype,
+    Nodemodel,
+}
+
+impl StrataFieldEnum {
+    // Display order of the fields in the host tooltips
+    pub const ALL: [StrataFieldEnum; 10] = [
+        StrataFieldEnum::Besteffort,
+        StrataFieldEnum::NetworkAddress,
+        StrataFieldEnum::Comment,
+        StrataFieldEnum::Cpuset,
+        StrataFieldEnum::Deploy,
+        StrataFieldEnum::Drain,
+        StrataFieldEnum::Gpudevice,
+        StrataFieldEnum::Type,
+        StrataFieldEnum::Cputype,
+        StrataFieldEnum::Nodemodel,
+    ];
+
+    /**
+     * Returns the OAR property name, used as the tooltip key
+     */
+    pub fn key(&self) -> &'static str {
+        match self {
+            StrataFieldEnum::Besteffort => "besteffort",
+            StrataFieldEnum::NetworkAddress => "network_address",
+            StrataFieldEnum::Comment => "comment",
+            StrataFieldEnum::Cpuset => "cpuset",
+            StrataFieldEnum::Deploy => "deploy",
+            StrataFieldEnum::Drain => "drain",
+            StrataFieldEnum::Gpudevice => "gpudevice",
+            StrataFieldEnum::Type => "type",
+            StrataFieldEnum::Cputype => "cputype",
+            StrataFieldEnum::Nodemodel => "nodemodel",
+        }
+    }
+}
+
+pub struct StrataFields {
+    pub shown: HashSet<StrataFieldEnum>,
+}
+
+impl Default for StrataFields {
+    fn default() -> Self {
+        Self {
+            shown: StrataFieldEnum::ALL.into_iter().collect(), // Default value shows every field
+        }
+    }
+}
+
+impl StrataFields {
+    /**
+     * Returns true if the field must appear in the host tooltips
+     */
+    pub fn is_shown(&self, field: StrataFieldEnum) -> bool {
+        self.shown.contains(&field)
+    }
+
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        ui.label(t!("app.gantt.settings.tooltip_fields_hint"));
+        for field in StrataFieldEnum::ALL {
+            let mut shown = self.is_shown(field);
+            if ui.checkbox(&mut shown, field.key()).changed() {
+                if shown {
+                    self.shown.insert(field);
+                } else {
+                    self.shown.remove(&field);
+                }
+            }
+        }
+    }
+}
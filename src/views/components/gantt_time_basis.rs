@@ -0,0 +1,67 @@
+use crate::models::data_structure::job::Job;
+
+#[derive(PartialEq, Clone, Copy)]
+pub enum TimeBasisEnum {
+    Scheduled,
+    Actual,
+}
+
+pub struct TimeBasis {
+    pub basis: TimeBasisEnum,
+}
+
+impl Default for TimeBasis {
+    fn default() -> Self {
+        Self {
+            basis: TimeBasisEnum::Scheduled, // Default value keeps the bars on the scheduled start
+        }
+    }
+}
+
+impl TimeBasis {
+
+    /**
+     * Start of the job bar, and whether it is the actual start.
+     * With the Actual basis, jobs that have not started yet stay on their scheduled start.
+     */
+    pub fn job_start(&self, job: &Job) -> (i64, bool) {
+        if self.basis == TimeBasisEnum::Actual && job.start_time > 0 {
+            (job.start_time, true)
+        } else {
+            (job.scheduled_start, false)
+        }
+    }
+
+    /**
+     * Start and end of the job bar: the stop time when the job is over,
+     * otherwise the start plus the walltime
+     */
+    pub fn job_span(&self, job: &Job) -> (i64, i64) {
+        let (start_s, _) = self.job_start(job);
+        let end_s = if job.stop_time > 0 {
+            job.stop_time
+        } else {
+            start_s + job.walltime
+        };
+        (start_s, end_s)
+    }
+
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(format!("{}:", t!("app.gantt.settings.time_basis")));
+            // Bars can start at the scheduled start or at the actual start of the job
+            ui.horizontal(|ui| {
+                ui.radio_value(
+                    &mut self.basis,
+                    TimeBasisEnum::Scheduled,
+                    t!("app.gantt.settings.time_basis_scheduled"),
+                );
+                ui.radio_value(
+                    &mut self.basis,
+                    TimeBasisEnum::Actual,
+                    t!("app.gantt.settings.time_basis_actual"),
+                );
+            });
+        });
+    }
+}
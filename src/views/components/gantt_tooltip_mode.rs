@@ -0,0 +1,55 @@
+#[derive(PartialEq, Clone, Copy)]
+pub enum TooltipModeEnum {
+    Instant,
+    Delayed,
+    OnClick,
+}
+
+pub struct TooltipMode {
+    pub mode: TooltipModeEnum,
+    // Hover time (in seconds) before the tooltip shows up in Delayed mode
+    pub delay_s: f64,
+}
+
+impl Default for TooltipMode {
+    fn default() -> Self {
+        Self {
+            mode: TooltipModeEnum::Instant, // Default value keeps the tooltips on hover
+            delay_s: 0.4,
+        }
+    }
+}
+
+impl TooltipMode {
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(format!("{}:", t!("app.gantt.settings.tooltip_mode")));
+            // Tooltips can follow the pointer right away, after a short hover, or on click
+            ui.horizontal(|ui| {
+                ui.radio_value(
+                    &mut self.mode,
+                    TooltipModeEnum::Instant,
+                    t!("app.gantt.settings.tooltip_mode_instant"),
+                );
+                ui.radio_value(
+                    &mut self.mode,
+                    TooltipModeEnum::Delayed,
+                    t!("app.gantt.settings.tooltip_mode_delayed"),
+                );
+                ui.radio_value(
+                    &mut self.mode,
+                    TooltipModeEnum::OnClick,
+                    t!("app.gantt.settings.tooltip_mode_on_click"),
+                );
+            });
+            if self.mode == TooltipModeEnum::Delayed {
+                ui.add(
+                    egui::DragValue::new(&mut self.delay_s)
+                        .range(0.1..=3.0)
+                        .speed(0.05)
+                        .suffix(" s"),
+                );
+            }
+        });
+    }
+}
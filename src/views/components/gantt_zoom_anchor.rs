@@ -0,0 +1,56 @@
+#[derive(PartialEq, Clone, Copy)]
+pub enum ZoomAnchorEnum {
+    Pointer,
+    Center,
+    LeftEdge,
+}
+
+pub struct ZoomAnchor {
+    pub anchor: ZoomAnchorEnum,
+}
+
+impl Default for ZoomAnchor {
+    fn default() -> Self {
+        Self {
+            anchor: ZoomAnchorEnum::Pointer, // Default value keeps zooming around the pointer
+        }
+    }
+}
+
+impl ZoomAnchor {
+    /**
+     * Screen abscissa kept fixed while zooming, between the chart edges `min_x` and `max_x`.
+     * The pointer anchor falls back to the center when the pointer position is unknown.
+     */
+    pub fn anchor_x(&self, pointer_x: Option<f32>, min_x: f32, max_x: f32) -> f32 {
+        match (self.anchor, pointer_x) {
+            (ZoomAnchorEnum::Pointer, Some(x)) => x,
+            (ZoomAnchorEnum::LeftEdge, _) => min_x,
+            _ => (min_x + max_x) / 2.0,
+        }
+    }
+
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(format!("{}:", t!("app.gantt.settings.zoom_anchor")));
+            // The time zoom keeps the pointer, the center or the left edge of the chart in place
+            ui.horizontal(|ui| {
+                ui.radio_value(
+                    &mut self.anchor,
+                    ZoomAnchorEnum::Pointer,
+                    t!("app.gantt.settings.zoom_anchor_pointer"),
+                );
+                ui.radio_value(
+                    &mut self.anchor,
+                    ZoomAnchorEnum::Center,
+                    t!("app.gantt.settings.zoom_anchor_center"),
+                );
+                ui.radio_value(
+                    &mut self.anchor,
+                    ZoomAnchorEnum::LeftEdge,
+                    t!("app.gantt.settings.zoom_anchor_left_edge"),
+                );
+            });
+        });
+    }
+}
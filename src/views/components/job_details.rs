@@ -1,34 +1,138 @@
 use crate::models::data_structure::cluster::Cluster;
 use crate::models::data_structure::job::Job;
-use crate::models::utils::date_converter::format_timestamp;
+use crate::models::data_structure::strata::Strata;
+use crate::models::utils::cpuset::{
+    format_cpuset_ranges, format_resource_ids, parse_cpuset, truncate_ranges,
+};
+use crate::models::utils::date_converter::{format_duration, format_timestamp};
+use crate::models::utils::utils::short_host_label;
 use eframe::egui;
+use std::collections::HashMap;
+
+// Scheduling delays above this value (in seconds) are highlighted
+const LARGE_SCHEDULING_DELAY_S: i64 = 3600;
+
+// Beyond this length, the assigned resource ranges are truncated (the full list can be copied)
+const ASSIGNED_RESOURCES_MAX_CHARS: usize = 300;
 
 pub struct JobDetailsWindow {
     pub open: bool,
     pub job: Job,
     pub cluster: Vec<Cluster>,
+    // Metadata of each host of the job, copied from `strata_by_host` when the window opens
+    // (None when the host is missing from the resource cache).
+    pub host_metadata: Vec<(String, Option<Strata>)>,
+    // Assigned OAR resource ids, formatted as compact ranges ("1-4, 9")
+    assigned_resources: String,
+    // Rank used to cascade the initial position of successive windows
+    cascade_index: usize,
+    focus_requested: bool,
+}
+
+/// Opens the details window of a job, or brings its window to the front if it is
+/// already in the list. New windows are cascaded from the previous ones.
+pub fn open_or_focus_job_details(windows: &mut Vec<JobDetailsWindow>, window: JobDetailsWindow) {
+    if let Some(existing) = windows.iter_mut().find(|w| w.job.id == window.job.id) {
+        existing.open = true;
+        existing.focus_requested = true;
+        return;
+    }
+
+    let mut window = window;
+    window.cascade_index = windows.len();
+    windows.push(window);
+}
+
+/// Escape closes the details window in front, Shift+Escape closes all of them.
+/// Ignored while a widget (e.g. a text field) has the keyboard focus.
+pub fn close_job_details_on_escape(ctx: &egui::Context, windows: &mut [JobDetailsWindow]) {
+    if !windows.iter().any(|w| w.open) || ctx.memory(|mem| mem.focused().is_some()) {
+        return;
+    }
+
+    let (escape, shift) = ctx.input(|i| (i.key_pressed(egui::Key::Escape), i.modifiers.shift));
+    if !escape {
+        return;
+    }
+
+    if shift {
+        for window in windows.iter_mut() {
+            window.open = false;
+        }
+        return;
+    }
+
+    // Layers are ordered back to front: the last details window found is the one in front
+    let front = ctx
+        .memory(|mem| {
+            mem.layer_ids()
+                .filter(|layer| layer.order == egui::Order::Middle)
+                .filter_map(|layer| {
+                    windows
+                        .iter()
+                        .position(|w| w.open && w.window_id() == layer.id)
+                })
+                .last()
+        })
+        .or_else(|| windows.iter().rposition(|w| w.open));
+
+    if let Some(index) = front {
+        windows[index].open = false;
+    }
 }
 
 impl JobDetailsWindow {
-    pub fn new(job: Job, cluster: Vec<Cluster>) -> Self {
+    pub fn new(job: Job, cluster: Vec<Cluster>, strata_by_host: &HashMap<String, Strata>) -> Self {
+        let host_metadata = job
+            .hosts
+            .iter()
+            .map(|host| {
+                let strata = strata_by_host
+                    .get(host.trim())
+                    .or_else(|| strata_by_host.get(&short_host_label(host)))
+                    .cloned();
+                (host.clone(), strata)
+            })
+            .collect();
+        let assigned_resources = format_resource_ids(&job.assigned_resources);
+
         Self {
             open: true,
             job: job,
             cluster: cluster,
+            host_metadata,
+            assigned_resources,
+            cascade_index: 0,
+            focus_requested: false,
         }
     }
 
+    fn window_id(&self) -> egui::Id {
+        egui::Id::new(("job_details_window", self.job.id))
+    }
+
     pub fn ui(&mut self, ui: &mut egui::Ui) {
         // If the window is not open, do not render it
         if !self.open {
             return;
         }
 
+        if self.focus_requested {
+            self.focus_requested = false;
+            ui.ctx()
+                .move_to_top(egui::LayerId::new(egui::Order::Middle, self.window_id()));
+        }
+
+        // Cascade: each new window is shifted by 30px, wrapping after 10 windows
+        let offset = (self.cascade_index % 10) as f32 * 30.0;
+
         egui::Window::new(format!(
             "{}: {}",
             t!("app.details.general.title"),
             self.job.id
         ))
+        .id(self.window_id())
+        .default_pos(egui::pos2(120.0 + offset, 120.0 + offset))
         .collapsible(true)
         .movable(true)
         .open(&mut self.open)
@@ -108,6 +212,27 @@ impl JobDetailsWindow {
                     ));
                     ui.strong(format_timestamp(self.job.start_time));
                 });
+                ui.horizontal(|ui| {
+                    ui.label(format!(
+                        "{}:",
+                        t!("app.details.timing_info.scheduling_delay")
+                    ));
+                    match self.job.scheduling_delay() {
+                        Some(delay) if delay > LARGE_SCHEDULING_DELAY_S => {
+                            ui.label(
+                                egui::RichText::new(format_duration(delay))
+                                    .color(ui.visuals().warn_fg_color)
+                                    .strong(),
+                            );
+                        }
+                        Some(delay) => {
+                            ui.strong(format_duration(delay));
+                        }
+                        None => {
+                            ui.strong("N/A");
+                        }
+                    }
+                });
                 ui.horizontal(|ui| {
                     ui.label(format!("{}:", t!("app.details.timing_info.stop_time")));
                     ui.strong(format_timestamp(self.job.stop_time));
@@ -120,6 +245,14 @@ impl JobDetailsWindow {
 
             ui.add_space(8.0);
 
+            // Lifecycle: each step with the time elapsed since the previous known one
+            ui.group(|ui| {
+                ui.heading(t!("app.details.lifecycle.title"));
+                Self::lifecycle_ui(ui, &self.job);
+            });
+
+            ui.add_space(8.0);
+
             if !self.cluster.is_empty() {
                 // Ressources
                 ui.group(|ui| {
@@ -207,6 +340,108 @@ impl JobDetailsWindow {
             }
 
             ui.add_space(8.0);
+
+            if !self.assigned_resources.is_empty() {
+                // Assigned OAR resource ids
+                ui.group(|ui| {
+                    ui.heading(t!(
+                        "app.details.resources.assigned",
+                        count = self.job.assigned_resources.len()
+                    ));
+                    ui.label(truncate_ranges(
+                        &self.assigned_resources,
+                        ASSIGNED_RESOURCES_MAX_CHARS,
+                    ));
+                    if ui.button(t!("app.details.resources.copy_list")).clicked() {
+                        ui.ctx().copy_text(self.assigned_resources.clone());
+                    }
+                });
+
+                ui.add_space(8.0);
+            }
+
+            if !self.host_metadata.is_empty() {
+                // Host metadata (cpuset, cpu, node model...)
+                ui.group(|ui| {
+                    ui.heading(t!("app.details.resources.metadata"));
+                    for (host, strata) in &self.host_metadata {
+                        egui::CollapsingHeader::new(host)
+                            .default_open(self.host_metadata.len() == 1)
+                            .show(ui, |ui| match strata {
+                                Some(strata) => Self::strata_ui(ui, strata),
+                                None => {
+                                    ui.weak(t!("app.details.resources.missing"));
+                                }
+                            });
+                    }
+                });
+            }
+        });
+    }
+
+    // Submission, scheduled start, actual start and stop of the job. Steps not reached yet
+    // (timestamp 0) are shown as "n/a" and skipped when computing the durations.
+    fn lifecycle_ui(ui: &mut egui::Ui, job: &Job) {
+        let steps = [
+            (t!("app.details.lifecycle.submitted"), job.submission_time),
+            (t!("app.details.lifecycle.scheduled"), job.scheduled_start),
+            (t!("app.details.lifecycle.started"), job.start_time),
+            (t!("app.details.lifecycle.stopped"), job.stop_time),
+        ];
+
+        egui::Grid::new(ui.next_auto_id()).num_columns(3).show(ui, |ui| {
+            let mut previous: Option<i64> = None;
+            for (label, time) in steps {
+                ui.label(format!("{}:", label));
+                if time > 0 {
+                    ui.strong(format_timestamp(time));
+                } else {
+                    ui.weak("n/a");
+                }
+                match previous {
+                    Some(previous) if time > 0 => {
+                        let elapsed = time - previous;
+                        let sign = if elapsed >= 0 { "+" } else { "" };
+                        ui.label(format!("{}{}", sign, format_duration(elapsed)));
+                    }
+                    _ => {
+                        ui.label("");
+                    }
+                }
+                if time > 0 {
+                    previous = Some(time);
+                }
+                ui.end_row();
+            }
+        });
+    }
+
+    fn strata_ui(ui: &mut egui::Ui, strata: &Strata) {
+        let mut cpuset = strata.cpuset.as_ref().map(parse_cpuset).unwrap_or_default();
+        let gpudevice = strata
+            .gpudevice
+            .as_ref()
+            .filter(|v| !v.is_null())
+            .map(|v| match v {
+                serde_json::Value::String(s) => s.clone(),
+                other => other.to_string(),
+            });
+
+        let fields = [
+            ("cpuset", Some(format_cpuset_ranges(&mut cpuset))),
+            ("cputype", strata.cputype.clone()),
+            ("nodemodel", strata.nodemodel.clone()),
+            ("gpudevice", gpudevice),
+            ("network_address", strata.network_address.clone()),
+        ];
+
+        egui::Grid::new(ui.next_auto_id()).num_columns(2).show(ui, |ui| {
+            for (name, value) in fields {
+                let value = value.filter(|v| !v.trim().is_empty());
+                ui.label(format!("{}:", name));
+                ui.strong(value.unwrap_or_else(|| "N/A".to_string()));
+                ui.end_row();
+            }
         });
     }
 
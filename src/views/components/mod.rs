@@ -1,4 +1,10 @@
 pub mod dashboard_components;
 pub mod gantt_aggregate_by;
+pub mod gantt_gutter_click;
+pub mod gantt_host_sort;
 pub mod gantt_job_color;
+pub mod gantt_strata_fields;
+pub mod gantt_time_basis;
+pub mod gantt_tooltip_mode;
+pub mod gantt_zoom_anchor;
 pub mod job_details;
@@ -1,4 +1,5 @@
 use crate::models::data_structure::job::JobState;
+use crate::models::utils::utils::cluster_resource_summary;
 use crate::views::components::dashboard_components::job_table::JobTable;
 use crate::views::components::dashboard_components::metric_box::MetricBox;
 use crate::views::components::dashboard_components::metric_chart::create_jobstate_chart;
@@ -89,6 +90,11 @@ impl View for Dashboard {
                 }
             });
 
+            ui.add_space(10.0);
+
+            // Size of each cluster (hosts, cores, threads, GPUs)
+            Self::clusters_summary(ui, app);
+
             ui.add_space(10.0);
             ui.separator();
 
@@ -97,3 +103,53 @@ impl View for Dashboard {
         });
     }
 }
+
+impl Dashboard {
+    fn clusters_summary(ui: &mut egui::Ui, app: &ApplicationContext) {
+        if app.all_clusters.is_empty() {
+            return;
+        }
+
+        egui::CollapsingHeader::new(t!("app.dashboard.clusters.title"))
+            .default_open(false)
+            .show(ui, |ui| {
+                egui::Grid::new("clusters_summary_grid")
+                    .num_columns(5)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.strong(t!("app.dashboard.clusters.name"));
+                        ui.strong(t!("app.dashboard.clusters.hosts"));
+                        ui.strong(t!("app.dashboard.clusters.cores"));
+                        ui.strong(t!("app.dashboard.clusters.threads"));
+                        ui.strong(t!("app.dashboard.clusters.gpus"));
+                        ui.end_row();
+
+                        let mut total = (0, 0, 0, 0);
+                        for cluster in &app.all_clusters {
+                            let (hosts, cores, threads, gpus) =
+                                cluster_resource_summary(cluster, &app.gpu_count_by_host);
+                            ui.label(&cluster.name);
+                            ui.label(hosts.to_string());
+                            ui.label(cores.to_string());
+                            ui.label(threads.to_string());
+                            ui.label(gpus.to_string());
+                            ui.end_row();
+
+                            total.0 += hosts;
+                            total.1 += cores;
+                            total.2 += threads;
+                            total.3 += gpus;
+                        }
+
+                        if app.all_clusters.len() > 1 {
+                            ui.strong(t!("app.dashboard.clusters.total"));
+                            ui.strong(total.0.to_string());
+                            ui.strong(total.1.to_string());
+                            ui.strong(total.2.to_string());
+                            ui.strong(total.3.to_string());
+                            ui.end_row();
+                        }
+                    });
+            });
+    }
+}
@@ -0,0 +1,461 @@
+use super::types::Options;
+use crate::models::data_structure::application_context::{ApplicationContext, UNKNOWN_GROUP};
+use crate::models::data_structure::cluster::Cluster;
+use crate::models::data_structure::job::Job;
+use crate::views::components::gantt_aggregate_by::{AggregateBy, AggregateByLevel1Enum, AggregateByLevel2Enum};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Groupes de niveau 1 : libellé → indices des jobs dans `filtered_jobs`.
+pub(super) type Level1Groups = BTreeMap<String, Vec<usize>>;
+/// Groupes de niveau 2 : libellé niveau 1 → libellé niveau 2 → indices des jobs.
+pub(super) type Level2Groups = BTreeMap<String, BTreeMap<String, Vec<usize>>>;
+
+pub(super) enum Aggregation {
+    Empty,
+    Level1(Level1Groups),
+    Level2(Level2Groups),
+}
+
+/// Entrées de l'agrégation : mode, générations des jobs filtrés et des clusters
+/// (voir `ApplicationContext::filtered_jobs_generation`), clusters du preset.
+#[derive(PartialEq)]
+struct AggregationKey {
+    aggregate_by: (AggregateByLevel1Enum, AggregateByLevel2Enum),
+    show_all_hosts: bool,
+    filtered_jobs_generation: u64,
+    clusters_generation: u64,
+    preset_clusters: Vec<String>,
+}
+
+/// Résultat de l'agrégation conservé entre deux frames.
+/// Il n'est reconstruit que lorsque la clé (mode d'agrégation, jobs filtrés,
+/// clusters affichés) change ; le reste du temps le canvas relit la structure existante.
+pub(super) struct AggregationCache {
+    key: Option<AggregationKey>,
+    aggregation: Aggregation,
+}
+
+impl Default for AggregationCache {
+    fn default() -> Self {
+        Self {
+            key: None,
+            aggregation: Aggregation::Empty,
+        }
+    }
+}
+
+impl AggregationCache {
+    /// Retourne l'agrégation courante, en la recalculant si les entrées ont changé.
+    pub(super) fn get(
+        &mut self,
+        options: &Options,
+        app: &ApplicationContext,
+        filtered_clusters: &[Cluster],
+    ) -> &Aggregation {
+        let key = AggregationKey {
+            aggregate_by: (options.aggregate_by.level_1, options.aggregate_by.level_2),
+            show_all_hosts: options.show_all_hosts,
+            filtered_jobs_generation: app.filtered_jobs_generation,
+            clusters_generation: app.clusters_generation,
+            preset_clusters: filtered_clusters.iter().map(|cluster| cluster.name.clone()).collect(),
+        };
+        if self.key.as_ref() != Some(&key) {
+            self.aggregation = build_aggregation(
+                &app.filtered_jobs,
+                filtered_clusters,
+                &app.all_clusters,
+                &options.aggregate_by,
+                options.show_all_hosts,
+            );
+            self.key = Some(key);
+        }
+        &self.aggregation
+    }
+
+    /// Clés des groupes de la dernière agrégation calculée : niveau 1, puis (niveau 1, niveau 2).
+    pub(super) fn group_keys(&self) -> (Vec<String>, Vec<(String, String)>) {
+        match &self.aggregation {
+            Aggregation::Empty => (Vec::new(), Vec::new()),
+            Aggregation::Level1(groups) => (groups.keys().cloned().collect(), Vec::new()),
+            Aggregation::Level2(groups) => (
+                groups.keys().cloned().collect(),
+                groups
+                    .iter()
+                    .flat_map(|(level_1, level_2_map)| {
+                        level_2_map.keys().map(move |level_2| (level_1.clone(), level_2.clone()))
+                    })
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// Clusters affichés : ceux du preset sélectionné dans les filtres globaux, sinon tous.
+pub(super) fn preset_clusters(app: &ApplicationContext) -> Vec<Cluster> {
+    let selected_cluster_names: Option<Vec<String>> = app
+        .filters
+        .selected_preset
+        .as_ref()
+        .and_then(|name| app.cluster_presets.iter().find(|p| p.name == *name))
+        .map(|p| p.clusters.clone());
+    if let Some(names) = selected_cluster_names {
+        app.all_clusters.iter().filter(|c| names.contains(&c.name)).cloned().collect()
+    } else {
+        app.all_clusters.clone()
+    }
+}
+
+/// Libellé d'une ligne de ressource (`r<id>`), trié naturellement par `compare_string_with_number`.
+pub(super) fn resource_row_label(resource_id: u32) -> String {
+    format!("r{}", resource_id)
+}
+
+pub(super) fn resource_id_from_row_label(label: &str) -> Option<u32> {
+    label.strip_prefix('r')?.parse().ok()
+}
+
+/// Regroupe les jobs en un seul passage, à l'aide d'index de noms pré-calculés
+/// plutôt que de parcourir les clusters pour chaque job.
+///
+/// Seule source du regroupement : le canvas (via `AggregationCache`) et l'export JSON du résumé
+/// d'agrégation l'appellent. `filtered_clusters` restreint les groupes aux clusters du preset
+/// (vide : pas de restriction) ; avec `show_all_hosts`, chaque hôte connu a sa ligne en vue
+/// Cluster → Hôte, même sans job.
+pub(super) fn build_aggregation(
+    jobs: &[Job],
+    filtered_clusters: &[Cluster],
+    all_clusters: &[Cluster],
+    aggregate_by: &AggregateBy,
+    show_all_hosts: bool,
+) -> Aggregation {
+    let restrict = !filtered_clusters.is_empty();
+    let allowed_clusters: HashSet<&str> = filtered_clusters.iter().map(|c| c.name.as_str()).collect();
+    let allowed_hosts: HashSet<&str> = filtered_clusters
+        .iter()
+        .flat_map(|c| c.hosts.iter().map(|h| h.name.as_str()))
+        .collect();
+    // Les jobs orphelins (groupe `unknown`) restent visibles quel que soit le preset
+    let cluster_allowed = |name: &str| !restrict || name == UNKNOWN_GROUP || allowed_clusters.contains(name);
+    let host_allowed = |name: &str| !restrict || name == UNKNOWN_GROUP || allowed_hosts.contains(name);
+
+    match (aggregate_by.level_1, aggregate_by.level_2) {
+        (AggregateByLevel1Enum::Owner, _) => {
+            let mut groups = Level1Groups::new();
+            for (index, job) in jobs.iter().enumerate() {
+                groups.entry(job.owner.clone()).or_default().push(index);
+            }
+            Aggregation::Level1(groups)
+        }
+
+        (AggregateByLevel1Enum::Host, AggregateByLevel2Enum::Owner) => {
+            let mut groups = Level2Groups::new();
+            for (index, job) in jobs.iter().enumerate() {
+                for host in job.hosts.iter() {
+                    if !host_allowed(host) {
+                        continue;
+                    }
+                    groups
+                        .entry(host.clone())
+                        .or_default()
+                        .entry(job.owner.clone())
+                        .or_default()
+                        .push(index);
+                }
+            }
+            Aggregation::Level2(groups)
+        }
+
+        (AggregateByLevel1Enum::Host, AggregateByLevel2Enum::None) => {
+            let mut groups = Level1Groups::new();
+            for (index, job) in jobs.iter().enumerate() {
+                for host in job.hosts.iter() {
+                    if !host_allowed(host) {
+                        continue;
+                    }
+                    groups.entry(host.clone()).or_default().push(index);
+                }
+            }
+            Aggregation::Level1(groups)
+        }
+
+        // Une ligne par ressource OAR de l'hôte, les jobs placés selon `assigned_resources`
+        (AggregateByLevel1Enum::Host, AggregateByLevel2Enum::Resource) => {
+            let clusters = if restrict { filtered_clusters } else { all_clusters };
+            let host_by_resource: HashMap<u32, &str> = clusters
+                .iter()
+                .flat_map(|c| c.hosts.iter())
+                .flat_map(|host| {
+                    host.cpus
+                        .iter()
+                        .flat_map(|cpu| cpu.resources.iter())
+                        .map(move |resource| (resource.id, host.name.as_str()))
+                })
+                .collect();
+
+            let mut groups = Level2Groups::new();
+            for (index, job) in jobs.iter().enumerate() {
+                for resource_id in job.assigned_resources.iter() {
+                    if let Some(host) = host_by_resource.get(resource_id) {
+                        groups
+                            .entry(host.to_string())
+                            .or_default()
+                            .entry(resource_row_label(*resource_id))
+                            .or_default()
+                            .push(index);
+                    }
+                }
+            }
+            Aggregation::Level2(groups)
+        }
+
+        // Cas non utilisés : Host n’a pas de fonction ici, Resource n'existe que sous Host
+        (AggregateByLevel1Enum::Host, AggregateByLevel2Enum::Host)
+        | (AggregateByLevel1Enum::Cluster, AggregateByLevel2Enum::Resource) => Aggregation::Empty,
+
+        (AggregateByLevel1Enum::Cluster, AggregateByLevel2Enum::Owner) => {
+            let mut groups = Level2Groups::new();
+            for (index, job) in jobs.iter().enumerate() {
+                for cluster in job.clusters.iter() {
+                    if !cluster_allowed(cluster) {
+                        continue;
+                    }
+                    groups
+                        .entry(cluster.clone())
+                        .or_default()
+                        .entry(job.owner.clone())
+                        .or_default()
+                        .push(index);
+                }
+            }
+            Aggregation::Level2(groups)
+        }
+
+        (AggregateByLevel1Enum::Cluster, AggregateByLevel2Enum::None) => {
+            let mut groups = Level1Groups::new();
+            for (index, job) in jobs.iter().enumerate() {
+                for cluster in job.clusters.iter() {
+                    if !cluster_allowed(cluster) {
+                        continue;
+                    }
+                    groups.entry(cluster.clone()).or_default().push(index);
+                }
+            }
+            Aggregation::Level1(groups)
+        }
+
+        (AggregateByLevel1Enum::Cluster, AggregateByLevel2Enum::Host) => {
+            let mut groups = Level2Groups::new();
+
+            // Pré-remplit une ligne vide pour chaque hôte connu, même inactif
+            if show_all_hosts {
+                for cluster in filtered_clusters.iter() {
+                    let hosts = groups.entry(cluster.name.clone()).or_default();
+                    for host in cluster.hosts.iter() {
+                        hosts.entry(host.name.clone()).or_default();
+                    }
+                }
+            }
+
+            let hosts_by_cluster: HashMap<&str, HashSet<&str>> = all_clusters
+                .iter()
+                .map(|c| (c.name.as_str(), c.hosts.iter().map(|h| h.name.as_str()).collect()))
+                .collect();
+
+            for (index, job) in jobs.iter().enumerate() {
+                for cluster_name in job.clusters.iter() {
+                    if !cluster_allowed(cluster_name) {
+                        continue;
+                    }
+                    if cluster_name == UNKNOWN_GROUP {
+                        groups
+                            .entry(cluster_name.clone())
+                            .or_default()
+                            .entry(UNKNOWN_GROUP.to_string())
+                            .or_default()
+                            .push(index);
+                        continue;
+                    }
+
+                    let cluster_hosts = match hosts_by_cluster.get(cluster_name.as_str()) {
+                        Some(hosts) => hosts,
+                        None => continue,
+                    };
+
+                    for host in job.hosts.iter() {
+                        if cluster_hosts.contains(host.as_str()) {
+                            groups
+                                .entry(cluster_name.clone())
+                                .or_default()
+                                .entry(host.clone())
+                                .or_default()
+                                .push(index);
+                        }
+                    }
+                }
+            }
+            Aggregation::Level2(groups)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::data_structure::cluster::test_cluster;
+    use crate::models::data_structure::job::test_job;
+    use AggregateByLevel1Enum as L1;
+    use AggregateByLevel2Enum as L2;
+
+    // dahu (dahu-1 : r1 r2, dahu-2 : r3, dahu-3 sans job) et gros (gros-1 : r4)
+    fn clusters() -> Vec<Cluster> {
+        vec![
+            test_cluster("dahu", &[("dahu-1", &[1, 2]), ("dahu-2", &[3]), ("dahu-3", &[5])]),
+            test_cluster("gros", &[("gros-1", &[4])]),
+        ]
+    }
+
+    fn job(id: u32, owner: &str, clusters: &[&str], hosts: &[&str], resources: &[u32]) -> Job {
+        Job {
+            owner: owner.to_string(),
+            clusters: clusters.iter().map(|name| name.to_string()).collect(),
+            hosts: hosts.iter().map(|name| name.to_string()).collect(),
+            assigned_resources: resources.to_vec(),
+            ..test_job(id, 0, 100)
+        }
+    }
+
+    // 0 : alice sur dahu-1 ; 1 : bob sur dahu-2 et gros-1 ; 2 : alice, orphelin (ressource inconnue)
+    fn jobs() -> Vec<Job> {
+        vec![
+            job(1, "alice", &["dahu"], &["dahu-1"], &[1]),
+            job(2, "bob", &["dahu", "gros"], &["dahu-2", "gros-1"], &[3, 4]),
+            job(3, "alice", &[UNKNOWN_GROUP], &[UNKNOWN_GROUP], &[99]),
+        ]
+    }
+
+    fn aggregate(level_1: L1, level_2: L2) -> Aggregation {
+        aggregate_in_preset(level_1, level_2, &[], false)
+    }
+
+    fn aggregate_in_preset(level_1: L1, level_2: L2, preset: &[Cluster], show_all: bool) -> Aggregation {
+        build_aggregation(&jobs(), preset, &clusters(), &AggregateBy { level_1, level_2 }, show_all)
+    }
+
+    type Groups<'a> = &'a [(&'a str, &'a [usize])];
+
+    fn level_1(groups: Groups) -> Level1Groups {
+        groups.iter().map(|(key, indices)| (key.to_string(), indices.to_vec())).collect()
+    }
+
+    fn level_2(groups: &[(&str, Groups)]) -> Level2Groups {
+        groups.iter().map(|(key, subgroups)| (key.to_string(), level_1(subgroups))).collect()
+    }
+
+    fn as_level_1(aggregation: Aggregation) -> Level1Groups {
+        match aggregation {
+            Aggregation::Level1(groups) => groups,
+            _ => panic!("agrégation à un niveau attendue"),
+        }
+    }
+
+    fn as_level_2(aggregation: Aggregation) -> Level2Groups {
+        match aggregation {
+            Aggregation::Level2(groups) => groups,
+            _ => panic!("agrégation à deux niveaux attendue"),
+        }
+    }
+
+    #[test]
+    fn owner() {
+        for level_2_kind in [L2::None, L2::Host] {
+            let groups = as_level_1(aggregate(L1::Owner, level_2_kind));
+            assert_eq!(groups, level_1(&[("alice", &[0, 2]), ("bob", &[1])]));
+        }
+    }
+
+    #[test]
+    fn host_then_owner() {
+        let groups = as_level_2(aggregate(L1::Host, L2::Owner));
+        let expected = level_2(&[
+            ("dahu-1", &[("alice", &[0])]),
+            ("dahu-2", &[("bob", &[1])]),
+            ("gros-1", &[("bob", &[1])]),
+            (UNKNOWN_GROUP, &[("alice", &[2])]),
+        ]);
+        assert_eq!(groups, expected);
+    }
+
+    #[test]
+    fn host_alone() {
+        let groups = as_level_1(aggregate(L1::Host, L2::None));
+        let expected = level_1(&[
+            ("dahu-1", &[0]),
+            ("dahu-2", &[1]),
+            ("gros-1", &[1]),
+            (UNKNOWN_GROUP, &[2]),
+        ]);
+        assert_eq!(groups, expected);
+    }
+
+    #[test]
+    fn host_then_resource() {
+        let groups = as_level_2(aggregate(L1::Host, L2::Resource));
+        // La ressource orpheline r99 n'a pas d'hôte : pas de ligne
+        let expected = level_2(&[
+            ("dahu-1", &[("r1", &[0])]),
+            ("dahu-2", &[("r3", &[1])]),
+            ("gros-1", &[("r4", &[1])]),
+        ]);
+        assert_eq!(groups, expected);
+    }
+
+    #[test]
+    fn cluster_then_owner() {
+        let groups = as_level_2(aggregate(L1::Cluster, L2::Owner));
+        let expected = level_2(&[
+            ("dahu", &[("alice", &[0]), ("bob", &[1])]),
+            ("gros", &[("bob", &[1])]),
+            (UNKNOWN_GROUP, &[("alice", &[2])]),
+        ]);
+        assert_eq!(groups, expected);
+    }
+
+    #[test]
+    fn cluster_alone() {
+        let groups = as_level_1(aggregate(L1::Cluster, L2::None));
+        assert_eq!(groups, level_1(&[("dahu", &[0, 1]), ("gros", &[1]), (UNKNOWN_GROUP, &[2])]));
+    }
+
+    #[test]
+    fn cluster_then_host() {
+        let groups = as_level_2(aggregate(L1::Cluster, L2::Host));
+        let expected = level_2(&[
+            ("dahu", &[("dahu-1", &[0]), ("dahu-2", &[1])]),
+            ("gros", &[("gros-1", &[1])]),
+            (UNKNOWN_GROUP, &[(UNKNOWN_GROUP, &[2])]),
+        ]);
+        assert_eq!(groups, expected);
+    }
+
+    #[test]
+    fn cluster_then_host_restricted_to_the_preset_with_all_hosts() {
+        let preset = vec![clusters().remove(0)];
+        let groups = as_level_2(aggregate_in_preset(L1::Cluster, L2::Host, &preset, true));
+        // gros est hors du preset ; les orphelins restent visibles ; dahu-3 a sa ligne vide
+        let expected = level_2(&[
+            ("dahu", &[("dahu-1", &[0]), ("dahu-2", &[1]), ("dahu-3", &[])]),
+            (UNKNOWN_GROUP, &[(UNKNOWN_GROUP, &[2])]),
+        ]);
+        assert_eq!(groups, expected);
+    }
+
+    #[test]
+    fn unused_combinations_are_empty() {
+        for (level_1_kind, level_2_kind) in [
+            (L1::Host, L2::Host),
+            (L1::Cluster, L2::Resource),
+        ] {
+            assert!(matches!(aggregate(level_1_kind, level_2_kind), Aggregation::Empty));
+        }
+    }
+}
@@ -1,13 +1,10 @@
+use super::aggregation::{preset_clusters, Aggregation, AggregationCache};
 use super::jobs::{paint_aggregated_jobs_level_1, paint_aggregated_jobs_level_2, paint_tooltip};
 use super::theme::get_theme_colors;
-use super::timeline::paint_timeline_text_on_top;
+use super::timeline::{paint_future_shading, paint_timeline_text_on_top};
 use super::types::{Info, Options};
 use crate::models::data_structure::application_context::ApplicationContext;
 use crate::models::data_structure::cluster::Cluster;
-use crate::models::data_structure::job::Job;
-use crate::models::utils::utils::{
-    cluster_contain_host, contains_cluster, contains_host, get_cluster_from_name,
-};
 use crate::views::components::gantt_aggregate_by::{AggregateByLevel1Enum, AggregateByLevel2Enum};
 use crate::views::components::job_details::JobDetailsWindow;
 use egui::{pos2, Rect, Stroke};
@@ -24,21 +21,16 @@ pub(super) fn ui_canvas(
     details_window: &mut Vec<JobDetailsWindow>,
     collapsed_jobs_level_1: &mut BTreeMap<String, bool>,
     collapsed_jobs_level_2: &mut BTreeMap<(String, String), bool>,
+    aggregation_cache: &mut AggregationCache,
     all_cluster: &Vec<Cluster>,
     gutter_width: f32,
 ) -> f32 {
-    // si un Preset est  sélectionné dans les filtres globaux.
-    // On limite l'affichage aux clusters contenus dans ce preset.
-    let selected_cluster_names: Option<Vec<String>> = app.filters.selected_preset.as_ref()
-        .and_then(|name| app.cluster_presets.iter().find(|p| p.name == *name))
-        .map(|p| p.clusters.clone());
-    let filtered_clusters: Vec<Cluster> = if let Some(names) = selected_cluster_names {
-        app.all_clusters.iter().filter(|c| names.contains(&c.name)).cloned().collect()
-    } else {
-        app.all_clusters.clone()
-    };
+    // Si un preset est sélectionné dans les filtres globaux, l'affichage est limité à ses clusters
+    let filtered_clusters = preset_clusters(app);
 
     options.hovered_grid5000_host = None;
+    options.focusable_jobs.clear();
+    options.selected_job_rect = None;
 
     if options.canvas_width_s <= 0.0 {
         options.canvas_width_s = (max_ns - min_ns) as f32;
@@ -52,17 +44,12 @@ pub(super) fn ui_canvas(
 
     let is_grid5000 = options.aggregate_by.level_1 == AggregateByLevel1Enum::Cluster
         && options.aggregate_by.level_2 == AggregateByLevel2Enum::Host;
-    let gutter_yellow = egui::Color32::from_rgb(252, 238, 170);
     // Le "gutter" correspond à la colonne de gauche contenant les labels
     // (cluster / host / owner).
     let gutter_bg = if is_grid5000 {
-        if info.ctx.style().visuals.dark_mode {
-            egui::Color32::from_gray(20)
-        } else {
-            egui::Color32::WHITE
-        }
+        theme_colors.gutter_background_grid5000
     } else {
-        gutter_yellow
+        theme_colors.gutter_background
     };
 
     let gutter_rect = Rect::from_min_max(
@@ -81,228 +68,57 @@ pub(super) fn ui_canvas(
         );
     }
 
-    let jobs = &app.filtered_jobs;
+    // Voile sur le futur, dessiné avant les barres
+    if options.shade_future {
+        if let Some(shading) = paint_future_shading(info, options, info.canvas, gutter_width) {
+            info.painter.add(shading);
+        }
+    }
 
-    // Regroupement des jobs selon le niveau d’agrégation sélectionné
-    match options.aggregate_by.level_1 {
-        AggregateByLevel1Enum::Owner => {
-            let mut jobs_by_owner: BTreeMap<String, Vec<&Job>> = BTreeMap::new();
-            for job in jobs.iter() {
-                jobs_by_owner
-                    .entry(job.owner.clone())
-                    .or_insert_with(Vec::new)
-                    .push(job);
-            }
+    let jobs = &app.filtered_jobs;
 
+    // Regroupement des jobs selon le niveau d’agrégation sélectionné.
+    // Le résultat est mis en cache et n’est recalculé que si les jobs ou le mode changent.
+    let aggregate_by_level_1 = options.aggregate_by.level_1;
+    let aggregate_by_level_2 = options.aggregate_by.level_2;
+    match aggregation_cache.get(options, app, &filtered_clusters) {
+        Aggregation::Level1(groups) => {
             cursor_y = paint_aggregated_jobs_level_1(
                 info,
                 options,
-                jobs_by_owner,
+                jobs,
+                groups,
                 cursor_y,
                 details_window,
                 collapsed_jobs_level_1,
                 app.font_size,
                 all_cluster,
-                AggregateByLevel1Enum::Owner,
+                aggregate_by_level_1,
                 gutter_width,
                 app,
             );
         }
 
-        AggregateByLevel1Enum::Host => match options.aggregate_by.level_2 {
-            AggregateByLevel2Enum::Owner => {
-                let mut jobs_by_host_by_owner: BTreeMap<String, BTreeMap<String, Vec<&Job>>> =
-                    BTreeMap::new();
-                let filtered_clusters = filtered_clusters.clone();
-
-                for job in jobs.iter() {
-                    for host in job.hosts.iter() {
-                        if filtered_clusters.len() != 0 && !contains_host(&filtered_clusters, host) {
-                            continue;
-                        }
-                        jobs_by_host_by_owner
-                            .entry(host.clone())
-                            .or_insert_with(BTreeMap::new)
-                            .entry(job.owner.clone())
-                            .or_insert_with(Vec::new)
-                            .push(job);
-                    }
-                }
-
-                cursor_y = paint_aggregated_jobs_level_2(
-                    info,
-                    options,
-                    jobs_by_host_by_owner,
-                    cursor_y,
-                    details_window,
-                    collapsed_jobs_level_1,
-                    collapsed_jobs_level_2,
-                    app.font_size,
-                    all_cluster,
-                    AggregateByLevel1Enum::Host,
-                    AggregateByLevel2Enum::Owner,
-                    gutter_width,
-                    app,
-                );
-            }
-
-            AggregateByLevel2Enum::None => {
-                let mut jobs_by_host: BTreeMap<String, Vec<&Job>> = BTreeMap::new();
-                let filtered_clusters = filtered_clusters.clone();
-
-                for job in jobs.iter() {
-                    for host in job.hosts.iter() {
-                        if filtered_clusters.len() != 0 && !contains_host(&filtered_clusters, host) {
-                            continue;
-                        }
-                        jobs_by_host
-                            .entry(host.clone())
-                            .or_insert_with(Vec::new)
-                            .push(job);
-                    }
-                }
-
-                cursor_y = paint_aggregated_jobs_level_1(
-                    info,
-                    options,
-                    jobs_by_host,
-                    cursor_y,
-                    details_window,
-                    collapsed_jobs_level_1,
-                    app.font_size,
-                    all_cluster,
-                    AggregateByLevel1Enum::Host,
-                    gutter_width,
-                    app,
-                );
-            }
-
-            AggregateByLevel2Enum::Host => {
-                // Cas non utilisé : Host n’a pas de fonction ici
-            }
-        },
-
-        AggregateByLevel1Enum::Cluster => match options.aggregate_by.level_2 {
-            AggregateByLevel2Enum::Owner => {
-                let mut jobs_by_cluster_by_owner: BTreeMap<String, BTreeMap<String, Vec<&Job>>> =
-                    BTreeMap::new();
-                let filtered_clusters = filtered_clusters.clone();
-
-                for job in jobs.iter() {
-                    for cluster in job.clusters.iter() {
-                        if filtered_clusters.len() != 0
-                            && !contains_cluster(&filtered_clusters, cluster)
-                        {
-                            continue;
-                        }
-                        jobs_by_cluster_by_owner
-                            .entry(cluster.clone())
-                            .or_insert_with(BTreeMap::new)
-                            .entry(job.owner.clone())
-                            .or_insert_with(Vec::new)
-                            .push(job);
-                    }
-                }
-
-                cursor_y = paint_aggregated_jobs_level_2(
-                    info,
-                    options,
-                    jobs_by_cluster_by_owner,
-                    cursor_y,
-                    details_window,
-                    collapsed_jobs_level_1,
-                    collapsed_jobs_level_2,
-                    app.font_size,
-                    all_cluster,
-                    AggregateByLevel1Enum::Cluster,
-                    AggregateByLevel2Enum::Owner,
-                    gutter_width,
-                    app,
-                );
-            }
-
-            AggregateByLevel2Enum::None => {
-                let mut jobs_by_cluster: BTreeMap<String, Vec<&Job>> = BTreeMap::new();
-                let filtered_clusters = filtered_clusters.clone();
-
-                for job in jobs.iter() {
-                    for cluster in job.clusters.iter() {
-                        if filtered_clusters.len() != 0
-                            && !contains_cluster(&filtered_clusters, cluster)
-                        {
-                            continue;
-                        }
-                        jobs_by_cluster
-                            .entry(cluster.clone())
-                            .or_insert_with(Vec::new)
-                            .push(job);
-                    }
-                }
-
-                cursor_y = paint_aggregated_jobs_level_1(
-                    info,
-                    options,
-                    jobs_by_cluster,
-                    cursor_y,
-                    details_window,
-                    collapsed_jobs_level_1,
-                    app.font_size,
-                    all_cluster,
-                    AggregateByLevel1Enum::Cluster,
-                    gutter_width,
-                    app,
-                );
-            }
-
-            AggregateByLevel2Enum::Host => {
-                let mut jobs_by_cluster_by_host: BTreeMap<String, BTreeMap<String, Vec<&Job>>> =
-                    BTreeMap::new();
-                let filtered_clusters = filtered_clusters.clone();
-
-                for job in jobs.iter() {
-                    for cluster_name in job.clusters.iter() {
-                        if filtered_clusters.len() != 0
-                            && !contains_cluster(&filtered_clusters, cluster_name)
-                        {
-                            continue;
-                        }
-
-                        let curr_cluster = match get_cluster_from_name(&app.all_clusters, cluster_name)
-                        {
-                            Some(c) => c,
-                            None => continue,
-                        };
-
-                        for host in job.hosts.iter() {
-                            if cluster_contain_host(&curr_cluster, host) {
-                                jobs_by_cluster_by_host
-                                    .entry(cluster_name.clone())
-                                    .or_insert_with(BTreeMap::new)
-                                    .entry(host.clone())
-                                    .or_insert_with(Vec::new)
-                                    .push(job);
-                            }
-                        }
-                    }
-                }
+        Aggregation::Level2(groups) => {
+            cursor_y = paint_aggregated_jobs_level_2(
+                info,
+                options,
+                jobs,
+                groups,
+                cursor_y,
+                details_window,
+                collapsed_jobs_level_1,
+                collapsed_jobs_level_2,
+                app.font_size,
+                all_cluster,
+                aggregate_by_level_1,
+                aggregate_by_level_2,
+                gutter_width,
+                app,
+            );
+        }
 
-                cursor_y = paint_aggregated_jobs_level_2(
-                    info,
-                    options,
-                    jobs_by_cluster_by_host,
-                    cursor_y,
-                    details_window,
-                    collapsed_jobs_level_1,
-                    collapsed_jobs_level_2,
-                    app.font_size,
-                    all_cluster,
-                    AggregateByLevel1Enum::Cluster,
-                    AggregateByLevel2Enum::Host,
-                    gutter_width,
-                    app,
-                );
-            }
-        },
+        Aggregation::Empty => {}
     }
     // Tooltip global + texte de timeline
     paint_tooltip(info, options, app);
@@ -1,25 +1,59 @@
+use crate::models::data_structure::application_context::ApplicationContext;
 use crate::models::data_structure::job::Job;
-/// Estimation de la puissance globale (W) sur une fenêtre [start_s, end_s].
 
+/// Pas minimal (s) de la série énergie.
+pub const MIN_ENERGY_STEP_S: i64 = 1;
+/// Nombre maximal de points de la série : au-delà, le pas est élargi.
+pub const MAX_ENERGY_POINTS: i64 = 20_000;
+
+/// Pas effectif pour la fenêtre [start_s, end_s] : le pas demandé, au moins `MIN_ENERGY_STEP_S`,
+/// élargi si besoin pour ne pas dépasser `MAX_ENERGY_POINTS` points.
+fn effective_step_s(start_s: i64, end_s: i64, step_s: i64) -> i64 {
+    let width_s = (end_s - start_s).max(0);
+    let min_step_for_cap = (width_s + MAX_ENERGY_POINTS - 1) / MAX_ENERGY_POINTS;
+    step_s.max(MIN_ENERGY_STEP_S).max(min_step_for_cap)
+}
+
+/// Unités consommatrices d'un job : ses ressources assignées, sinon ses hôtes, sinon 0.
+pub fn energy_units(job: &Job) -> usize {
+    if !job.assigned_resources.is_empty() {
+        job.assigned_resources.len()
+    } else {
+        job.hosts.len()
+    }
+}
+
+/// Estimation de la puissance globale (W) sur une fenêtre [start_s, end_s].
+///
 /// - Si `assigned_resources` est rempli => unités = assigned_resources.len()
-/// - Sinon => unités = hosts.len() 
+/// - Sinon => unités = hosts.len()
 /// - Puissance = unités * watts_per_unit
 ///
-/// `step_s` = pas en secondes (ex: 10 => 1 point toutes les 10s)
+/// `step_s` = pas demandé en secondes (ex: 10 => 1 point toutes les 10s). Il est élargi si la
+/// fenêtre donnerait plus de `MAX_ENERGY_POINTS` points ; le pas effectif est renvoyé avec la série.
+///
+/// Contrat (utilisé par le graphe énergie) :
+/// - aucun job, fenêtre vide (`end_s <= start_s`) ou `step_s <= 0` => série vide ;
+/// - sinon un point à `start_s`, puis tous les pas effectifs, le dernier étant `<= end_s` ;
+/// - un job compte sur [scheduled_start, scheduled_start + walltime], bornes incluses,
+///   donc un job couvrant toute la fenêtre donne un plateau `unités * watts_per_unit` ;
+/// - les jobs qui se chevauchent additionnent leurs unités ;
+/// - un job sans ressource ni hôte ne compte pas (0 W).
 pub fn estimate_global_energy_series(
-    jobs: &[Job],
+    jobs: &[&Job],
     start_s: i64,
     end_s: i64,
     step_s: i64,
     watts_per_unit: f64,
-) -> Vec<(i64, f64)> {
-    if end_s <= start_s || step_s <= 0 {
-        return Vec::new();
+) -> (Vec<(i64, f64)>, i64) {
+    if jobs.is_empty() || end_s <= start_s || step_s <= 0 {
+        return (Vec::new(), step_s);
     }
+    let step_s = effective_step_s(start_s, end_s, step_s);
 
     // Garder seulement les jobs
     let mut relevant: Vec<&Job> = Vec::new();
-    for j in jobs {
+    for &j in jobs {
         let js = j.scheduled_start;
         let je = j.scheduled_start + j.walltime as i64;
         if je >= start_s && js <= end_s {
@@ -38,15 +72,7 @@ pub fn estimate_global_energy_series(
             let je = j.scheduled_start + j.walltime as i64;
 
             if js <= t && t <= je {
-                let units = if !j.assigned_resources.is_empty() {
-                    j.assigned_resources.len()
-                } else if !j.hosts.is_empty() {
-                    j.hosts.len()
-                } else {
-                    0
-                };
-
-                total_units += units;
+                total_units += energy_units(j);
             }
         }
 
@@ -55,5 +81,152 @@ pub fn estimate_global_energy_series(
         t += step_s;
     }
 
-    out
-}
\ No newline at end of file
+    (out, step_s)
+}
+
+/// Dernière série énergie calculée. Elle n'est recalculée que si la fenêtre, les jobs filtrés
+/// (`filtered_jobs_generation`), les filtres propres au graphe (cluster, propriétaire) ou les
+/// paramètres changent : les frames sans navigation ne font que comparer la clé.
+#[derive(Default)]
+pub struct EnergySeriesCache {
+    key: Option<EnergySeriesKey>,
+    points: Vec<(i64, f64)>,
+    step_s: i64,
+}
+
+#[derive(PartialEq)]
+struct EnergySeriesKey {
+    window: (i64, i64),
+    filtered_jobs_generation: u64,
+    cluster: Option<String>,
+    owner: Option<String>,
+    step_s: i64,
+    watts_per_unit: u64,
+}
+
+impl EnergySeriesCache {
+    /// `cluster` / `owner` : filtres du graphe énergie, appliqués en plus des filtres globaux.
+    pub fn update(
+        &mut self,
+        app: &ApplicationContext,
+        (cluster, owner): (Option<&String>, Option<&String>),
+        (start_s, end_s): (i64, i64),
+        step_s: i64,
+        watts_per_unit: f64,
+    ) {
+        let key = Some(EnergySeriesKey {
+            window: (start_s, end_s),
+            filtered_jobs_generation: app.filtered_jobs_generation,
+            cluster: cluster.cloned(),
+            owner: owner.cloned(),
+            step_s,
+            watts_per_unit: watts_per_unit.to_bits(),
+        });
+        if self.key == key {
+            return;
+        }
+
+        let kept: Vec<&Job> = app
+            .filtered_jobs
+            .iter()
+            .filter(|job| cluster.is_none_or(|cluster| job.clusters.contains(cluster)))
+            .filter(|job| owner.is_none_or(|owner| job.owner == *owner))
+            .collect();
+        (self.points, self.step_s) =
+            estimate_global_energy_series(&kept, start_s, end_s, step_s, watts_per_unit);
+        self.key = key;
+    }
+
+    pub fn points(&self) -> &[(i64, f64)] {
+        &self.points
+    }
+
+    /// Pas effectivement utilisé par la série (voir `effective_step_s`)
+    pub fn step_s(&self) -> i64 {
+        self.step_s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::data_structure::job::test_job;
+
+    const WATTS: f64 = 10.0;
+
+    fn job_with_resources(id: u32, scheduled_start: i64, walltime: i64, resources: u32) -> Job {
+        Job {
+            assigned_resources: (1..=resources).collect(),
+            ..test_job(id, scheduled_start, walltime)
+        }
+    }
+
+    fn watts(series: &[(i64, f64)]) -> Vec<f64> {
+        series.iter().map(|(_, w)| *w).collect()
+    }
+
+    #[test]
+    fn no_jobs_give_an_empty_series() {
+        let (series, _) = estimate_global_energy_series(&[], 0, 100, 10, WATTS);
+        assert!(series.is_empty());
+    }
+
+    #[test]
+    fn non_positive_step_gives_an_empty_series() {
+        let job = job_with_resources(1, 0, 100, 2);
+        assert!(estimate_global_energy_series(&[&job], 0, 100, 0, WATTS).0.is_empty());
+        assert!(estimate_global_energy_series(&[&job], 0, 100, -5, WATTS).0.is_empty());
+    }
+
+    #[test]
+    fn job_covering_the_window_gives_a_plateau() {
+        let job = job_with_resources(1, 0, 1000, 4);
+        let (series, step_s) = estimate_global_energy_series(&[&job], 100, 200, 10, WATTS);
+        assert_eq!(step_s, 10);
+        assert_eq!(series.len(), 11);
+        assert_eq!(series.first().map(|(t, _)| *t), Some(100));
+        assert_eq!(series.last().map(|(t, _)| *t), Some(200));
+        assert!(watts(&series).iter().all(|w| *w == 4.0 * WATTS));
+    }
+
+    #[test]
+    fn job_overlapping_the_edge_counts_until_its_end_included() {
+        // Job sur [50, 120] dans la fenêtre [100, 200] : présent de 100 à 120 inclus
+        let job = job_with_resources(1, 50, 70, 1);
+        let (series, _) = estimate_global_energy_series(&[&job], 100, 200, 10, WATTS);
+        let expected: Vec<f64> = series
+            .iter()
+            .map(|(t, _)| if *t <= 120 { WATTS } else { 0.0 })
+            .collect();
+        assert_eq!(watts(&series), expected);
+    }
+
+    #[test]
+    fn overlapping_jobs_sum_their_units() {
+        let first = job_with_resources(1, 0, 100, 2);
+        let second = job_with_resources(2, 50, 100, 3);
+        let (series, _) = estimate_global_energy_series(&[&first, &second], 0, 150, 10, WATTS);
+        let at = |t: i64| series.iter().find(|(time, _)| *time == t).map(|(_, w)| *w);
+        assert_eq!(at(20), Some(2.0 * WATTS));
+        assert_eq!(at(70), Some(5.0 * WATTS));
+        assert_eq!(at(120), Some(3.0 * WATTS));
+    }
+
+    #[test]
+    fn hosts_counted_without_assigned_resources() {
+        let by_hosts = Job {
+            hosts: vec!["dahu-1".to_string(), "dahu-2".to_string()],
+            ..test_job(1, 0, 100)
+        };
+        let by_resources = Job {
+            hosts: vec!["dahu-3".to_string()],
+            ..job_with_resources(2, 0, 100, 5)
+        };
+        assert_eq!(energy_units(&by_hosts), 2);
+        assert_eq!(energy_units(&by_resources), 5);
+        assert_eq!(energy_units(&test_job(3, 0, 100)), 0);
+
+        let (series, _) = estimate_global_energy_series(&[&by_hosts, &by_resources], 0, 50, 10, WATTS);
+        assert!(watts(&series).iter().all(|w| *w == 7.0 * WATTS));
+    }
+}
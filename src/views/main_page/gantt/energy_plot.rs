@@ -1,7 +1,13 @@
+use super::interaction::MAX_CANVAS_WIDTH_S;
+use super::types::Options;
+use crate::models::utils::date_converter::format_duration;
+use crate::views::components::gantt_zoom_anchor::ZoomAnchor;
 use chrono::{Local, TimeZone};
 use eframe::egui;
+use egui::PointerButton;
 use egui_plot::{
-    CoordinatesFormatter, Corner, Line, Plot, PlotBounds, PlotPoints, VLine,
+    CoordinatesFormatter, Corner, HLine, Legend, Line, LineStyle, Plot, PlotBounds, PlotMemory, PlotPoints,
+    VLine,
 };
 
 fn fmt_hhmm(ts: i64) -> String {
@@ -18,16 +24,57 @@ fn fmt_hhmmss(ts: i64) -> String {
         .unwrap_or_else(|| "?".to_string())
 }
 
+/// Puissance lisible : W, puis kW et MW au-delà de mille.
+fn fmt_watts(watts: f64) -> String {
+    let magnitude = watts.abs();
+    if magnitude >= 1e6 {
+        format!("{:.2} MW", watts / 1e6)
+    } else if magnitude >= 1e3 {
+        format!("{:.1} kW", watts / 1e3)
+    } else {
+        format!("{:.0} W", watts)
+    }
+}
+
+/// Dépassement du budget sur la série : durée (s) au-dessus du budget et énergie excédentaire (kWh).
+/// Chaque point vaut jusqu'au suivant.
+fn over_budget(points_w: &[(i64, f64)], budget_w: f64) -> (i64, f64) {
+    let mut duration_s = 0;
+    let mut excess_wh = 0.0;
+    for pair in points_w.windows(2) {
+        let ((t0, w0), (t1, _)) = (pair[0], pair[1]);
+        if w0 > budget_w {
+            duration_s += t1 - t0;
+            excess_wh += (w0 - budget_w) * (t1 - t0) as f64 / 3600.0;
+        }
+    }
+    (duration_s, excess_wh / 1000.0)
+}
+
+/// Navigation demandée depuis le graphe d'énergie, appliquée au Gantt par l'appelant.
+pub enum EnergyPlotNavigation {
+    /// Nouvelle fenêtre visible [début, fin], en secondes non arrondies
+    Window(f64, f64),
+    /// Double clic : retour à la plage chargée, comme sur le Gantt
+    Reset,
+}
+
 /// Affiche le graphe global de consommation d’énergie.
-/// Le graphe est synchronisé avec la fenêtre temporelle visible du Gantt.
+/// Le graphe est synchronisé avec la fenêtre temporelle visible du Gantt ; la souris y a les mêmes
+/// effets que sur le Gantt (glisser, Ctrl + molette, pincement, clic droit vertical, double clic).
+/// Avec `energy_lock_y_axis`, l'axe Y va de 0 au maximum global de la série quelle que soit la fenêtre.
+/// Avec `power_budget_w`, une ligne horizontale marque le budget et les dépassements sont ombrés.
+/// Avec `energy_legend`, une légende permet de masquer des séries d'un clic (`energy_hidden_series`).
 pub fn ui_energy_global(
     ui: &mut egui::Ui,
     points_w: &[(i64, f64)],
-    visible_start_s: i64,
-    visible_end_s: i64,
+    (visible_start_s, visible_end_s): (f64, f64),
     now_s: i64,
     left_gutter_width_px: f32,
-) -> Option<(i64, i64)> {
+    options: &mut Options,
+) -> Option<EnergyPlotNavigation> {
+    let (lock_y_axis, power_budget_w) = (options.energy_lock_y_axis, options.power_budget_w);
+
     ui.label("Consommation globale (estimée)");
 
     if points_w.is_empty() {
@@ -52,30 +99,46 @@ pub fn ui_energy_global(
         ui.weak("Données énergie invalides.");
         return None;
     }
+    // La ligne de budget reste visible même au-dessus de la courbe
+    if let Some(budget_w) = power_budget_w {
+        global_y_max = global_y_max.max(budget_w);
+    }
 
 
-    let line = Line::new(pts).color(egui::Color32::BLUE);
+    // Les éléments de même nom forment une seule entrée de légende (budget : ligne + ombre)
+    let power_name = t!("app.gantt.energy_series.power").to_string();
+    let budget_name = t!("app.gantt.energy_series.budget").to_string();
+    let line = Line::new(pts).color(egui::Color32::BLUE).name(&power_name);
     let now_line = VLine::new(now_s as f64)
         .color(egui::Color32::RED)
-        .width(2.0);
+        .width(2.0)
+        .name(t!("app.gantt.energy_series.now"));
 
     let initial_bounds = PlotBounds::from_min_max(
-        [visible_start_s as f64, global_y_min],
-        [visible_end_s as f64, global_y_max],
+        [visible_start_s, global_y_min],
+        [visible_end_s, global_y_max],
     );
 
     // Texte affiché au survol, dessiné manuellement pour éviter le tooltip de egui_plot (x=..., y=...).
     let mut hover_label: Option<String> = None;
 
-    let plot_resp = Plot::new("energy_global_plot")
+    let plot_id = egui::Id::new("energy_global_plot");
+    let mut plot = Plot::new("energy_global_plot")
+        .id(plot_id)
         .height(210.0)
         .y_axis_min_width(left_gutter_width_px.max(0.0))
         .show_axes([true, true])
         .show_x(true)
         .show_y(true)
         .show_grid(true)
-        .allow_drag(true)
-        .allow_zoom(true)
+        .x_axis_label(t!("app.gantt.energy_axis_time"))
+        .y_axis_label(t!("app.gantt.energy_axis_power"))
+        // La navigation native du graphe est remplacée par celle du Gantt (voir `navigation`)
+        .allow_drag(false)
+        .allow_zoom(false)
+        .allow_scroll(false)
+        .allow_boxed_zoom(false)
+        .allow_double_click_reset(false)
         .label_formatter(|_, _| String::new())
         .coordinates_formatter(
             Corner::LeftTop,
@@ -85,9 +148,25 @@ pub fn ui_energy_global(
             let ts = mark.value.round() as i64;
             fmt_hhmm(ts)
         })
-        .show(ui, |plot_ui| {
-            let vx0 = visible_start_s;
-            let vx1 = visible_end_s;
+        .y_axis_formatter(|mark, _| fmt_watts(mark.value));
+    if let Some(corner) = options.energy_legend {
+        // Les séries masquées viennent des options, pour survivre à un masquage de la légende
+        plot = plot.legend(
+            Legend::default()
+                .position(corner)
+                .hidden_items(options.energy_hidden_series.iter().cloned()),
+        );
+    } else if let Some(mut memory) = PlotMemory::load(ui.ctx(), plot_id) {
+        // Sans légende, plus rien ne permet de réafficher une série : tout est affiché
+        if !memory.hidden_items.is_empty() {
+            memory.hidden_items.clear();
+            memory.store(ui.ctx(), plot_id);
+        }
+    }
+
+    let plot_resp = plot.show(ui, |plot_ui| {
+            let vx0 = visible_start_s.floor() as i64;
+            let vx1 = visible_end_s.ceil() as i64;
 
             // Recalcule les bornes y sur la fenêtre visible pour garder une courbe lisible pendant les déplacements
             let mut y_min = f64::INFINITY;
@@ -99,11 +178,21 @@ pub fn ui_energy_global(
                 }
             }
 
-            let bounds = if y_min.is_finite() && y_max.is_finite() {
+            if let Some(budget_w) = power_budget_w {
+                y_max = y_max.max(budget_w);
+            }
+
+            let bounds = if lock_y_axis {
+                let pad = (global_y_max.abs() * 0.05).max(1.0);
+                PlotBounds::from_min_max(
+                    [visible_start_s, global_y_min.min(0.0)],
+                    [visible_end_s, global_y_max + pad],
+                )
+            } else if y_min.is_finite() && y_max.is_finite() {
                 let pad = ((y_max - y_min).abs() * 0.10).max(1.0);
                 PlotBounds::from_min_max(
-                    [visible_start_s as f64, y_min - pad],
-                    [visible_end_s as f64, y_max + pad],
+                    [visible_start_s, y_min - pad],
+                    [visible_end_s, y_max + pad],
                 )
             } else {
                 initial_bounds
@@ -112,12 +201,31 @@ pub fn ui_energy_global(
             // Le graphe suit la fenêtre temporelle du Gantt
             plot_ui.set_plot_bounds(bounds);
 
+            if let Some(budget_w) = power_budget_w {
+                // Ombre des dépassements : courbe écrêtée par le bas au budget, remplie jusqu'au budget
+                let over: PlotPoints = points_w.iter().map(|(t, w)| [*t as f64, w.max(budget_w)]).collect();
+                plot_ui.line(
+                    Line::new(over)
+                        .color(egui::Color32::from_rgb(230, 120, 0))
+                        .width(0.0)
+                        .fill(budget_w as f32)
+                        .fill_alpha(0.35)
+                        .name(&budget_name),
+                );
+                plot_ui.hline(
+                    HLine::new(budget_w)
+                        .color(egui::Color32::from_rgb(230, 120, 0))
+                        .width(1.5)
+                        .style(LineStyle::dashed_loose())
+                        .name(&budget_name),
+                );
+            }
             plot_ui.line(line);
             plot_ui.vline(now_line);
             // Tooltip personnalisé : heure exacte + puissance en watts
             if let Some(pos) = plot_ui.pointer_coordinate() {
                 let ts = pos.x.round() as i64;
-                hover_label = Some(format!("{}  |  {:.0} W", fmt_hhmmss(ts), pos.y));
+                hover_label = Some(format!("{}  |  {}", fmt_hhmmss(ts), fmt_watts(pos.y)));
             }
         });
 
@@ -139,18 +247,75 @@ pub fn ui_energy_global(
             painter.galley(rect.min + padding, galley, text_color);
         }
 
-        let b = plot_resp.transform.bounds();
-        let new_start = b.min()[0].round() as i64;
-        let new_end = b.max()[0].round() as i64;
-    
-
-    let scrolled = ui.input(|i| i.raw_scroll_delta.y != 0.0);
-    if plot_resp.response.dragged()
-        || plot_resp.response.double_clicked()
-        || (plot_resp.response.hovered() && scrolled)
-    {
-        return Some((new_start, new_end));
+        if let Some(budget_w) = power_budget_w {
+            let (duration_s, excess_kwh) = over_budget(points_w, budget_w);
+            ui.weak(t!(
+                "app.gantt.energy_over_budget",
+                budget = fmt_watts(budget_w),
+                duration = format_duration(duration_s),
+                energy = format!("{:.2}", excess_kwh)
+            ));
+        }
+
+    // Un clic dans la légende a mis à jour la mémoire du graphe : on le reporte dans les options
+    if options.energy_legend.is_some() {
+        if let Some(memory) = PlotMemory::load(ui.ctx(), plot_id) {
+            options.energy_hidden_series = memory.hidden_items.into_iter().collect();
+        }
+    }
+
+    navigation(ui, &plot_resp.response, &plot_resp.transform, (visible_start_s, visible_end_s), &options.zoom_anchor)
+}
+
+/// Traduit la souris sur le graphe en nouvelle fenêtre, avec la correspondance du Gantt
+/// (`interaction::interact_with_canvas`) : les secondes par pixel viennent de la largeur réelle du
+/// graphe, sans passer par celle du Gantt.
+fn navigation(
+    ui: &egui::Ui,
+    response: &egui::Response,
+    transform: &egui_plot::PlotTransform,
+    (start_s, end_s): (f64, f64),
+    zoom_anchor: &ZoomAnchor,
+) -> Option<EnergyPlotNavigation> {
+    if response.double_clicked() {
+        return Some(EnergyPlotNavigation::Reset);
+    }
+
+    let frame = transform.frame();
+    let s_per_px = (end_s - start_s) / frame.width().max(1.0) as f64;
+    let mut pan_px = 0.0;
+    let mut zoom_factor = 1.0;
+
+    // Glisser (clic gauche) : déplacement horizontal
+    if response.dragged_by(PointerButton::Primary) {
+        pan_px += response.drag_delta().x;
+    }
+    if response.hovered() {
+        let (mods, scroll, zoom_delta) = ui.input(|i| (i.modifiers, i.smooth_scroll_delta, i.zoom_delta()));
+        // Ctrl/Cmd + molette et pincement : zoom ; défilement horizontal (ou Maj + molette) : déplacement.
+        // La molette seule reste au défilement de la page.
+        zoom_factor = zoom_delta;
+        if !(mods.ctrl || mods.command || mods.alt) && scroll.x != 0.0 {
+            pan_px += scroll.x;
+            ui.ctx().input_mut(|i| i.smooth_scroll_delta.x = 0.0);
+        }
+        // Drag vertical avec clic droit : zoom temporel
+        if response.dragged_by(PointerButton::Secondary) {
+            zoom_factor *= (response.drag_delta().y * 0.01).exp();
+        }
+    }
+    if pan_px == 0.0 && zoom_factor == 1.0 {
+        return None;
     }
 
-    None
-}
\ No newline at end of file
+    let mut start_s = start_s - pan_px as f64 * s_per_px;
+    let mut end_s = end_s - pan_px as f64 * s_per_px;
+    let new_width_s = (end_s - start_s) / zoom_factor as f64;
+    if zoom_factor != 1.0 && new_width_s <= MAX_CANVAS_WIDTH_S as f64 {
+        let anchor_x = zoom_anchor.anchor_x(response.hover_pos().map(|pos| pos.x), frame.left(), frame.right());
+        let anchor_s = transform.value_from_position(egui::pos2(anchor_x, frame.center().y)).x;
+        start_s = anchor_s - (anchor_s - start_s) / zoom_factor as f64;
+        end_s = start_s + new_width_s;
+    }
+    Some(EnergyPlotNavigation::Window(start_s, end_s))
+}
@@ -0,0 +1,88 @@
+// Export du diagramme de Gantt sous forme de figure (PNG ou SVG).
+//
+// Le Gantt est peint hors écran, dans une couche egui cachée, avec les fonctions `paint_*`
+// habituelles (voir `GanttChart::paint_offscreen`). Les formes egui obtenues servent de
+// modèle de dessin commun : `export_png.rs` les rastérise, `export_svg.rs` les traduit en SVG.
+
+use chrono::{Local, NaiveDateTime, TimeZone};
+use std::path::PathBuf;
+
+/// Taille maximale (en pixels) de chaque côté de la figure exportée.
+pub(super) const MAX_EXPORT_SIDE_PX: u32 = 16_384;
+
+const DATETIME_FORMAT: &str = "%Y-%m-%d %H:%M";
+
+#[derive(Clone, Copy, PartialEq)]
+pub(super) enum ExportFormat {
+    Png,
+    Svg,
+}
+
+impl ExportFormat {
+    fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Png => "png",
+            ExportFormat::Svg => "svg",
+        }
+    }
+}
+
+/// Paramètres saisis dans le menu d'export et résultat du dernier export.
+pub(super) struct ExportSettings {
+    pub(super) width_px: u32,
+    pub(super) start: String,
+    pub(super) end: String,
+    /// Chemin du fichier ; l'extension est remplacée selon le format exporté
+    pub(super) path: String,
+    /// Demande d'export, traitée au prochain rendu du canvas
+    pub(super) requested: Option<ExportFormat>,
+    pub(super) status: Option<Result<String, String>>,
+}
+
+impl Default for ExportSettings {
+    fn default() -> Self {
+        Self {
+            width_px: 3000,
+            start: String::new(),
+            end: String::new(),
+            path: "gantt.png".to_string(),
+            requested: None,
+            status: None,
+        }
+    }
+}
+
+impl ExportSettings {
+    /// Pré-remplit les bornes avec une fenêtre de temps.
+    pub(super) fn set_range(&mut self, (start_s, end_s): (i64, i64)) {
+        self.start = format_datetime(start_s);
+        self.end = format_datetime(end_s);
+    }
+
+    /// Bornes saisies, si elles sont valides et ordonnées.
+    pub(super) fn range(&self) -> Option<(i64, i64)> {
+        let start_s = parse_datetime(&self.start)?;
+        let end_s = parse_datetime(&self.end)?;
+        (start_s < end_s).then_some((start_s, end_s))
+    }
+
+    pub(super) fn path_for(&self, format: ExportFormat) -> PathBuf {
+        PathBuf::from(self.path.trim()).with_extension(format.extension())
+    }
+}
+
+fn format_datetime(timestamp_s: i64) -> String {
+    Local
+        .timestamp_opt(timestamp_s, 0)
+        .single()
+        .map(|date| date.format(DATETIME_FORMAT).to_string())
+        .unwrap_or_default()
+}
+
+fn parse_datetime(text: &str) -> Option<i64> {
+    let naive = NaiveDateTime::parse_from_str(text.trim(), DATETIME_FORMAT).ok()?;
+    Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .map(|date| date.timestamp())
+}
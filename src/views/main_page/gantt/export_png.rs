@@ -0,0 +1,121 @@
+// Export PNG : les formes peintes hors écran (voir `export.rs`) sont tessellées puis
+// rastérisées ici sur CPU (aucun accès au GPU n'est nécessaire).
+// Coût mémoire : environ largeur × hauteur × 8 octets (tampon de couleurs + image RGBA),
+// soit ~130 Mo pour 8000 × 2000 px et jusqu'à ~2 Go au maximum de 16384 × 16384 px.
+
+use egui::epaint::{ClippedPrimitive, FontImage, Primitive, Vertex};
+use egui::{pos2, vec2, Color32, Pos2, Rect, TextureId};
+use std::path::Path;
+
+/// Rastérise les primitives tessellées (positions en pixels) dans une image RGBA opaque.
+/// Seule la texture des polices est échantillonnée ; les autres textures sont traitées comme blanches.
+pub(super) fn rasterize(
+    primitives: &[ClippedPrimitive],
+    font_image: &FontImage,
+    [width, height]: [usize; 2],
+    background: Color32,
+) -> Vec<u8> {
+    let font_texels: Vec<Color32> = font_image.srgba_pixels(None).collect();
+    let mut pixels = vec![background; width * height];
+    let bounds = Rect::from_min_size(Pos2::ZERO, vec2(width as f32, height as f32));
+
+    for ClippedPrimitive { clip_rect, primitive } in primitives {
+        let Primitive::Mesh(mesh) = primitive else {
+            continue;
+        };
+        let clip = clip_rect.intersect(bounds);
+        if !clip.is_positive() {
+            continue;
+        }
+        let texture = (mesh.texture_id == TextureId::default())
+            .then_some((font_texels.as_slice(), font_image.size));
+
+        for triangle in mesh.indices.chunks_exact(3) {
+            let vertices = [
+                &mesh.vertices[triangle[0] as usize],
+                &mesh.vertices[triangle[1] as usize],
+                &mesh.vertices[triangle[2] as usize],
+            ];
+            fill_triangle(&mut pixels, width, clip, vertices, texture);
+        }
+    }
+
+    pixels
+        .iter()
+        .flat_map(|color| {
+            let [r, g, b, _] = color.to_array();
+            [r, g, b, 255]
+        })
+        .collect()
+}
+
+fn edge(a: Pos2, b: Pos2, p: Pos2) -> f32 {
+    (b.x - a.x) * (p.y - a.y) - (b.y - a.y) * (p.x - a.x)
+}
+
+/// Remplit un triangle (couleurs prémultipliées interpolées, mélange « over »),
+/// en échantillonnant la texture au plus proche voisin.
+fn fill_triangle(
+    pixels: &mut [Color32],
+    width: usize,
+    clip: Rect,
+    [a, b, c]: [&Vertex; 3],
+    texture: Option<(&[Color32], [usize; 2])>,
+) {
+    let area = edge(a.pos, b.pos, c.pos);
+    if area.abs() < f32::EPSILON || !area.is_finite() {
+        return;
+    }
+
+    let min_x = a.pos.x.min(b.pos.x).min(c.pos.x).max(clip.min.x).floor() as usize;
+    let max_x = a.pos.x.max(b.pos.x).max(c.pos.x).min(clip.max.x).ceil() as usize;
+    let min_y = a.pos.y.min(b.pos.y).min(c.pos.y).max(clip.min.y).floor() as usize;
+    let max_y = a.pos.y.max(b.pos.y).max(c.pos.y).min(clip.max.y).ceil() as usize;
+
+    for y in min_y..max_y {
+        for x in min_x..max_x {
+            let p = pos2(x as f32 + 0.5, y as f32 + 0.5);
+            let wa = edge(b.pos, c.pos, p) / area;
+            let wb = edge(c.pos, a.pos, p) / area;
+            let wc = 1.0 - wa - wb;
+            if wa < 0.0 || wb < 0.0 || wc < 0.0 {
+                continue;
+            }
+
+            let mut src = [0.0f32; 4];
+            for (channel, value) in src.iter_mut().enumerate() {
+                *value = wa * a.color[channel] as f32
+                    + wb * b.color[channel] as f32
+                    + wc * c.color[channel] as f32;
+            }
+            if let Some((texels, [texture_w, texture_h])) = texture {
+                let uv = a.uv.to_vec2() * wa + b.uv.to_vec2() * wb + c.uv.to_vec2() * wc;
+                let tx = ((uv.x * texture_w as f32) as usize).min(texture_w - 1);
+                let ty = ((uv.y * texture_h as f32) as usize).min(texture_h - 1);
+                let texel = texels[ty * texture_w + tx];
+                for (channel, value) in src.iter_mut().enumerate() {
+                    *value *= texel[channel] as f32 / 255.0;
+                }
+            }
+
+            let dst = &mut pixels[y * width + x];
+            let keep = 1.0 - src[3] / 255.0;
+            *dst = Color32::from_rgba_premultiplied(
+                (src[0] + dst.r() as f32 * keep).round() as u8,
+                (src[1] + dst.g() as f32 * keep).round() as u8,
+                (src[2] + dst.b() as f32 * keep).round() as u8,
+                (src[3] + dst.a() as f32 * keep).round() as u8,
+            );
+        }
+    }
+}
+
+/// Écrit une image RGBA 8 bits dans un fichier PNG.
+pub(super) fn write_png(path: &Path, width: u32, height: u32, rgba: &[u8]) -> Result<(), String> {
+    let file = std::fs::File::create(path).map_err(|e| e.to_string())?;
+    let mut encoder = png::Encoder::new(std::io::BufWriter::new(file), width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(|e| e.to_string())?;
+    writer.write_image_data(rgba).map_err(|e| e.to_string())
+}
@@ -0,0 +1,270 @@
+// Export SVG : traduit les formes peintes hors écran (voir `export.rs`) en éléments
+// vectoriels `<rect>`, `<line>`, `<path>`, `<text>`… Les textures (images, maillages
+// texturés) et les callbacks de rendu ne sont pas exportés.
+
+use egui::epaint::{ClippedShape, ColorMode, PathStroke, TextShape};
+use egui::{vec2, Color32, FontFamily, Pos2, Rect, Shape, Stroke};
+use std::fmt::Write;
+
+/// Construit le document SVG de taille `width` × `height` à partir des formes egui.
+pub(super) fn shapes_to_svg(shapes: &[ClippedShape], [width, height]: [u32; 2], background: Color32) -> String {
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+    );
+    let _ = writeln!(svg, r#"<rect width="100%" height="100%"{}/>"#, fill_attr(background));
+
+    // Un clipPath par rectangle de découpe distinct, les formes consécutives partageant le même groupe
+    let bounds = Rect::from_min_size(Pos2::ZERO, vec2(width as f32, height as f32));
+    let mut clip_rects: Vec<Rect> = Vec::new();
+    let mut current_clip: Option<usize> = None;
+    for ClippedShape { clip_rect, shape } in shapes {
+        let clip_rect = clip_rect.intersect(bounds);
+        if !clip_rect.is_positive() {
+            continue;
+        }
+        let clip_index = match clip_rects.iter().position(|rect| *rect == clip_rect) {
+            Some(index) => index,
+            None => {
+                clip_rects.push(clip_rect);
+                let _ = writeln!(
+                    svg,
+                    r#"<clipPath id="clip{}"><rect x="{}" y="{}" width="{}" height="{}"/></clipPath>"#,
+                    clip_rects.len() - 1,
+                    num(clip_rect.min.x),
+                    num(clip_rect.min.y),
+                    num(clip_rect.width()),
+                    num(clip_rect.height()),
+                );
+                clip_rects.len() - 1
+            }
+        };
+        if current_clip != Some(clip_index) {
+            if current_clip.is_some() {
+                svg.push_str("</g>\n");
+            }
+            let _ = writeln!(svg, r#"<g clip-path="url(#clip{clip_index})">"#);
+            current_clip = Some(clip_index);
+        }
+        write_shape(&mut svg, shape);
+    }
+    if current_clip.is_some() {
+        svg.push_str("</g>\n");
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn write_shape(svg: &mut String, shape: &Shape) {
+    match shape {
+        Shape::Noop | Shape::Mesh(_) | Shape::Callback(_) => {}
+        Shape::Vec(shapes) => shapes.iter().for_each(|shape| write_shape(svg, shape)),
+        Shape::Rect(rect) => {
+            if !rect.rect.is_finite() {
+                return;
+            }
+            let _ = writeln!(
+                svg,
+                r#"<rect x="{}" y="{}" width="{}" height="{}" rx="{}"{}{}/>"#,
+                num(rect.rect.min.x),
+                num(rect.rect.min.y),
+                num(rect.rect.width()),
+                num(rect.rect.height()),
+                num(rect.rounding.nw.max(rect.rounding.ne).max(rect.rounding.sw).max(rect.rounding.se)),
+                fill_attr(rect.fill),
+                stroke_attr(&rect.stroke),
+            );
+        }
+        Shape::LineSegment { points, stroke } => {
+            let _ = writeln!(
+                svg,
+                r#"<line x1="{}" y1="{}" x2="{}" y2="{}"{}/>"#,
+                num(points[0].x),
+                num(points[0].y),
+                num(points[1].x),
+                num(points[1].y),
+                path_stroke_attr(stroke),
+            );
+        }
+        Shape::Path(path) => {
+            if path.points.is_empty() {
+                return;
+            }
+            let mut data = points_data(&path.points);
+            if path.closed {
+                data.push_str(" Z");
+            }
+            let _ = writeln!(
+                svg,
+                r#"<path d="{}"{}{}/>"#,
+                data,
+                fill_attr(if path.closed { path.fill } else { Color32::TRANSPARENT }),
+                path_stroke_attr(&path.stroke),
+            );
+        }
+        Shape::Circle(circle) => {
+            let _ = writeln!(
+                svg,
+                r#"<circle cx="{}" cy="{}" r="{}"{}{}/>"#,
+                num(circle.center.x),
+                num(circle.center.y),
+                num(circle.radius),
+                fill_attr(circle.fill),
+                stroke_attr(&circle.stroke),
+            );
+        }
+        Shape::Ellipse(ellipse) => {
+            let _ = writeln!(
+                svg,
+                r#"<ellipse cx="{}" cy="{}" rx="{}" ry="{}"{}{}/>"#,
+                num(ellipse.center.x),
+                num(ellipse.center.y),
+                num(ellipse.radius.x),
+                num(ellipse.radius.y),
+                fill_attr(ellipse.fill),
+                stroke_attr(&ellipse.stroke),
+            );
+        }
+        Shape::QuadraticBezier(bezier) => {
+            let [from, control, to] = bezier.points;
+            let _ = writeln!(
+                svg,
+                r#"<path d="M {} {} Q {} {} {} {}{}"{}{}/>"#,
+                num(from.x),
+                num(from.y),
+                num(control.x),
+                num(control.y),
+                num(to.x),
+                num(to.y),
+                if bezier.closed { " Z" } else { "" },
+                fill_attr(bezier.fill),
+                path_stroke_attr(&bezier.stroke),
+            );
+        }
+        Shape::CubicBezier(bezier) => {
+            let [from, control_1, control_2, to] = bezier.points;
+            let _ = writeln!(
+                svg,
+                r#"<path d="M {} {} C {} {} {} {} {} {}{}"{}{}/>"#,
+                num(from.x),
+                num(from.y),
+                num(control_1.x),
+                num(control_1.y),
+                num(control_2.x),
+                num(control_2.y),
+                num(to.x),
+                num(to.y),
+                if bezier.closed { " Z" } else { "" },
+                fill_attr(bezier.fill),
+                path_stroke_attr(&bezier.stroke),
+            );
+        }
+        Shape::Text(text) => write_text(svg, text),
+    }
+}
+
+/// Un élément `<text>` par suite de glyphes de même format sur une ligne, positionné sur la ligne de base.
+fn write_text(svg: &mut String, text: &TextShape) {
+    let transform = if text.angle != 0.0 {
+        format!(
+            r#" transform="rotate({} {} {})""#,
+            num(text.angle.to_degrees()),
+            num(text.pos.x),
+            num(text.pos.y)
+        )
+    } else {
+        String::new()
+    };
+
+    for row in text.galley.rows.iter() {
+        let mut glyphs = row.glyphs.iter().peekable();
+        while let Some(first) = glyphs.next() {
+            let mut content = String::from(first.chr);
+            while let Some(glyph) = glyphs.next_if(|glyph| glyph.section_index == first.section_index) {
+                content.push(glyph.chr);
+            }
+            if content.trim().is_empty() {
+                continue;
+            }
+
+            let format = &text.galley.job.sections[first.section_index as usize].format;
+            let color = text.override_text_color.unwrap_or(if format.color == Color32::PLACEHOLDER {
+                text.fallback_color
+            } else {
+                format.color
+            });
+            let family = match format.font_id.family {
+                FontFamily::Monospace => "monospace",
+                _ => "sans-serif",
+            };
+            let _ = writeln!(
+                svg,
+                r#"<text x="{}" y="{}" font-family="{}" font-size="{}"{}{} xml:space="preserve">{}</text>"#,
+                num(text.pos.x + first.pos.x),
+                num(text.pos.y + first.pos.y),
+                family,
+                num(format.font_id.size),
+                fill_attr(color.gamma_multiply(text.opacity_factor)),
+                transform,
+                escape(&content),
+            );
+        }
+    }
+}
+
+fn num(value: f32) -> String {
+    let rounded = (value * 100.0).round() / 100.0;
+    if rounded == rounded.trunc() {
+        format!("{}", rounded as i64)
+    } else {
+        format!("{rounded}")
+    }
+}
+
+fn points_data(points: &[Pos2]) -> String {
+    points
+        .iter()
+        .enumerate()
+        .map(|(index, point)| format!("{} {} {}", if index == 0 { "M" } else { "L" }, num(point.x), num(point.y)))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Couleur egui (prémultipliée) vers `rgb(…)` et opacité SVG.
+fn color_attrs(name: &str, color: Color32) -> String {
+    let [r, g, b, a] = color.to_srgba_unmultiplied();
+    if a == 255 {
+        format!(r#" {name}="rgb({r},{g},{b})""#)
+    } else {
+        format!(r#" {name}="rgb({r},{g},{b})" {name}-opacity="{}""#, num(a as f32 / 255.0))
+    }
+}
+
+fn fill_attr(color: Color32) -> String {
+    if color.a() == 0 {
+        r#" fill="none""#.to_string()
+    } else {
+        color_attrs("fill", color)
+    }
+}
+
+fn stroke_attr(stroke: &Stroke) -> String {
+    if stroke.is_empty() {
+        return String::new();
+    }
+    format!(r#"{} stroke-width="{}""#, color_attrs("stroke", stroke.color), num(stroke.width))
+}
+
+fn path_stroke_attr(stroke: &PathStroke) -> String {
+    match stroke.color {
+        ColorMode::Solid(color) => stroke_attr(&Stroke::new(stroke.width, color)),
+        // Dégradé calculé par callback : pas d'équivalent simple en SVG
+        ColorMode::UV(_) => String::new(),
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
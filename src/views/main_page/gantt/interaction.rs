@@ -2,62 +2,93 @@ use super::types::{Info, Options};
 use egui::{lerp, PointerButton, Response};
 
 pub(super) fn interact_with_canvas(options: &mut Options, response: &Response, info: &Info) {
+    // Gestes tactiles à deux doigts : pincement pour le zoom temporel, glissement pour le déplacement
+    let multi_touch = info
+        .ctx
+        .input(|i| i.multi_touch())
+        .filter(|touch| response.rect.contains(touch.center_pos));
+    if let Some(touch) = &multi_touch {
+        if touch.translation_delta.x != 0.0 {
+            options.sideways_pan_in_points += touch.translation_delta.x;
+            options.zoom_to_relative_s_range = None;
+        }
+        if touch.zoom_delta != 1.0 {
+            // Ancre « pointeur » : le point milieu du geste
+            zoom_around(options, info, touch.zoom_delta, Some(touch.center_pos.x));
+        }
+        info.ctx.request_repaint();
+    }
+    // Le premier doigt est aussi vu comme un pointeur : pendant un geste, on ignore le drag et le zoom souris
+    let mouse_enabled = multi_touch.is_none();
+
+    // Maj + glisser (clic gauche) : tracé d'une sous-fenêtre de temps, à la place du déplacement
+    let selecting = info.ctx.input(|i| i.modifiers.shift);
+    if mouse_enabled && selecting && response.dragged_by(PointerButton::Primary) {
+        let origin = info.ctx.input(|i| i.pointer.press_origin());
+        if let (Some(origin), Some(pos)) = (origin, response.interact_pointer_pos()) {
+            let current_s = info.s_from_point(options, pos.x);
+            let origin_s = match options.time_selection {
+                Some((origin_s, _)) if !response.drag_started() => origin_s,
+                _ => info.s_from_point(options, origin.x),
+            };
+            options.time_selection = Some((origin_s, current_s));
+        }
+    }
+
     // Déplacement horizontal du Gantt avec le clic gauche
-    if response.dragged_by(PointerButton::Primary) && response.drag_delta().x != 0.0 {
+    if mouse_enabled
+        && !selecting
+        && response.dragged_by(PointerButton::Primary)
+        && response.drag_delta().x != 0.0
+    {
         options.sideways_pan_in_points += response.drag_delta().x;
         options.zoom_to_relative_s_range = None;
     }
 
-    if response.hovered() {
-        let (mods, scroll_y) = info.ctx.input(|i| (i.modifiers, i.smooth_scroll_delta.y));
-        // Alt + molette verticale : zoom vertical sur la hauteur des lignes
-        if mods.alt && !(mods.ctrl || mods.command) && scroll_y != 0.0 {
-            const MIN_ROW_HEIGHT: f32 = 8.0;
-            const MAX_ROW_HEIGHT: f32 = 80.0;
-
-            let zoom_factor_y = (-scroll_y * 0.0025).exp();
-            options.rect_height = (options.rect_height * zoom_factor_y).clamp(MIN_ROW_HEIGHT, MAX_ROW_HEIGHT);
+    if mouse_enabled && response.hovered() {
+        // Correspondance molette / modificateurs, une seule action par combinaison :
+        // - molette seule : défilement vertical des lignes (laissé à la ScrollArea)
+        // - Ctrl/Cmd + molette : zoom temporel
+        // - Maj + molette : déplacement horizontal (egui convertit déjà la molette en défilement horizontal)
+        // - Alt + molette : hauteur des lignes
+        // Le défilement horizontal d'un pavé tactile déplace aussi la vue, le pincement zoome.
+        let (mods, scroll, zoom_delta) =
+            info.ctx.input(|i| (i.modifiers, i.smooth_scroll_delta, i.zoom_delta()));
+        let mut zoom_factor = 1.0;
 
-            // On consomme le scroll pour éviter qu’il soit réutilisé ailleurs
-            info.ctx.input_mut(|i| i.smooth_scroll_delta.y = 0.0);
-            info.ctx.request_repaint();
-        }
-
-        if info.ctx.input(|i| i.smooth_scroll_delta.x != 0.0) {
-            options.sideways_pan_in_points += info.ctx.input(|i| i.smooth_scroll_delta.x);
-            options.zoom_to_relative_s_range = None;
-        }
+        if mods.ctrl || mods.command {
+            // egui transforme Ctrl/Cmd + molette en facteur de zoom, sans défilement
+            zoom_factor = zoom_delta;
+        } else if mods.alt {
+            if scroll.y != 0.0 {
+                const MIN_ROW_HEIGHT: f32 = 8.0;
+                const MAX_ROW_HEIGHT: f32 = 80.0;
 
-        let mut zoom_factor = info.ctx.input(|i| i.zoom_delta_2d().x);
+                let zoom_factor_y = (-scroll.y * 0.0025).exp();
+                options.rect_height = (options.rect_height * zoom_factor_y).clamp(MIN_ROW_HEIGHT, MAX_ROW_HEIGHT);
 
-        // Ctrl/Cmd + molette verticale : zoom temporel
-        if zoom_factor == 1.0 {
-            let (mods, scroll_y) = info.ctx.input(|i| (i.modifiers, i.smooth_scroll_delta.y));
-            if (mods.ctrl || mods.command) && scroll_y != 0.0 {
-                zoom_factor *= (-scroll_y * 0.0025).exp();
+                // On consomme le scroll pour éviter qu’il soit réutilisé ailleurs
+                info.ctx.input_mut(|i| i.smooth_scroll_delta.y = 0.0);
+                info.ctx.request_repaint();
+            }
+        } else {
+            if scroll.x != 0.0 {
+                options.sideways_pan_in_points += scroll.x;
+                options.zoom_to_relative_s_range = None;
+                info.ctx.input_mut(|i| i.smooth_scroll_delta.x = 0.0);
             }
+            // Pincement sur pavé tactile (événement de zoom sans modificateur)
+            zoom_factor = zoom_delta;
         }
+
         // Drag vertical avec clic droit : zoom temporel
         if response.dragged_by(PointerButton::Secondary) {
             zoom_factor *= (response.drag_delta().y * 0.01).exp();
         }
 
         if zoom_factor != 1.0 {
-            let new_width = options.canvas_width_s / zoom_factor;
-
-            let max_canvas_width = 2 * 24 * 60 * 60; 
-            if new_width <= max_canvas_width as f32 {
-                options.canvas_width_s = new_width;
-
-                // On zoome autour de la position de la souris
-                if let Some(mouse_pos) = response.hover_pos() {
-                    let origin_x = info.canvas.min.x + info.gutter_width;
-                    let zoom_center = mouse_pos.x - origin_x;
-                    options.sideways_pan_in_points =
-                        (options.sideways_pan_in_points - zoom_center) * zoom_factor + zoom_center;
-                }
-            }
-            options.zoom_to_relative_s_range = None;
+            // Ancre « pointeur » : la position de la souris
+            zoom_around(options, info, zoom_factor, response.hover_pos().map(|pos| pos.x));
         }
     }
     // Double clic : retour à la fenêtre temporelle complète
@@ -75,7 +106,7 @@ pub(super) fn interact_with_canvas(options: &mut Options, response: &Response, i
         let canvas_width = info.usable_width();
 
         let target_canvas_width_s = (end_s - start_s) as f32;
-        let target_pan_in_points = -canvas_width * start_s as f32 / target_canvas_width_s;
+        let target_pan_in_points = pan_for_window_start(start_s, target_canvas_width_s, canvas_width);
 
         options.canvas_width_s = lerp(
             options.canvas_width_s.recip()..=target_canvas_width_s.recip(),
@@ -91,3 +122,29 @@ pub(super) fn interact_with_canvas(options: &mut Options, response: &Response, i
         info.ctx.request_repaint();
     }
 }
+
+/// Largeur maximale de la fenêtre visible atteignable au zoom (secondes)
+pub(super) const MAX_CANVAS_WIDTH_S: f32 = (2 * 24 * 60 * 60) as f32;
+
+/// Applique un facteur de zoom temporel en gardant fixe l'abscisse choisie par `options.zoom_anchor`
+/// (pointeur `pointer_x` en points écran, centre ou bord gauche du graphe).
+fn zoom_around(options: &mut Options, info: &Info, zoom_factor: f32, pointer_x: Option<f32>) {
+    let new_width = options.canvas_width_s / zoom_factor;
+
+    if new_width <= MAX_CANVAS_WIDTH_S {
+        options.canvas_width_s = new_width;
+
+        let origin_x = info.canvas.min.x + info.gutter_width;
+        let anchor_x = options.zoom_anchor.anchor_x(pointer_x, origin_x, info.canvas.max.x);
+        let zoom_center = anchor_x - origin_x;
+        options.sideways_pan_in_points =
+            (options.sideways_pan_in_points - zoom_center) * zoom_factor + zoom_center;
+    }
+    options.zoom_to_relative_s_range = None;
+}
+
+/// Décalage horizontal (points) qui place l'instant `offset_s` (secondes après le début de la
+/// plage chargée) au bord gauche de la zone utile, pour une fenêtre de `canvas_width_s` secondes.
+pub(super) fn pan_for_window_start(offset_s: f64, canvas_width_s: f32, usable_width_px: f32) -> f32 {
+    -usable_width_px * offset_s as f32 / canvas_width_s
+}
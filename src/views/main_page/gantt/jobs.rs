@@ -1,26 +1,35 @@
-use super::labels::{
-    build_label_meta_level1, build_label_meta_level2, short_host_label, site_for_cluster_name,
-    LabelMeta,
-};
+use super::aggregation::{resource_id_from_row_label, Level1Groups, Level2Groups};
+use super::labels::{build_label_meta_level1, build_label_meta_level2, LabelMeta};
 use super::theme::get_theme_colors;
 use super::types::{
     gutter_g5k_total_w, Info, Options, GUTTER_G5K_CLUSTER_W, GUTTER_G5K_HOST_W, GUTTER_G5K_SITE_W,
 };
 use crate::models::data_structure::cluster::Cluster;
-use crate::models::data_structure::job::Job;
-use crate::models::data_structure::resource::ResourceState;
+use crate::models::data_structure::filters::command_contains;
+use crate::models::data_structure::job::{Job, JobState};
+use crate::models::data_structure::resource::{ResourceState, ResourceStateCounts};
+use crate::models::data_structure::strata::Strata;
 use crate::models::data_structure::application_context::ApplicationContext;
-use crate::models::utils::date_converter::format_timestamp;
+use crate::models::utils::cpuset::{
+    format_cpuset_ranges, format_resource_ids, parse_cpuset, truncate_ranges,
+};
+use crate::models::utils::date_converter::{format_duration, format_timestamp};
 use crate::models::utils::utils::{
-    compare_string_with_number, get_cluster_state_from_name, get_host_state_from_name,
-    get_tree_structure_for_job,
+    cluster_resource_summary, compare_string_with_number, get_cluster_state_from_name,
+    get_host_state_from_name, get_resource_state_from_id, get_tree_structure_for_job, short_host_label, site_for_cluster_name,
+    site_for_host,
 };
 use crate::views::components::gantt_aggregate_by::{AggregateByLevel1Enum, AggregateByLevel2Enum};
-use crate::views::components::job_details::JobDetailsWindow;
+use crate::views::components::gantt_gutter_click::{GutterClickActionEnum, GutterFilter};
+use crate::views::components::gantt_host_sort::HostSort;
+use crate::views::components::gantt_strata_fields::{StrataFieldEnum, StrataFields};
+use crate::views::components::gantt_time_basis::TimeBasis;
+use crate::views::components::gantt_tooltip_mode::TooltipModeEnum;
+use crate::views::components::job_details::{open_or_focus_job_details, JobDetailsWindow};
 use egui::{
-    pos2, Align2, Color32, CursorIcon, FontId, Id, LayerId, Order, Pos2, Rect, Shape, Stroke,
+    pos2, Align2, Color32, CursorIcon, FontId, Id, LayerId, Order, Pos2, Rect, Rounding, Shape, Stroke,
 };
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 fn json_value_to_inline(v: &serde_json::Value) -> Option<String> {
     match v {
@@ -48,130 +57,575 @@ fn json_value_to_inline(v: &serde_json::Value) -> Option<String> {
     }
 }
 
-fn format_cpuset_grid5000(values: &mut Vec<i32>) -> Option<String> {
-    use range_set_blaze::RangeSetBlaze;
-    
-    if values.is_empty() {
+fn cpuset_like_grid5000(s: &crate::models::data_structure::strata::Strata) -> Option<String> {
+    if let Some(v) = s.cpuset.as_ref() {
+        match v {
+            serde_json::Value::Number(n) => {
+                if let Some(i) = n.as_i64() {
+                    if (0..=i32::MAX as i64).contains(&i) {
+                        return Some(format!("[{}]", i));
+                    }
+                }
+            }
+            serde_json::Value::Array(_) | serde_json::Value::String(_) => {
+                let mut ints = parse_cpuset(v);
+                // Une chaîne ne contenant qu'un entier (ex. "cpuset3") n'est pas une liste de cœurs
+                let is_list = !matches!(v, serde_json::Value::String(_)) || ints.len() > 1;
+                if is_list {
+                    let ranges = format_cpuset_ranges(&mut ints);
+                    if !ranges.is_empty() {
+                        return Some(ranges);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let count = s.core_count.or(s.thread_count).unwrap_or(0);
+    if count <= 0 {
         return None;
     }
-    
-    values.sort_unstable();
-    values.dedup();
-    
-    let mut range_set = RangeSetBlaze::<i32>::new();
-    for &val in values.iter() {
-        range_set.insert(val);
-    }
-    
-    let mut result_parts: Vec<String> = Vec::new();
-    
-    for range in range_set.ranges() {
-        if range.start() == range.end() {
-            result_parts.push(range.start().to_string());
-        } else {
-            result_parts.push(format!("{}-{}", range.start(), range.end()));
+    Some(format!("0-{}", count - 1))
+}
+
+/// Valeur d'un champ Strata pour les tooltips, `None` si absente ou vide.
+fn strata_field_value(s: &Strata, field: StrataFieldEnum) -> Option<String> {
+    let value = match field {
+        StrataFieldEnum::Besteffort => s.besteffort.clone(),
+        StrataFieldEnum::NetworkAddress => s.network_address.clone(),
+        StrataFieldEnum::Comment => s.comment.clone(),
+        StrataFieldEnum::Cpuset => cpuset_like_grid5000(s),
+        StrataFieldEnum::Deploy => s.deploy.clone(),
+        StrataFieldEnum::Drain => s.drain.clone(),
+        StrataFieldEnum::Gpudevice => s.gpudevice.as_ref().and_then(json_value_to_inline),
+        StrataFieldEnum::Type => s.r#type.clone(),
+        StrataFieldEnum::Cputype => s.cputype.clone(),
+        StrataFieldEnum::Nodemodel => s.nodemodel.clone(),
+    };
+    value.map(|v| v.trim().to_string()).filter(|v| !v.is_empty())
+}
+
+/// Lignes du tooltip d'un hôte : nom, cluster, site, propriétés Strata choisies dans les
+/// réglages puis `resource_id`. Partagé par le survol du canvas et des libellés d'hôte.
+fn host_tooltip_lines(host_full: &str, app: &ApplicationContext, fields: &StrataFields) -> Vec<String> {
+    let mut lines = vec![format!("host: {}", host_full)];
+
+    let key_full = host_full.trim();
+    let key_short = short_host_label(key_full);
+    let strata = app
+        .strata_by_host
+        .get(key_full)
+        .or_else(|| app.strata_by_host.get(&key_short));
+
+    // Sans Strata, le cluster est déduit du nom court (ex. « dahu-12 » → « dahu »)
+    let derived_cluster = key_short.split('-').next().unwrap_or("").trim();
+    let cluster = strata
+        .and_then(|s| s.cluster.as_deref())
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .or_else(|| if !derived_cluster.is_empty() { Some(derived_cluster) } else { None });
+    if let Some(cluster) = cluster {
+        lines.push(format!("cluster: {}", cluster));
+    }
+
+    let site = cluster
+        .and_then(|cluster| app.cluster_site_map.get(cluster))
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .or_else(|| site_for_host(host_full, &app.strata_by_host));
+    if let Some(site) = site {
+        lines.push(format!("site: {}", site));
+    }
+
+    let host = app
+        .all_clusters
+        .iter()
+        .flat_map(|cluster| cluster.hosts.iter())
+        .find(|host| host.name == key_full || host.name == key_short);
+    if let Some(host) = host {
+        lines.push(resource_states_line(&host.resource_state_counts()));
+    }
+
+    if let Some(s) = strata {
+        lines.extend(
+            StrataFieldEnum::ALL
+                .into_iter()
+                .filter(|field| fields.is_shown(*field))
+                .filter_map(|field| strata_field_value(s, field).map(|v| format!("{}: {}", field.key(), v))),
+        );
+        if let Some(rid) = s.resource_id {
+            lines.push(format!("resource_id: {}", rid));
         }
     }
-    
-    if result_parts.is_empty() {
-        return None;
+    lines
+}
+
+/// Décompte des ressources par état, ex. « ressources : 30 Alive / 2 Dead / 0 Absent ».
+fn resource_states_line(counts: &ResourceStateCounts) -> String {
+    t!(
+        "app.gantt.resource_states",
+        alive = counts.alive,
+        dead = counts.dead,
+        absent = counts.absent
+    )
+    .to_string()
+}
+
+/// Tooltip d'une bande de cluster de la gouttière Grid5000 : nom, taille et ressources par état.
+fn cluster_tooltip_lines(cluster_name: &str, app: &ApplicationContext) -> Vec<String> {
+    let mut lines = vec![format!("cluster: {}", cluster_name)];
+    if let Some(cluster) = app.all_clusters.iter().find(|c| c.name == cluster_name) {
+        let (hosts, cores, threads, gpus) = cluster_resource_summary(cluster, &app.gpu_count_by_host);
+        lines.push(
+            t!(
+                "app.gantt.cluster_summary",
+                hosts = hosts,
+                cores = cores,
+                threads = threads,
+                gpus = gpus
+            )
+            .to_string(),
+        );
+        lines.push(resource_states_line(&cluster.resource_state_counts()));
+    }
+    lines
+}
+
+/// Nombre maximal de jobs listés dans le tooltip d'un hôte de la gouttière.
+const HOST_JOBS_TOOLTIP_MAX: usize = 12;
+
+/// Jobs d'un hôte présents dans la fenêtre visible (id, propriétaire, état), par ordre de début :
+/// sur une ligne dense où les barres se confondent, c'est le seul moyen de les énumérer.
+/// Liste limitée à `HOST_JOBS_TOOLTIP_MAX` lignes, suivies de « +N autres ».
+fn host_jobs_lines(jobs: &[&Job], time_basis: &TimeBasis, (start_s, end_s): (i64, i64)) -> Vec<String> {
+    let mut visible: Vec<&Job> = jobs
+        .iter()
+        .copied()
+        .filter(|job| job.id != 0)
+        .filter(|job| {
+            let (job_start_s, job_end_s) = time_basis.job_span(job);
+            job_start_s <= end_s && job_end_s >= start_s
+        })
+        .collect();
+    if visible.is_empty() {
+        return Vec::new();
     }
-    
-    Some(result_parts.join(", "))
+    visible.sort_by_key(|job| (time_basis.job_span(job).0, job.id));
+    visible.dedup_by_key(|job| job.id);
+
+    let mut lines = vec![t!("app.gantt.host_jobs", count = visible.len()).to_string()];
+    lines.extend(
+        visible
+            .iter()
+            .take(HOST_JOBS_TOOLTIP_MAX)
+            .map(|job| format!("  #{} {} ({})", job.id, job.owner, job.state.get_label())),
+    );
+    if visible.len() > HOST_JOBS_TOOLTIP_MAX {
+        lines.push(t!("app.gantt.host_jobs_more", count = visible.len() - HOST_JOBS_TOOLTIP_MAX).to_string());
+    }
+    lines
+}
+
+/// Affiche le tooltip d'un hôte (voir `host_tooltip_lines`), suivi de la liste de ses jobs
+/// dans la fenêtre visible (voir `host_jobs_lines`).
+fn host_tooltip(
+    ui: &mut egui::Ui,
+    host_full: &str,
+    app: &ApplicationContext,
+    options: &Options,
+    window: (i64, i64),
+    host_jobs: &[&Job],
+) {
+    let mut lines = host_tooltip_lines(host_full, app, &options.strata_fields);
+    lines.extend(host_jobs_lines(host_jobs, &options.time_basis, window));
+    tooltip_body(ui, &lines.join("\n"));
 }
 
-fn extract_ints_from_str(s: &str) -> Vec<i32> {
-    let mut out: Vec<i32> = Vec::new();
-    let mut cur: i64 = 0;
-    let mut in_num = false;
-    for ch in s.chars() {
-        if ch.is_ascii_digit() {
-            in_num = true;
-            cur = cur * 10 + (ch as i64 - '0' as i64);
-        } else if in_num {
-            if cur <= i32::MAX as i64 {
-                out.push(cur as i32);
+/// Contenu d'un tooltip : le texte, puis l'indication du raccourci de copie.
+/// Ctrl/Cmd+C copie le texte tant que le tooltip est affiché et qu'aucun champ n'a le focus.
+fn tooltip_body(ui: &mut egui::Ui, text: &str) {
+    ui.set_max_width(800.0);
+    ui.label(text);
+    ui.weak(t!("app.gantt.tooltip_copy_hint"));
+
+    let copy_requested = ui.memory(|mem| mem.focused().is_none())
+        && ui.input(|i| i.events.iter().any(|event| matches!(event, egui::Event::Copy)));
+    if copy_requested {
+        ui.ctx().copy_text(text.to_string());
+    }
+}
+
+/// Jobs réels qui chevauchent dans le temps (barres dessinées, voir `TimeBasis::job_span`) un
+/// autre job partageant au moins une ressource assignée : anomalie d'ordonnancement ou de données
+/// (préemption, relevé incohérent). Balayage par début croissant avec la liste des jobs en cours.
+fn overlapping_jobs<'a>(jobs: impl IntoIterator<Item = &'a Job>, time_basis: &TimeBasis) -> HashSet<u32> {
+    let mut seen: HashSet<u32> = HashSet::new();
+    let mut spans: Vec<(i64, i64, &Job)> = jobs
+        .into_iter()
+        .filter(|job| job.id != 0 && !job.assigned_resources.is_empty() && seen.insert(job.id))
+        .map(|job| {
+            let (start_s, end_s) = time_basis.job_span(job);
+            (start_s, end_s, job)
+        })
+        .collect();
+    spans.sort_by_key(|(start_s, _, job)| (*start_s, job.id));
+
+    let mut overlapping: HashSet<u32> = HashSet::new();
+    let mut active: Vec<(i64, HashSet<u32>, u32)> = Vec::new();
+    for (start_s, end_s, job) in spans {
+        active.retain(|(active_end_s, _, _)| *active_end_s > start_s);
+        let resources: HashSet<u32> = job.assigned_resources.iter().copied().collect();
+        for (_, active_resources, active_id) in active.iter() {
+            if !active_resources.is_disjoint(&resources) {
+                overlapping.insert(*active_id);
+                overlapping.insert(job.id);
             }
-            cur = 0;
-            in_num = false;
         }
+        active.push((end_s, resources, job.id));
     }
-    if in_num && cur <= i32::MAX as i64 {
-        out.push(cur as i32);
+    overlapping
+}
+
+/// Résumé "(N jobs, M cores)" d’un groupe de niveau 1, suivi du nombre de chevauchements
+/// détectés s'il y en a.
+/// Un job présent dans plusieurs sous-groupes n’est compté qu’une fois, et le job
+/// synthétique `all_resources` (id 0) est ignoré. Les cœurs correspondent au nombre
+/// de ressources OAR assignées.
+fn level_1_summary<'a>(jobs: impl IntoIterator<Item = &'a Job>, overlaps: usize) -> String {
+    let mut seen: HashSet<u32> = HashSet::new();
+    let mut cores = 0usize;
+    for job in jobs {
+        if job.id == 0 || !seen.insert(job.id) {
+            continue;
+        }
+        cores += job.assigned_resources.len();
+    }
+    let summary = t!(
+        "app.gantt.header_summary",
+        jobs = seen.len(),
+        cores = cores
+    )
+    .to_string();
+    if overlaps > 0 {
+        format!("{} {}", summary, t!("app.gantt.header_overlaps", count = overlaps))
+    } else {
+        summary
     }
-    out
 }
 
-fn cpuset_like_grid5000(s: &crate::models::data_structure::strata::Strata) -> Option<String> {
+/// Périodes de [start_s, end_s] non couvertes par les jobs réels (hors `all_resources`),
+/// calculées à partir des intervalles triés et fusionnés.
+fn idle_gaps(jobs: &[&Job], time_basis: &TimeBasis, (start_s, end_s): (i64, i64)) -> Vec<(i64, i64)> {
+    let mut intervals: Vec<(i64, i64)> = jobs
+        .iter()
+        .filter(|job| job.id != 0)
+        .map(|job| {
+            let (job_start, job_end) = time_basis.job_span(job);
+            (job_start.max(start_s), job_end.min(end_s))
+        })
+        .filter(|(job_start, job_end)| job_start < job_end)
+        .collect();
+    intervals.sort_unstable();
+
+    let mut gaps = Vec::new();
+    let mut covered_until = start_s;
+    for (job_start, job_end) in intervals {
+        if job_start > covered_until {
+            gaps.push((covered_until, job_start));
+        }
+        covered_until = covered_until.max(job_end);
+    }
+    if covered_until < end_s {
+        gaps.push((covered_until, end_s));
+    }
+    gaps
+}
 
-    if let Some(v) = s.cpuset.as_ref() {
-        match v {
-            serde_json::Value::Array(arr) => {
-                let mut ints: Vec<i32> = Vec::new();
-                for x in arr {
-                    match x {
-                        serde_json::Value::Number(n) => {
-                            if let Some(i) = n.as_i64() {
-                                if (0..=i32::MAX as i64).contains(&i) {
-                                    ints.push(i as i32);
-                                }
-                            }
-                        }
-                        serde_json::Value::String(s) => {
-                            for i in extract_ints_from_str(s) {
-                                ints.push(i);
-                            }
-                        }
-                        _ => {}
-                    }
-                }
-                if let Some(s) = format_cpuset_grid5000(&mut ints) {
-                    return Some(s);
-                }
+/// Grise les périodes d'inactivité d'une ligne d'hôte, sous les jobs.
+fn paint_idle_gaps(info: &Info, options: &Options, jobs: &[&Job], top_y: f32) {
+    let chart_clip_rect = Rect::from_min_max(
+        pos2(info.canvas.min.x + info.gutter_width, info.canvas.min.y),
+        pos2(info.canvas.max.x, info.canvas.max.y),
+    );
+    let painter = info.painter.with_clip_rect(chart_clip_rect);
+    let color = get_theme_colors(&info.ctx.style()).idle_gap;
+
+    for (gap_start, gap_end) in idle_gaps(jobs, &options.time_basis, info.visible_range_s(options)) {
+        let rect = Rect::from_min_max(
+            pos2(info.point_from_s(options, gap_start), top_y),
+            pos2(info.point_from_s(options, gap_end), top_y + options.rect_height),
+        );
+        painter.rect_filled(rect, 0.0, color);
+    }
+}
+
+/// Teinte toute la largeur d'une ligne d'hôte Alive sans job dans la fenêtre visible
+/// (voir `Options::show_idle`), sous les barres et les hachures.
+fn paint_idle_row(info: &Info, options: &Options, jobs: &[&Job], state: ResourceState, top_y: f32) {
+    if !options.show_idle
+        || state != ResourceState::Alive
+        || has_job_in_window(jobs, &options.time_basis, info.visible_range_s(options))
+    {
+        return;
+    }
+    let rect = Rect::from_min_max(
+        pos2(info.canvas.min.x + info.gutter_width, top_y),
+        pos2(info.canvas.max.x, top_y + options.rect_height),
+    );
+    info.painter.rect_filled(rect, 0.0, get_theme_colors(&info.ctx.style()).idle);
+}
+
+/// Nombre de cases de la bande d'activité d'un propriétaire
+const HEAT_STRIP_BUCKETS: usize = 60;
+/// Hauteur (px) de la bande d'activité dans la gouttière
+const HEAT_STRIP_HEIGHT: f32 = 4.0;
+
+/// Activité par case sur [start_s, end_s] : secondes de recouvrement pondérées par le nombre
+/// de ressources de chaque job réel (hors `all_resources`).
+fn activity_buckets(jobs: &[&Job], time_basis: &TimeBasis, (start_s, end_s): (i64, i64), buckets: usize) -> Vec<f64> {
+    let mut activity = vec![0.0; buckets];
+    if end_s <= start_s || buckets == 0 {
+        return activity;
+    }
+    let bucket_s = (end_s - start_s) as f64 / buckets as f64;
+    for job in jobs.iter().filter(|job| job.id != 0) {
+        let (job_start, job_end) = time_basis.job_span(job);
+        let (job_start, job_end) = (job_start.max(start_s), job_end.min(end_s));
+        if job_start >= job_end {
+            continue;
+        }
+        let weight = job.assigned_resources.len().max(1) as f64;
+        let first = ((job_start - start_s) as f64 / bucket_s) as usize;
+        let last = (((job_end - start_s) as f64 / bucket_s) as usize).min(buckets - 1);
+        for (index, value) in activity.iter_mut().enumerate().take(last + 1).skip(first) {
+            let bucket_start = start_s as f64 + index as f64 * bucket_s;
+            let overlap = (job_end as f64).min(bucket_start + bucket_s) - (job_start as f64).max(bucket_start);
+            if overlap > 0.0 {
+                *value += overlap * weight;
             }
-            serde_json::Value::String(raw) => {
-                let mut ints = extract_ints_from_str(raw);
-                if ints.len() > 1 {
-                    if let Some(s) = format_cpuset_grid5000(&mut ints) {
-                        return Some(s);
-                    }
-                }
+        }
+    }
+    activity
+}
+
+/// Bande d'activité d'un propriétaire dans la gouttière, sur toute la plage chargée,
+/// avec la fenêtre visible encadrée (mini-carte de la ligne).
+fn paint_heat_strip(info: &Info, options: &Options, jobs: &[&Job], bottom_y: f32) {
+    let activity = activity_buckets(jobs, &options.time_basis, (info.start_s, info.stop_s), HEAT_STRIP_BUCKETS);
+    let max_activity = activity.iter().cloned().fold(0.0, f64::max);
+    if max_activity <= 0.0 {
+        return;
+    }
+
+    let theme_colors = get_theme_colors(&info.ctx.style());
+    let strip_rect = Rect::from_min_max(
+        pos2(info.canvas.min.x + 6.0, bottom_y - HEAT_STRIP_HEIGHT - 1.0),
+        pos2(info.canvas.min.x + info.gutter_width - 6.0, bottom_y - 1.0),
+    );
+    let bucket_w = strip_rect.width() / HEAT_STRIP_BUCKETS as f32;
+    for (index, value) in activity.iter().enumerate() {
+        if *value <= 0.0 {
+            continue;
+        }
+        let intensity = (value / max_activity) as f32;
+        let x0 = strip_rect.min.x + index as f32 * bucket_w;
+        info.painter.rect_filled(
+            Rect::from_min_max(pos2(x0, strip_rect.min.y), pos2(x0 + bucket_w, strip_rect.max.y)),
+            0.0,
+            theme_colors.line.gamma_multiply(0.15 + 0.85 * intensity),
+        );
+    }
+
+    // Fenêtre visible, ramenée à l'échelle de la bande
+    let (visible_start, visible_end) = info.visible_range_s(options);
+    let span_s = (info.stop_s - info.start_s).max(1) as f32;
+    let to_x = |s: i64| {
+        strip_rect.min.x + strip_rect.width() * ((s - info.start_s) as f32 / span_s).clamp(0.0, 1.0)
+    };
+    info.painter.rect_stroke(
+        Rect::from_min_max(
+            pos2(to_x(visible_start), strip_rect.min.y - 1.0),
+            pos2(to_x(visible_end).max(to_x(visible_start) + 1.0), strip_rect.max.y + 1.0),
+        ),
+        0.0,
+        Stroke::new(1.0, theme_colors.text_dim),
+    );
+}
+
+/// Séparateur horizontal au-dessus d'un groupe de niveau 1 ou 2 : couleur et épaisseur de base
+/// du thème, épaisseur multipliée par `separator_scale`, niveau 2 éventuellement en tirets.
+fn paint_group_separator(info: &Info, options: &Options, chart_x0: f32, y: f32, level: u8) {
+    let theme_colors = get_theme_colors(&info.ctx.style());
+    let (color, width) = if level == 1 {
+        (theme_colors.aggregated_line_level_1, theme_colors.aggregated_line_width_level_1)
+    } else {
+        (theme_colors.aggregated_line_level_2, theme_colors.aggregated_line_width_level_2)
+    };
+    let stroke = Stroke::new(width * options.separator_scale, color);
+    let points = [pos2(chart_x0, y), pos2(info.canvas.max.x, y)];
+
+    if level != 1 && options.dashed_level_2_separators {
+        info.painter.extend(Shape::dashed_line(&points, stroke, 6.0, 4.0));
+    } else {
+        info.painter.line_segment(points, stroke);
+    }
+}
+
+/// Réserve, avant le dessin d'une ligne, la place de son fond zébré (`zebra_rows`) : sa hauteur
+/// n'est connue qu'après coup, mais il doit rester sous les jobs de la ligne.
+fn reserve_zebra_row(info: &Info, options: &Options) -> Option<egui::layers::ShapeIdx> {
+    options.zebra_rows.then(|| info.painter.add(Shape::Noop))
+}
+
+/// Fond d'une ligne sur toute la largeur du canvas, une ligne sur deux (rang `index` pair).
+fn paint_zebra_row(info: &Info, slot: Option<egui::layers::ShapeIdx>, index: usize, top: f32, bottom: f32) {
+    let Some(slot) = slot else {
+        return;
+    };
+    if index.is_multiple_of(2) && bottom > top {
+        let rect = Rect::from_min_max(pos2(info.canvas.min.x, top), pos2(info.canvas.max.x, bottom));
+        let color = get_theme_colors(&info.ctx.style()).zebra;
+        info.painter.set(slot, Shape::rect_filled(rect, 0.0, color));
+    }
+}
+
+/// Sous-ligne de chaque job de `jobs` (même ordre) parmi les jobs qui se chevauchent dans le
+/// temps, et nombre de sous-lignes. Coloration d'un graphe d'intervalles : par début croissant,
+/// chaque job prend la première sous-ligne libre, d'où autant de sous-lignes que de jobs simultanés
+/// au pic. Le job 0 (fond de la ligne) reste hors des sous-lignes.
+fn job_lanes(jobs: &[&Job], time_basis: &TimeBasis) -> (Vec<usize>, usize) {
+    let mut order: Vec<(i64, i64, usize)> = jobs
+        .iter()
+        .enumerate()
+        .filter(|(_, job)| job.id != 0)
+        .map(|(index, job)| {
+            let (start_s, end_s) = time_basis.job_span(job);
+            (start_s, end_s, index)
+        })
+        .collect();
+    order.sort_by_key(|&(start_s, _, index)| (start_s, jobs[index].id));
+
+    let mut lanes = vec![0; jobs.len()];
+    // Fin du dernier job de chaque sous-ligne
+    let mut lane_ends: Vec<i64> = Vec::new();
+    for (start_s, end_s, index) in order {
+        let lane = match lane_ends.iter().position(|&lane_end_s| lane_end_s <= start_s) {
+            Some(lane) => lane,
+            None => {
+                lane_ends.push(end_s);
+                lane_ends.len() - 1
             }
-            serde_json::Value::Number(n) => {
-                if let Some(i) = n.as_i64() {
-                    if (0..=i32::MAX as i64).contains(&i) {
-                        return Some(format!("[{}]", i));
-                    }
-                }
+        };
+        lane_ends[lane] = end_s;
+        lanes[index] = lane;
+    }
+    (lanes, lane_ends.len().max(1))
+}
+
+/// Haut et hauteur de la barre de chaque job d'une ligne dont les barres commencent à `job_row_y`.
+/// Avec `stack_shared_jobs` et une ligne `stacked` (hôte ou ressource), les jobs simultanés se
+/// partagent la hauteur de la ligne en sous-lignes ; sous `MIN_LANE_HEIGHT`, les sous-lignes en trop
+/// sont repliées sur les premières (les barres se chevauchent de nouveau).
+fn job_bar_slots(options: &Options, jobs: &[&Job], job_row_y: f32, stacked: bool) -> Vec<(f32, f32)> {
+    const MIN_LANE_HEIGHT: f32 = 3.0;
+    const LANE_GAP: f32 = 1.0;
+
+    let full_row = (job_row_y, options.rect_height);
+    if !options.stack_shared_jobs || !stacked {
+        return vec![full_row; jobs.len()];
+    }
+    let (lanes, lane_count) = job_lanes(jobs, &options.time_basis);
+    let max_lanes = ((options.rect_height + LANE_GAP) / (MIN_LANE_HEIGHT + LANE_GAP)).floor().max(1.0) as usize;
+    let lane_count = lane_count.min(max_lanes);
+    let lane_height = (options.rect_height - LANE_GAP * (lane_count - 1) as f32) / lane_count as f32;
+
+    jobs.iter()
+        .zip(lanes)
+        .map(|(job, lane)| {
+            if job.id == 0 {
+                full_row
+            } else {
+                let lane_top = job_row_y + (lane % lane_count) as f32 * (lane_height + LANE_GAP);
+                (lane_top, lane_height)
             }
-            _ => {}
-        }
+        })
+        .collect()
+}
+
+/// Haut des barres d'une ligne de jobs commençant à `row_top`. En mode compact, la barre est
+/// centrée dans la ligne de `row_height` px, comme le libellé de la gouttière : la zone survolée
+/// et cliquée d'un job (son rectangle dessiné) reste alignée sur l'hôte ou le groupe affiché en
+/// face. Hors mode compact, les lignes gardent leur placement d'origine.
+fn row_bar_top(row_top: f32, row_height: f32, bar_height: f32, compact: bool) -> f32 {
+    if compact {
+        row_top + ((row_height - bar_height) * 0.5).max(0.0)
+    } else {
+        row_top
     }
+}
 
-    let count = s.core_count.or(s.thread_count).unwrap_or(0);
-    if count <= 0 {
-        return None;
+/// Jobs correspondant aux indices d'un groupe mis en cache (voir `aggregation.rs`).
+/// Le job synthétique `all_resources` (id 0) passe en tête : il est peint en fond, sous les jobs réels.
+fn resolve_jobs<'a>(jobs: &'a [Job], indices: &[usize]) -> Vec<&'a Job> {
+    let mut job_list: Vec<&Job> = indices.iter().map(|&index| &jobs[index]).collect();
+    job_list.sort_by_key(|job| job.id != 0);
+    job_list
+}
+
+/// Vrai si au moins un job réel (hors `all_resources`) intersecte la fenêtre [start_s, end_s].
+fn has_job_in_window(jobs: &[&Job], time_basis: &TimeBasis, (start_s, end_s): (i64, i64)) -> bool {
+    jobs.iter().any(|job| {
+        let (job_start, job_end) = time_basis.job_span(job);
+        job.id != 0 && job_start <= end_s && job_end >= start_s
+    })
+}
+
+fn job_tooltip_text(job: &Job, time_basis: &TimeBasis) -> String {
+    // Le début affiché est celui qui place la barre (prévu ou réel)
+    let (start_s, is_actual) = time_basis.job_start(job);
+    let start_label = if is_actual {
+        t!("app.details.tooltip.actual_start_time")
+    } else {
+        t!("app.details.tooltip.start_time")
+    };
+    let mut text = format!(
+        "{}: {}\n{}: {:?}\n{}: {}\n{}: {}\n{}: {} seconds",
+        t!("app.details.tooltip.job_id"),
+        job.id,
+        t!("app.details.tooltip.owner"),
+        job.owner,
+        t!("app.details.tooltip.state"),
+        job.state.get_label(),
+        start_label,
+        format_timestamp(start_s),
+        t!("app.details.tooltip.walltime"),
+        job.walltime
+    );
+    if let Some(delay) = job.scheduling_delay() {
+        text.push_str(&format!(
+            "\n{}: {}",
+            t!("app.details.tooltip.scheduling_delay"),
+            format_duration(delay)
+        ));
     }
-    Some(format!("0-{}", count - 1))
+    // Ressources OAR attribuées, tronquées : la liste complète est dans la fenêtre de détails
+    if !job.assigned_resources.is_empty() {
+        text.push_str(&format!(
+            "\n{} ({}): {}",
+            t!("app.details.tooltip.resources"),
+            job.assigned_resources.len(),
+            truncate_ranges(&format_resource_ids(&job.assigned_resources), 60)
+        ));
+    }
+    text
 }
 
 pub(super) fn paint_tooltip(info: &Info, options: &mut Options, app: &ApplicationContext) {
     let mut tooltip_text = String::new();
 
     if let Some(job) = &options.current_hovered_job {
-        tooltip_text.push_str(&format!(
-            "{}: {}\n{}: {:?}\n{}: {}\n{}: {}\n{}: {} seconds",
-            t!("app.details.tooltip.job_id"),
-            job.id,
-            t!("app.details.tooltip.owner"),
-            job.owner,
-            t!("app.details.tooltip.state"),
-            job.state.get_label(),
-            t!("app.details.tooltip.start_time"),
-            format_timestamp(job.scheduled_start),
-            t!("app.details.tooltip.walltime"),
-            job.walltime
-        ));
+        tooltip_text.push_str(&job_tooltip_text(job, &options.time_basis));
     }
 
     if let Some(resource_state) = &options.current_hovered_resource_state {
@@ -195,76 +649,117 @@ pub(super) fn paint_tooltip(info: &Info, options: &mut Options, app: &Applicatio
         if let Some(label) = options.current_hovered_resource_label.as_deref() {
             let trimmed = label.trim();
             if !trimmed.is_empty() {
-                tooltip_text.push_str(&format!("{}: {}\n", kind_label.to_lowercase(), trimmed));
-
-                let mut keys_to_try: Vec<String> = Vec::new();
-                keys_to_try.push(trimmed.to_string());
-                keys_to_try.push(trimmed.split('.').next().unwrap_or(trimmed).to_string());
-                for key in keys_to_try {
-                    if let Some(s) = app.strata_by_host.get(&key) {
-                        if let Some(cluster) = s.cluster.as_deref().map(str::trim).filter(|v| !v.is_empty()) {
-                            tooltip_text.push_str(&format!("cluster: {}\n", cluster));
-                        }
-                        if let Some(net) = s
-                            .network_address
-                            .as_deref()
-                            .map(str::trim)
-                            .filter(|v| !v.is_empty())
-                        {
-                            tooltip_text.push_str(&format!("network_address: {}\n", net));
-                        }
-                        if let Some(comment) = s.comment.as_deref().map(str::trim).filter(|v| !v.is_empty()) {
-                            tooltip_text.push_str(&format!("comment: {}\n", comment));
-                        }
-                        if let Some(cpuset) = cpuset_like_grid5000(s) {
-                            let cpuset = cpuset.trim();
-                            if !cpuset.is_empty() {
-                                tooltip_text.push_str(&format!("cpuset: {}\n", cpuset));
-                            }
-                        }
-                        if let Some(model) = s.nodemodel.as_deref().map(str::trim).filter(|v| !v.is_empty()) {
-                            tooltip_text.push_str(&format!("nodemodel: {}\n", model));
-                        }
-                        if let Some(cpu) = s.cputype.as_deref().map(str::trim).filter(|v| !v.is_empty()) {
-                            tooltip_text.push_str(&format!("cputype: {}\n", cpu));
-                        }
-                        if let Some(rid) = s.resource_id {
-                            tooltip_text.push_str(&format!("resource_id: {}\n", rid));
-                        }
-                        break;
+                if kind_label == "Host" {
+                    for line in host_tooltip_lines(trimmed, app, &options.strata_fields) {
+                        tooltip_text.push_str(&line);
+                        tooltip_text.push('\n');
+                    }
+                } else {
+                    tooltip_text.push_str(&format!("{}: {}\n", kind_label.to_lowercase(), trimmed));
+                }
+
+                // Taille du cluster survolé
+                if kind_label == "Cluster" {
+                    if let Some(cluster) = app.all_clusters.iter().find(|c| c.name == trimmed) {
+                        let (hosts, cores, threads, gpus) =
+                            cluster_resource_summary(cluster, &app.gpu_count_by_host);
+                        tooltip_text.push_str(&format!(
+                            "{}\n",
+                            t!(
+                                "app.gantt.cluster_summary",
+                                hosts = hosts,
+                                cores = cores,
+                                threads = threads,
+                                gpus = gpus
+                            )
+                        ));
                     }
                 }
             }
         }
 
         tooltip_text.push_str(&format!(
-            "{} State: {:?}",
+            "{} State: {:?}\n{}",
             kind_label,
-            resource_state
+            resource_state,
+            t!("app.gantt.state_snapshot_note")
         ));
         options.current_hovered_resource_state = None;
         options.current_hovered_resource_label = None;
     }
 
+    match options.tooltip_mode.mode {
+        TooltipModeEnum::Instant => {}
+        TooltipModeEnum::Delayed => {
+            // Le survol doit rester sur le même élément pendant le délai
+            let now = info.ctx.input(|i| i.time);
+            if tooltip_text.is_empty() {
+                options.tooltip_hover = None;
+            } else if options.tooltip_hover.as_ref().is_none_or(|(text, _)| *text != tooltip_text) {
+                options.tooltip_hover = Some((tooltip_text.clone(), now));
+            }
+            if let Some((_, since)) = options.tooltip_hover {
+                let remaining = options.tooltip_mode.delay_s - (now - since);
+                if remaining > 0.0 {
+                    info.ctx.request_repaint_after(std::time::Duration::from_secs_f64(remaining));
+                    tooltip_text.clear();
+                }
+            }
+        }
+        TooltipModeEnum::OnClick => {
+            // Un clic épingle le tooltip de l'élément cliqué, un clic ailleurs le retire
+            if info.response.clicked() {
+                options.tooltip_pinned = info
+                    .response
+                    .hover_pos()
+                    .filter(|_| !tooltip_text.is_empty())
+                    .map(|pos| (tooltip_text.clone(), pos));
+            }
+            tooltip_text.clear();
+            if let Some((text, pos)) = &options.tooltip_pinned {
+                egui::containers::popup::show_tooltip_at(
+                    &info.ctx,
+                    info.response.layer_id,
+                    egui::Id::new("pinned-tooltip"),
+                    *pos,
+                    |ui| tooltip_body(ui, text),
+                );
+                return;
+            }
+        }
+    }
+
     if !tooltip_text.is_empty() {
         if let Some(_pointer_pos) = info.response.hover_pos() {
             egui::show_tooltip_at_pointer(
                 &info.ctx,
                 info.response.layer_id,
                 egui::Id::new("tooltip"),
-                |ui| {
-                    ui.set_max_width(800.0);
-                    ui.label(tooltip_text);
-                },
+                |ui| tooltip_body(ui, &tooltip_text),
+            );
+            return;
+        }
+    }
+
+    // Sans survol souris, le job sélectionné au clavier garde son tooltip
+    if let (Some(job_id), Some(rect)) = (options.selected_job_id, options.selected_job_rect) {
+        if let Some(job) = app.filtered_jobs.iter().find(|j| j.id == job_id) {
+            egui::containers::popup::show_tooltip_for(
+                &info.ctx,
+                info.response.layer_id,
+                egui::Id::new("selected-job-tooltip"),
+                &rect,
+                |ui| tooltip_body(ui, &job_tooltip_text(job, &options.time_basis)),
             );
         }
     }
 }
 
-pub(super) fn paint_aggregated_jobs_level_1<'a>(
+pub(super) fn paint_aggregated_jobs_level_1(
     info: &Info,
     options: &mut Options,
-    jobs: BTreeMap<String, Vec<&'a Job>>,
+    jobs: &[Job],
+    groups: &Level1Groups,
     mut cursor_y: f32,
     details_window: &mut Vec<JobDetailsWindow>,
     collapsed_jobs: &mut BTreeMap<String, bool>,
@@ -274,7 +769,6 @@ pub(super) fn paint_aggregated_jobs_level_1<'a>(
     gutter_width: f32,
     app: &ApplicationContext,
 ) -> f32 {
-    let theme_colors = get_theme_colors(&info.ctx.style());
 
     let compact = options.compact_rows;
     let row_height = options.rect_height.max(info.text_height);
@@ -285,35 +779,56 @@ pub(super) fn paint_aggregated_jobs_level_1<'a>(
 
     cursor_y += spacing_between_level_1;
 
-    let mut sorted_level_1: Vec<String> = jobs.keys().cloned().collect();
+    let mut sorted_level_1: Vec<String> = groups.keys().cloned().collect();
     sorted_level_1.sort_by(|a, b| compare_string_with_number(a, b));
 
-    let aggregation_height = font_size as f32 + 5.0 + offset_level_1;
-
     let chart_x0 = info.canvas.min.x + gutter_width;
 
+    let visible_range = info.visible_range_s(options);
+
+    let group_cap = options.group_cap();
+    let mut painted_groups = 0;
+    let mut hidden_groups = 0;
+
     for level_1 in sorted_level_1 {
-        let job_list = jobs.get(&level_1).unwrap();
+        let job_list = resolve_jobs(jobs, &groups[&level_1]);
 
-        info.painter.line_segment(
-            [
-                pos2(chart_x0, cursor_y),
-                pos2(info.canvas.max.x, cursor_y),
-            ],
-            Stroke::new(1.5, theme_colors.aggregated_line_level_1),
-        );
+        if options.hide_idle_rows && !has_job_in_window(&job_list, &options.time_basis, visible_range) {
+            continue;
+        }
+        if painted_groups >= group_cap {
+            hidden_groups += 1;
+            continue;
+        }
+        painted_groups += 1;
+        options.overlapping_jobs = overlapping_jobs(job_list.iter().copied(), &options.time_basis);
+
+        let row_top = cursor_y;
+        let zebra_slot = reserve_zebra_row(info, options);
+
+        paint_group_separator(info, options, chart_x0, cursor_y, 1);
 
         cursor_y += offset_level_1;
 
         let text_pos = pos2(info.canvas.min.x + 6.0, cursor_y + info.text_height * 0.5);
 
         let is_collapsed = collapsed_jobs.entry(level_1.clone()).or_insert(false);
-        *is_collapsed = false;
         let label_meta = build_label_meta_level1(&level_1, aggregate_by, all_cluster);
 
-        paint_job_info(
+        // Les badges d'hôtes gardent leur nom seul ; les autres en-têtes affichent un résumé.
+        let header_label = if label_meta.is_none() {
+            format!(
+                "{} {}",
+                level_1,
+                level_1_summary(job_list.iter().copied(), options.overlapping_jobs.len())
+            )
+        } else {
+            level_1.clone()
+        };
+
+        let label_clicked = paint_job_info(
             info,
-            &level_1,
+            &header_label,
             text_pos,
             is_collapsed,
             1,
@@ -322,7 +837,17 @@ pub(super) fn paint_aggregated_jobs_level_1<'a>(
             compact,
             label_meta,
             app,
+            options,
+            &job_list,
         );
+        if label_clicked {
+            let filter = match aggregate_by {
+                AggregateByLevel1Enum::Owner => Some(GutterFilter::Owner(level_1.clone())),
+                AggregateByLevel1Enum::Host => Some(GutterFilter::Host(level_1.clone())),
+                AggregateByLevel1Enum::Cluster => None,
+            };
+            handle_gutter_click(options, is_collapsed, filter, &job_list);
+        }
 
         cursor_y += spacing_between_level_1;
 
@@ -340,20 +865,31 @@ pub(super) fn paint_aggregated_jobs_level_1<'a>(
         };
 
         if !*is_collapsed {
-            let job_row_y = cursor_y;
+            let job_row_y = row_bar_top(cursor_y, row_height, options.rect_height, compact);
 
-            for job in job_list {
-                paint_job(
+            if aggregate_by == AggregateByLevel1Enum::Host {
+                paint_idle_row(info, options, &job_list, state, job_row_y);
+            }
+            if options.show_idle_gaps && aggregate_by == AggregateByLevel1Enum::Host {
+                paint_idle_gaps(info, options, &job_list, job_row_y);
+            }
+
+            let stacked = aggregate_by == AggregateByLevel1Enum::Host;
+            let bar_slots = job_bar_slots(options, &job_list, job_row_y, stacked);
+            for (job, (bar_top, bar_height)) in job_list.iter().zip(bar_slots) {
+                let result = paint_job(
                     info,
                     options,
                     job,
-                    job_row_y,
+                    bar_top,
                     details_window,
                     all_cluster,
+                    &app.strata_by_host,
                     state,
-                    aggregation_height,
+                    bar_height,
                     resource_label_for_state_tooltip,
                 );
+                options.frame_stats.record_job(result != PaintResult::Culled);
             }
 
             if !job_list.is_empty() {
@@ -362,15 +898,35 @@ pub(super) fn paint_aggregated_jobs_level_1<'a>(
             cursor_y += spacing_between_level_1;
         }
         cursor_y += spacing_between_level_1;
+        paint_zebra_row(info, zebra_slot, painted_groups, row_top, cursor_y);
+
+        if options.show_owner_heat_strip && aggregate_by == AggregateByLevel1Enum::Owner {
+            paint_heat_strip(info, options, &job_list, cursor_y);
+        }
+
+        // Saut rapide vers un propriétaire : on relève sa ligne pour la faire défiler à l'écran
+        if aggregate_by == AggregateByLevel1Enum::Owner && options.scroll_to_owner.as_ref() == Some(&level_1) {
+            options.scroll_to_owner = None;
+            options.scroll_to_rect = Some(Rect::from_min_max(
+                pos2(info.canvas.min.x, row_top),
+                pos2(info.canvas.max.x, cursor_y),
+            ));
+        }
+    }
+
+    options.frame_stats.groups_level_1 += painted_groups;
+    if hidden_groups > 0 {
+        cursor_y = paint_show_more_groups(info, options, cursor_y, hidden_groups);
     }
 
     cursor_y
 }
 
-pub(super) fn paint_aggregated_jobs_level_2<'a>(
+pub(super) fn paint_aggregated_jobs_level_2(
     info: &Info,
     options: &mut Options,
-    jobs: BTreeMap<String, BTreeMap<String, Vec<&'a Job>>>,
+    jobs: &[Job],
+    groups: &Level2Groups,
     mut cursor_y: f32,
     details_window: &mut Vec<JobDetailsWindow>,
     collapsed_jobs_level_1: &mut BTreeMap<String, bool>,
@@ -401,15 +957,15 @@ pub(super) fn paint_aggregated_jobs_level_2<'a>(
 
     #[derive(Clone)]
     struct GanttGutterHostRow {
+        cluster: String,
         host_short: String,
         host_full: String,
-        cluster: String,
-        site: String,
         row_rect: Rect,
     }
 
     #[derive(Clone)]
     struct GanttGutterSpan {
+        label: String,
         top: f32,
         bottom: f32,
     }
@@ -428,18 +984,49 @@ pub(super) fn paint_aggregated_jobs_level_2<'a>(
 
     cursor_y += spacing_between_level_1;
 
-    let mut sorted_level_1: Vec<String> = jobs.keys().cloned().collect();
+    let mut sorted_level_1: Vec<String> = groups.keys().cloned().collect();
     sorted_level_1.sort_by(|a, b| compare_string_with_number(a, b));
 
     let chart_x0 = info.canvas.min.x + gutter_width;
 
+    let visible_range = info.visible_range_s(options);
+
+    let group_cap = options.group_cap();
+    let mut painted_groups = 0;
+    let mut hidden_groups = 0;
+    // Rang des lignes de niveau 2 dessinées, pour l'alternance des fonds
+    let mut zebra_index = 0;
+
     for level_1 in sorted_level_1 {
         let level_1_section_top = cursor_y;
-        let level_2_map = jobs.get(&level_1).unwrap();
+        let level_2_map = &groups[&level_1];
+
+        if options.hide_idle_rows
+            && !level_2_map
+                .values()
+                .any(|job_list| has_job_in_window(&resolve_jobs(jobs, job_list), &options.time_basis, visible_range))
+        {
+            continue;
+        }
+        if painted_groups >= group_cap {
+            hidden_groups += 1;
+            continue;
+        }
+        painted_groups += 1;
+        options.overlapping_jobs = overlapping_jobs(
+            level_2_map.values().flatten().map(|&index| &jobs[index]),
+            &options.time_basis,
+        );
         let level_1_key = level_1.clone();
 
         let cluster_site = if hide_level_1_headers {
-            site_for_cluster_name(&level_1, all_cluster).unwrap_or_default()
+            site_for_cluster_name(
+                &level_1,
+                all_cluster,
+                &app.strata_by_host,
+                &app.cluster_site_map,
+            )
+            .unwrap_or_default()
         } else {
             String::new()
         };
@@ -448,13 +1035,7 @@ pub(super) fn paint_aggregated_jobs_level_2<'a>(
         let mut cluster_bottom: Option<f32> = None;
 
         if !hide_level_1_headers {
-            info.painter.line_segment(
-                [
-                    pos2(chart_x0, cursor_y),
-                    pos2(info.canvas.max.x, cursor_y),
-                ],
-                Stroke::new(1.5, theme_colors.aggregated_line_level_1),
-            );
+            paint_group_separator(info, options, chart_x0, cursor_y, 1);
 
             cursor_y += offset_level_1;
 
@@ -470,13 +1051,31 @@ pub(super) fn paint_aggregated_jobs_level_2<'a>(
             let is_collapsed_level_1 = collapsed_jobs_level_1
                 .entry(level_1.clone())
                 .or_insert(false);
-            *is_collapsed_level_1 = false;
             let label_meta_level_1 =
                 build_label_meta_level1(&level_1, aggregate_by_level_1, all_cluster);
 
-            paint_job_info(
+            let header_label = if label_meta_level_1.is_none() {
+                format!(
+                    "{} {}",
+                    level_1,
+                    level_1_summary(
+                        level_2_map.values().flatten().map(|&index| &jobs[index]),
+                        options.overlapping_jobs.len()
+                    )
+                )
+            } else {
+                level_1.clone()
+            };
+
+            // Jobs de l'hôte pour son tooltip (en-tête d'hôte seulement)
+            let level_1_jobs: Vec<&Job> = if label_meta_level_1.is_some() {
+                resolve_jobs(jobs, &level_2_map.values().flatten().copied().collect::<Vec<_>>())
+            } else {
+                Vec::new()
+            };
+            let label_clicked = paint_job_info(
                 info,
-                &level_1,
+                &header_label,
                 text_pos,
                 is_collapsed_level_1,
                 1,
@@ -485,7 +1084,18 @@ pub(super) fn paint_aggregated_jobs_level_2<'a>(
                 compact,
                 label_meta_level_1,
                 app,
+                options,
+                &level_1_jobs,
             );
+            if label_clicked {
+                let filter = match aggregate_by_level_1 {
+                    AggregateByLevel1Enum::Owner => Some(GutterFilter::Owner(level_1.clone())),
+                    AggregateByLevel1Enum::Host => Some(GutterFilter::Host(level_1.clone())),
+                    AggregateByLevel1Enum::Cluster => None,
+                };
+                let group_jobs = resolve_jobs(jobs, &level_2_map.values().flatten().copied().collect::<Vec<_>>());
+                handle_gutter_click(options, is_collapsed_level_1, filter, &group_jobs);
+            }
 
             if compact {
                 cursor_y += header_height;
@@ -499,21 +1109,38 @@ pub(super) fn paint_aggregated_jobs_level_2<'a>(
         let is_collapsed_level_1 = collapsed_jobs_level_1
             .entry(level_1.clone())
             .or_insert(false);
-        *is_collapsed_level_1 = false;
 
         if !*is_collapsed_level_1 {
-            let mut sorted_level_2: Vec<_> = level_2_map.keys().collect();
-            sorted_level_2.sort_by(|a, b| compare_string_with_number(a, b));
+            let mut sorted_level_2: Vec<_> = level_2_map
+                .iter()
+                .filter(|(_, job_list)| {
+                    !options.hide_idle_rows
+                        || has_job_in_window(&resolve_jobs(jobs, job_list), &options.time_basis, visible_range)
+                })
+                .map(|(level_2, _)| level_2)
+                .collect();
+            if aggregate_by_level_2 == AggregateByLevel2Enum::Host && options.host_sort.is_by_state()
+            {
+                // Regroupe les hôtes en panne (Dead, puis Absent) en tête du cluster
+                sorted_level_2.sort_by(|a, b| {
+                    let rank_a = HostSort::state_rank(&get_host_state_from_name(all_cluster, a));
+                    let rank_b = HostSort::state_rank(&get_host_state_from_name(all_cluster, b));
+                    rank_a
+                        .cmp(&rank_b)
+                        .then_with(|| compare_string_with_number(a, b))
+                });
+            } else {
+                sorted_level_2.sort_by(|a, b| compare_string_with_number(a, b));
+            }
 
             for level_2 in sorted_level_2 {
-                if let Some(job_list) = level_2_map.get(level_2) {
-                    info.painter.line_segment(
-                        [
-                            pos2(chart_x0, cursor_y),
-                            pos2(info.canvas.max.x, cursor_y),
-                        ],
-                        Stroke::new(0.5, theme_colors.aggregated_line_level_2),
-                    );
+                if let Some(job_indices) = level_2_map.get(level_2) {
+                    options.frame_stats.groups_level_2 += 1;
+                    zebra_index += 1;
+                    let row_top = cursor_y;
+                    let zebra_slot = reserve_zebra_row(info, options);
+                    let job_list = resolve_jobs(jobs, job_indices);
+                    paint_group_separator(info, options, chart_x0, cursor_y, 2);
 
                     cursor_y += spacing_between_level_2;
 
@@ -533,7 +1160,6 @@ pub(super) fn paint_aggregated_jobs_level_2<'a>(
                     let is_collapsed_level_2 = collapsed_jobs_level_2
                         .entry((level_1_key.to_string(), level_2.to_string()))
                         .or_insert(false);
-                    *is_collapsed_level_2 = false;
                     let label_meta_level_2 = build_label_meta_level2(
                         &level_1,
                         level_2,
@@ -543,22 +1169,26 @@ pub(super) fn paint_aggregated_jobs_level_2<'a>(
                     );
 
                     if hide_level_1_headers {
-                        let extra_pad = if compact { 2.0 } else { 10.0 };
-                        let row_height = options.rect_height.max(info.text_height + extra_pad);
+                        // En compact, la ligne de la gouttière couvre exactement la bande de la
+                        // ligne de jobs : pas de chevauchement avec les hôtes voisins au survol
+                        let gutter_row_height = if compact {
+                            row_height
+                        } else {
+                            options.rect_height.max(info.text_height + 10.0)
+                        };
                         let row_rect = Rect::from_min_max(
-                            pos2(info.canvas.min.x, row_center_y - row_height * 0.5),
+                            pos2(info.canvas.min.x, row_center_y - gutter_row_height * 0.5),
                             pos2(
                                 info.canvas.min.x + gutter_width,
-                                row_center_y + row_height * 0.5,
+                                row_center_y + gutter_row_height * 0.5,
                             ),
                         );
 
                         let host_short = short_host_label(&level_2.to_string());
                         grid5000_host_rows.push(GanttGutterHostRow {
+                            cluster: level_1.clone(),
                             host_short,
                             host_full: level_2.to_string(),
-                            cluster: level_1.clone(),
-                            site: cluster_site.clone(),
                             row_rect,
                         });
 
@@ -566,7 +1196,7 @@ pub(super) fn paint_aggregated_jobs_level_2<'a>(
                         cluster_bottom =
                             Some(cluster_bottom.unwrap_or(row_rect.max.y).max(row_rect.max.y));
                     } else {
-                        paint_job_info(
+                        let label_clicked = paint_job_info(
                             info,
                             &level_2.to_string(),
                             text_pos,
@@ -577,13 +1207,26 @@ pub(super) fn paint_aggregated_jobs_level_2<'a>(
                             compact,
                             label_meta_level_2,
                             app,
+                            options,
+                            &job_list,
                         );
+                        if label_clicked {
+                            let filter = match aggregate_by_level_2 {
+                                AggregateByLevel2Enum::Owner => Some(GutterFilter::Owner(level_2.to_string())),
+                                AggregateByLevel2Enum::Host => Some(GutterFilter::Host(level_2.to_string())),
+                                AggregateByLevel2Enum::Resource | AggregateByLevel2Enum::None => None,
+                            };
+                            handle_gutter_click(options, is_collapsed_level_2, filter, &job_list);
+                        }
                     }
 
                     cursor_y += spacing_between_level_2;
 
                     let state = if aggregate_by_level_2 == AggregateByLevel2Enum::Host {
                         get_host_state_from_name(all_cluster, level_2)
+                    } else if aggregate_by_level_2 == AggregateByLevel2Enum::Resource {
+                        resource_id_from_row_label(level_2)
+                            .map_or(ResourceState::Unknown, |id| get_resource_state_from_id(all_cluster, id))
                     } else if aggregate_by_level_2 == AggregateByLevel2Enum::None {
                         if aggregate_by_level_1 == AggregateByLevel1Enum::Host {
                             get_host_state_from_name(all_cluster, &level_1)
@@ -597,42 +1240,74 @@ pub(super) fn paint_aggregated_jobs_level_2<'a>(
                     };
 
                     if !*is_collapsed_level_2 {
-                        let aligned_y = if compact {
-                            cursor_y
+                        let job_row_y = if compact {
+                            row_bar_top(cursor_y, row_height, options.rect_height, compact)
                         } else {
                             cursor_y - options.rect_height * 0.5
                         };
-                        let job_row_y = aligned_y;
 
-                        let adjusted_aggregation_height = spacing_between_level_2 * 2.0;
+                        let resource_label_for_state_tooltip =
+                            if aggregate_by_level_2 == AggregateByLevel2Enum::Host
+                                || aggregate_by_level_2 == AggregateByLevel2Enum::Resource
+                            {
+                                Some(level_2.as_str())
+                            } else if aggregate_by_level_2 == AggregateByLevel2Enum::None {
+                                if aggregate_by_level_1 == AggregateByLevel1Enum::Host
+                                    || aggregate_by_level_1 == AggregateByLevel1Enum::Cluster
+                                {
+                                    Some(level_1.as_str())
+                                } else {
+                                    None
+                                }
+                            } else {
+                                None
+                            };
+
+                        let is_resource_row = aggregate_by_level_2 == AggregateByLevel2Enum::Host
+                            || aggregate_by_level_2 == AggregateByLevel2Enum::Resource;
+                        if is_resource_row {
+                            paint_idle_row(info, options, &job_list, state, job_row_y);
+                        }
+                        if options.show_idle_gaps && is_resource_row {
+                            paint_idle_gaps(info, options, &job_list, job_row_y);
+                        }
 
-                        for job in job_list.iter() {
-                            paint_job(
+                        // Ligne d'un hôte ou d'une ressource : ses jobs simultanés peuvent s'y empiler
+                        let stacked = is_resource_row
+                            || (aggregate_by_level_2 == AggregateByLevel2Enum::None
+                                && aggregate_by_level_1 == AggregateByLevel1Enum::Host);
+                        let bar_slots = job_bar_slots(options, &job_list, job_row_y, stacked);
+                        let mut any_job_painted = false;
+                        for (job, (bar_top, bar_height)) in job_list.iter().zip(bar_slots) {
+                            let result = paint_job(
                                 info,
                                 options,
                                 job,
-                                job_row_y,
+                                bar_top,
                                 details_window,
                                 all_cluster,
+                                &app.strata_by_host,
                                 state,
-                                adjusted_aggregation_height,
-                                if aggregate_by_level_2 == AggregateByLevel2Enum::Host {
-                                    Some(level_2.as_str())
-                                } else if aggregate_by_level_2 == AggregateByLevel2Enum::None {
-                                    if aggregate_by_level_1 == AggregateByLevel1Enum::Host
-                                        || aggregate_by_level_1 == AggregateByLevel1Enum::Cluster
-                                    {
-                                        Some(level_1.as_str())
-                                    } else {
-                                        None
-                                    }
-                                } else {
-                                    None
-                                },
+                                bar_height,
+                                resource_label_for_state_tooltip,
                             );
+                            options.frame_stats.record_job(result != PaintResult::Culled);
+                            any_job_painted |= result != PaintResult::Culled;
                         }
 
-                        if !job_list.is_empty() {
+                        // Hôte inactif : la ligne reste visible avec la hachure de son état
+                        if options.show_all_hosts && !any_job_painted {
+                            paint_resource_state_hatch(
+                                info,
+                                options,
+                                job_row_y,
+                                options.rect_height,
+                                state,
+                                resource_label_for_state_tooltip,
+                            );
+                        }
+
+                        if !job_list.is_empty() || options.show_all_hosts {
                             let row_spacing = if compact
                                 && aggregate_by_level_1 == AggregateByLevel1Enum::Host
                                 && aggregate_by_level_2 == AggregateByLevel2Enum::Owner
@@ -645,8 +1320,39 @@ pub(super) fn paint_aggregated_jobs_level_2<'a>(
                         }
                     }
                     cursor_y += spacing_between_level_2;
+                    paint_zebra_row(info, zebra_slot, zebra_index, row_top, cursor_y);
                 }
             }
+        } else if aggregate_by_level_2 == AggregateByLevel2Enum::Resource {
+            // Hôte replié en vue Hôte → Ressource : ses jobs sur une seule ligne, comme en vue Hôte
+            let mut host_indices: Vec<usize> = level_2_map.values().flatten().copied().collect();
+            host_indices.sort_unstable();
+            host_indices.dedup();
+            let job_list = resolve_jobs(jobs, &host_indices);
+            let state = get_host_state_from_name(all_cluster, &level_1);
+            let job_row_y = row_bar_top(cursor_y, row_height, options.rect_height, compact);
+
+            paint_idle_row(info, options, &job_list, state, job_row_y);
+            if options.show_idle_gaps {
+                paint_idle_gaps(info, options, &job_list, job_row_y);
+            }
+            let bar_slots = job_bar_slots(options, &job_list, job_row_y, true);
+            for (job, (bar_top, bar_height)) in job_list.iter().zip(bar_slots) {
+                let result = paint_job(
+                    info,
+                    options,
+                    job,
+                    bar_top,
+                    details_window,
+                    all_cluster,
+                    &app.strata_by_host,
+                    state,
+                    bar_height,
+                    Some(level_1.as_str()),
+                );
+                options.frame_stats.record_job(result != PaintResult::Culled);
+            }
+            cursor_y += row_height + spacing_between_jobs + options.spacing;
         }
         cursor_y += spacing_between_level_1;
 
@@ -681,6 +1387,7 @@ pub(super) fn paint_aggregated_jobs_level_2<'a>(
         if hide_level_1_headers {
             if let (Some(top), Some(bottom)) = (cluster_top, cluster_bottom) {
                 grid5000_cluster_spans.push(GanttGutterSpan {
+                    label: level_1.clone(),
                     top,
                     bottom,
                 });
@@ -690,6 +1397,7 @@ pub(super) fn paint_aggregated_jobs_level_2<'a>(
                         *s_bottom = (*s_bottom).max(bottom);
                     } else {
                         grid5000_site_spans.push(GanttGutterSpan {
+                            label: label.clone(),
                             top: *s_top,
                             bottom: *s_bottom,
                         });
@@ -706,8 +1414,9 @@ pub(super) fn paint_aggregated_jobs_level_2<'a>(
     }
 
     if hide_level_1_headers {
-        if let Some((_label, top, bottom)) = current_site.take() {
+        if let Some((label, top, bottom)) = current_site.take() {
             grid5000_site_spans.push(GanttGutterSpan {
+                label,
                 top,
                 bottom,
             });
@@ -771,6 +1480,19 @@ pub(super) fn paint_aggregated_jobs_level_2<'a>(
                 pos2(cluster_x1, span.bottom),
             );
             gutter_painter.rect_filled(rect, 0.0, c_cluster);
+
+            if info.response.hover_pos().is_some_and(|mouse_pos| rect.contains(mouse_pos)) {
+                let layer_id = egui::LayerId::new(
+                    egui::Order::Tooltip,
+                    egui::Id::new("gantt-grid5000-cluster-tooltip-layer"),
+                );
+                egui::containers::popup::show_tooltip_at_pointer(
+                    &info.ctx,
+                    layer_id,
+                    egui::Id::new(format!("gantt-grid5000-cluster-tooltip:{}", span.label)),
+                    |ui| tooltip_body(ui, &cluster_tooltip_lines(&span.label, app).join("\n")),
+                );
+            }
         }
 
         for row in &grid5000_host_rows {
@@ -827,99 +1549,12 @@ pub(super) fn paint_aggregated_jobs_level_2<'a>(
                         layer_id,
                         egui::Id::new(format!("gantt-grid5000-host-tooltip:{}", row.host_full)),
                         |ui| {
-                            ui.label(format!("host: {}", row.host_full));
-                            if !row.cluster.trim().is_empty() {
-                                ui.label(format!("cluster: {}", row.cluster));
-                            }
-                            if !row.site.trim().is_empty() {
-                                ui.label(format!("site: {}", row.site));
-                            }
-
-                            let key_full = row.host_full.trim();
-                            let key_short = short_host_label(key_full);
-                            if let Some(s) = app
-                                .strata_by_host
-                                .get(key_full)
-                                .or_else(|| app.strata_by_host.get(&key_short))
-                            {
-                                if let Some(b) = s
-                                    .besteffort
-                                    .as_deref()
-                                    .map(str::trim)
-                                    .filter(|v| !v.is_empty())
-                                {
-                                    ui.label(format!("besteffort: {}", b));
-                                }
-                                if let Some(comment) = s
-                                    .comment
-                                    .as_deref()
-                                    .map(str::trim)
-                                    .filter(|v| !v.is_empty())
-                                {
-                                    ui.label(format!("comment: {}", comment));
-                                }
-                                if let Some(cpuset) = cpuset_like_grid5000(s) {
-                                    let cpuset = cpuset.trim();
-                                    if !cpuset.is_empty() {
-                                        ui.label(format!("cpuset: {}", cpuset));
-                                    }
-                                }
-                                if let Some(dep) = s
-                                    .deploy
-                                    .as_deref()
-                                    .map(str::trim)
-                                    .filter(|v| !v.is_empty())
-                                {
-                                    ui.label(format!("deploy: {}", dep));
-                                }
-                                if let Some(dr) = s
-                                    .drain
-                                    .as_deref()
-                                    .map(str::trim)
-                                    .filter(|v| !v.is_empty())
-                                {
-                                    ui.label(format!("drain: {}", dr));
-                                }
-                                if let Some(g) = s.gpudevice.as_ref().and_then(json_value_to_inline)
-                                {
-                                    let g = g.trim();
-                                    if !g.is_empty() {
-                                        ui.label(format!("gpudevice: {}", g));
-                                    }
-                                }
-                                if let Some(net) = s
-                                    .network_address
-                                    .as_deref()
-                                    .map(str::trim)
-                                    .filter(|v| !v.is_empty())
-                                {
-                                    ui.label(format!("network_address: {}", net));
-                                }
-                                if let Some(t) = s
-                                    .r#type
-                                    .as_deref()
-                                    .map(str::trim)
-                                    .filter(|v| !v.is_empty())
-                                {
-                                    ui.label(format!("type: {}", t));
-                                }
-                                if let Some(cpu) = s
-                                    .cputype
-                                    .as_deref()
-                                    .map(str::trim)
-                                    .filter(|v| !v.is_empty())
-                                {
-                                    ui.label(format!("cputype: {}", cpu));
-                                }
-                                if let Some(model) = s
-                                    .nodemodel
-                                    .as_deref()
-                                    .map(str::trim)
-                                    .filter(|v| !v.is_empty())
-                                {
-                                    ui.label(format!("nodemodel: {}", model));
-                                }
-                            }
+                            let host_jobs = groups
+                                .get(&row.cluster)
+                                .and_then(|hosts| hosts.get(&row.host_full))
+                                .map(|indices| resolve_jobs(jobs, indices))
+                                .unwrap_or_default();
+                            host_tooltip(ui, &row.host_full, app, options, visible_range, &host_jobs)
                         },
                     );
                 }
@@ -977,6 +1612,11 @@ pub(super) fn paint_aggregated_jobs_level_2<'a>(
         }
     }
 
+    options.frame_stats.groups_level_1 += painted_groups;
+    if hidden_groups > 0 {
+        cursor_y = paint_show_more_groups(info, options, cursor_y, hidden_groups);
+    }
+
     cursor_y
 }
 
@@ -994,44 +1634,83 @@ fn paint_job(
     top_y: f32,
     details_window: &mut Vec<JobDetailsWindow>,
     all_cluster: &Vec<Cluster>,
+    strata_by_host: &HashMap<String, Strata>,
     state: ResourceState,
-    _aggregation_height: f32,
+    bar_height: f32,
     resource_label_for_state_tooltip: Option<&str>,
 ) -> PaintResult {
-    let theme_colors = get_theme_colors(&info.ctx.style());
+    // `all_resources` n'est qu'un fond : la hachure d'état de la ligne, sans barre, survol,
+    // sélection ni fenêtre de détails
+    if job.id == 0 {
+        paint_resource_state_hatch(
+            info,
+            options,
+            top_y,
+            options.rect_height,
+            state,
+            resource_label_for_state_tooltip,
+        );
+        return PaintResult::Painted;
+    }
+
     let chart_clip_rect = Rect::from_min_max(
         pos2(info.canvas.min.x + info.gutter_width, info.canvas.min.y),
         pos2(info.canvas.max.x, info.canvas.max.y),
     );
     let chart_painter = info.painter.with_clip_rect(chart_clip_rect);
-    let start_x = info.point_from_s(options, job.scheduled_start);
-    let stop_time = if job.stop_time > 0 {
-        job.stop_time
-    } else {
-        job.scheduled_start + job.walltime
-    };
+    let (start_s, stop_time) = options.time_basis.job_span(job);
+    let start_x = info.point_from_s(options, start_s);
     let end_x = info.point_from_s(options, stop_time);
     let width = end_x - start_x;
 
-    if width < options.cull_width {
+    // Walltime réservé mais non consommé (job terminé avant la fin de sa réservation) : le
+    // contour compte pour l'élagage, il reste visible quand la fin réelle est hors de la vue
+    let reserved_end_s = start_s + job.walltime;
+    let reserved_end_x = (options.show_reserved_vs_actual && job.stop_time > 0 && reserved_end_s > job.stop_time)
+        .then(|| info.point_from_s(options, reserved_end_s));
+    if reserved_end_x.map_or(width, |x| width.max(x - start_x)) < options.cull_width {
         return PaintResult::Culled;
     }
 
-    let height = options.rect_height;
+    let height = bar_height;
     let rounding = options.rounding;
 
     let rect = Rect::from_min_size(pos2(start_x, top_y), egui::vec2(width.max(options.min_width), height));
+    let reserved_rect = reserved_end_x.map(|x| Rect::from_min_max(rect.min, pos2(x.max(rect.max.x), rect.max.y)));
+
+    let (hovered_color, normal_color) = if options.job_color.is_random() {
+        job.get_gantt_color()
+    } else {
+        job.state.get_color()
+    };
 
     let visible_rect = rect.intersect(chart_clip_rect);
     if visible_rect.is_negative() {
-        return PaintResult::Culled;
+        // Seul le walltime réservé est dans la vue : contour sans barre ni interaction
+        return match reserved_rect.filter(|reserved| !reserved.intersect(chart_clip_rect).is_negative()) {
+            Some(reserved) => {
+                paint_reserved_walltime(&chart_painter, reserved, rounding, normal_color);
+                PaintResult::Painted
+            }
+            None => PaintResult::Culled,
+        };
     }
 
+    options.focusable_jobs.push((job.id, visible_rect));
+
     let is_job_trully_hovered = info
         .response
         .hover_pos()
         .map_or(false, |mouse_pos| visible_rect.contains(mouse_pos));
 
+    if options.selected_job_id == Some(job.id) {
+        let selection_color = get_theme_colors(&info.ctx.style()).text;
+        chart_painter.rect_stroke(visible_rect.expand(2.0), rounding, Stroke::new(2.0, selection_color));
+        if options.selected_job_rect.is_none() {
+            options.selected_job_rect = Some(visible_rect);
+        }
+    }
+
     let is_grid5000 = options.aggregate_by.level_1 == AggregateByLevel1Enum::Cluster
         && options.aggregate_by.level_2 == AggregateByLevel2Enum::Host;
     if is_grid5000 && is_job_trully_hovered {
@@ -1061,20 +1740,18 @@ fn paint_job(
     }
 
     if is_job_trully_hovered && info.response.secondary_clicked() {
-        let window = JobDetailsWindow::new(job.clone(), get_tree_structure_for_job(job, all_cluster));
-        if !details_window.iter().any(|w| w.job.id == job.id) {
-            details_window.push(window);
-        }
+        let window = JobDetailsWindow::new(
+            job.clone(),
+            get_tree_structure_for_job(job, all_cluster),
+            strata_by_host,
+        );
+        open_or_focus_job_details(details_window, window);
     }
 
     if is_job_trully_hovered && info.response.clicked() && !info.response.double_clicked() {
-        let job_duration_s = job.walltime as f64;
-        let job_start_s = job.scheduled_start as f64;
-        let job_end_s = if job.stop_time > 0 {
-            job.stop_time as f64
-        } else {
-            job_start_s + job_duration_s
-        };
+        options.selected_job_id = Some(job.id);
+        let job_start_s = start_s as f64;
+        let job_end_s = stop_time as f64;
         options.zoom_to_relative_s_range = Some((
             info.ctx.input(|i| i.time),
             (
@@ -1084,16 +1761,50 @@ fn paint_job(
         ));
     }
 
-    let (hovered_color, normal_color) = if options.job_color.is_random() {
-        job.get_gantt_color()
-    } else {
-        job.state.get_color()
-    };
-
     let fill_color = if is_job_hovered { hovered_color } else { normal_color };
 
+    if let Some(reserved) = reserved_rect {
+        paint_reserved_walltime(&chart_painter, reserved, rounding, normal_color);
+    }
+
+    // Attente en file : trait de la soumission jusqu'au début de la barre, avec une graduation
+    if options.show_submission && job.submission_time > 0 && job.submission_time < start_s {
+        let submission_x = info.point_from_s(options, job.submission_time);
+        let mid_y = rect.center().y;
+        let tick_half_height = (height * 0.3).max(2.0);
+        let wait_stroke = Stroke::new(1.0, normal_color.gamma_multiply(0.8));
+        chart_painter.line_segment([pos2(submission_x, mid_y), pos2(rect.min.x, mid_y)], wait_stroke);
+        chart_painter.line_segment(
+            [
+                pos2(submission_x, mid_y - tick_half_height),
+                pos2(submission_x, mid_y + tick_half_height),
+            ],
+            Stroke::new(1.5, normal_color),
+        );
+    }
+
     chart_painter.rect_filled(visible_rect, rounding, fill_color);
 
+    if options.show_progress && job.state == JobState::Running {
+        paint_job_progress(&chart_painter, job, rect, visible_rect, rounding);
+    }
+
+    // Aperçu de la recherche de commande : contour distinct de la sélection et des alertes
+    if options
+        .command_highlight
+        .as_deref()
+        .is_some_and(|query| command_contains(&job.command, query))
+    {
+        let highlight_stroke = Stroke::new(2.0, Color32::from_rgb(0, 200, 255));
+        chart_painter.rect_stroke(visible_rect.expand(1.5), rounding, highlight_stroke);
+    }
+
+    // Chevauchement avec un autre job sur une même ressource : contour d'alerte
+    if options.overlapping_jobs.contains(&job.id) {
+        let overlap_stroke = Stroke::new(2.0, Color32::from_rgb(255, 140, 0));
+        chart_painter.rect_stroke(visible_rect.shrink(1.0), rounding, overlap_stroke);
+    }
+
     if is_job_hovered {
         let hover_fill = Color32::from_rgba_unmultiplied(140, 140, 140, 60);
         let hover_stroke = Stroke::new(3.0, Color32::from_gray(80));
@@ -1101,73 +1812,318 @@ fn paint_job(
         chart_painter.rect_stroke(visible_rect.expand(1.0), rounding, hover_stroke);
     }
 
-    if state == ResourceState::Dead || state == ResourceState::Absent {
-        let hachure_color = match state {
-            ResourceState::Dead => Color32::from_rgba_premultiplied(255, 0, 0, 150),
-            ResourceState::Absent => theme_colors.hatch,
-            _ => Color32::TRANSPARENT,
-        };
+    paint_resource_state_hatch(
+        info,
+        options,
+        top_y,
+        height,
+        state,
+        resource_label_for_state_tooltip,
+    );
 
-        let hachure_spacing = 10.0;
-        let mut shapes = Vec::new();
-        let mut x = info.canvas.min.x;
-        let current_time_x = info.point_from_s(options, chrono::Utc::now().timestamp());
+    if options.show_queue_tag && !job.queue.is_empty() {
+        paint_queue_tag(&chart_painter, visible_rect, &job.queue);
+    }
 
-        let hatch_y = top_y;
+    if is_job_hovered {
+        PaintResult::Hovered
+    } else {
+        PaintResult::Painted
+    }
+}
 
-        let hover_rect = match state {
-            ResourceState::Dead => Rect::from_min_max(
-                pos2(info.canvas.min.x, hatch_y),
-                pos2(info.canvas.max.x, hatch_y + height),
-            ),
-            ResourceState::Absent => Rect::from_min_max(
-                pos2(info.canvas.min.x, hatch_y),
-                pos2(current_time_x, hatch_y + height),
-            ),
-            _ => Rect::from_min_max(pos2(0.0, 0.0), pos2(0.0, 0.0)),
-        };
+/// Avancement d'un job en cours : la part écoulée du walltime, `(maintenant - début) / walltime`
+/// bornée à [0, 1], assombrit le début de la barre `bar` et le reste prévu est éclairci.
+fn paint_job_progress(painter: &egui::Painter, job: &Job, bar: Rect, visible_rect: Rect, rounding: f32) {
+    if job.walltime <= 0 {
+        return;
+    }
+    let started_s = if job.start_time > 0 { job.start_time } else { job.scheduled_start };
+    let elapsed_s = chrono::Utc::now().timestamp() - started_s;
+    let fraction = (elapsed_s as f32 / job.walltime as f32).clamp(0.0, 1.0);
+    let split_x = bar.min.x + bar.width() * fraction;
+
+    let elapsed = Rect::from_min_max(bar.min, pos2(split_x, bar.max.y)).intersect(visible_rect);
+    let remaining = Rect::from_min_max(pos2(split_x, bar.min.y), bar.max).intersect(visible_rect);
+    // Coins arrondis seulement aux extrémités de la barre, pas à la séparation
+    let left = Rounding { nw: rounding, sw: rounding, ..Rounding::ZERO };
+    let right = Rounding { ne: rounding, se: rounding, ..Rounding::ZERO };
+    if elapsed.is_positive() {
+        painter.rect_filled(elapsed, left, Color32::from_black_alpha(70));
+    }
+    if remaining.is_positive() {
+        painter.rect_filled(remaining, right, Color32::from_white_alpha(50));
+    }
+}
 
-        let hover_rect = hover_rect.intersect(chart_clip_rect);
+/// Étiquette de la file du job (ex. « besteffort ») au bord gauche de la barre, découpée à la barre.
+/// Rien n'est dessiné si la barre est trop étroite pour contenir l'étiquette entière.
+fn paint_queue_tag(painter: &egui::Painter, bar: Rect, queue: &str) {
+    let padding = 2.0;
+    let font = FontId::proportional((bar.height() * 0.6).clamp(7.0, 10.0));
+    let galley = painter.layout_no_wrap(queue.to_string(), font, Color32::WHITE);
+    let tag_size = galley.size() + egui::vec2(padding * 2.0, 0.0);
+    if tag_size.x + padding * 2.0 > bar.width() {
+        return;
+    }
 
-        let is_hachure_hovered = info
-            .response
-            .hover_pos()
-            .map_or(false, |mouse_pos| hover_rect.contains(mouse_pos));
+    let tag_rect = Rect::from_min_size(pos2(bar.min.x + padding, bar.center().y - tag_size.y * 0.5), tag_size);
+    let tag_painter = painter.with_clip_rect(bar.intersect(painter.clip_rect()));
+    tag_painter.rect_filled(tag_rect, 2.0, Color32::from_black_alpha(110));
+    tag_painter.galley(tag_rect.min + egui::vec2(padding, 0.0), galley, Color32::WHITE);
+}
 
-        let final_hachure_color = if is_hachure_hovered {
-            hachure_color.gamma_multiply(1.5)
-        } else {
-            hachure_color
-        };
+/// Walltime réservé d'un job terminé avant la fin de sa réservation : fond léger et contour,
+/// de son début à la fin de la réservation.
+fn paint_reserved_walltime(painter: &egui::Painter, reserved_rect: Rect, rounding: f32, color: Color32) {
+    painter.rect_filled(reserved_rect, rounding, color.gamma_multiply(0.2));
+    painter.rect_stroke(reserved_rect, rounding, Stroke::new(1.0, color.gamma_multiply(0.7)));
+}
 
-        while x < info.canvas.max.x {
-            if state == ResourceState::Absent && x >= current_time_x {
-                break;
-            }
-            shapes.push(Shape::line_segment(
-                [pos2(x, hatch_y), pos2(x + hachure_spacing, hatch_y + height)],
-                Stroke::new(2.0, final_hachure_color),
-            ));
+/// Rend chaque barre dessinée (`options.focusable_jobs`) focalisable, dans l'ordre de début des
+/// jobs : la barre du job choisi par Tab / Maj+Tab prend le focus, le job focalisé est
+/// sélectionné, ramené dans la vue et entouré, et Entrée ouvre sa fenêtre de détails. Seuls les
+/// jobs de la vue sont concernés ; le sens `focusable_noninteractive` laisse les clics et le
+/// glisser au canvas. Renvoie le job dont la barre vient de prendre le focus, à centrer.
+pub(super) fn interact_with_focusable_jobs(
+    ui: &mut egui::Ui,
+    options: &mut Options,
+    app: &ApplicationContext,
+    details_window: &mut Vec<JobDetailsWindow>,
+) -> Option<u32> {
+    let focus_stroke = ui.visuals().selection.stroke;
+    let base_id = ui.id().with("gantt_job_focus");
+    let jobs_by_id: HashMap<u32, &Job> = app.filtered_jobs.iter().map(|job| (job.id, job)).collect();
+    // Un job peut occuper plusieurs lignes (un hôte par ligne) : une barre par occurrence
+    let mut occurrences: HashMap<u32, usize> = HashMap::new();
+    let mut bars: Vec<(&Job, Rect)> = std::mem::take(&mut options.focusable_jobs)
+        .into_iter()
+        .filter_map(|(job_id, rect)| jobs_by_id.get(&job_id).map(|job| (*job, rect)))
+        .collect();
+    bars.sort_by_key(|(job, _)| (job.scheduled_start, job.id));
+
+    // Demande devenue caduque si la sélection a changé avant que la barre soit dessinée
+    if options.focus_request_job_id != options.selected_job_id {
+        options.focus_request_job_id = None;
+    }
+    options.focused_job_bar = None;
+    let mut focused_job_id = None;
+    for (job, rect) in bars {
+        let occurrence = occurrences.entry(job.id).or_default();
+        let id = base_id.with((job.id, *occurrence));
+        *occurrence += 1;
+
+        let response = ui.interact(rect, id, egui::Sense::focusable_noninteractive());
+        response.widget_info(|| {
+            egui::WidgetInfo::labeled(
+                egui::WidgetType::Button,
+                true,
+                t!("app.gantt.job_focus_label", id = job.id, owner = job.owner, state = job.state.get_label()),
+            )
+        });
+        if options.focus_request_job_id == Some(job.id) {
+            options.focus_request_job_id = None;
+            response.request_focus();
+        }
+        if !response.has_focus() {
+            continue;
+        }
+
+        options.focused_job_bar = Some(id);
+        if response.gained_focus() {
+            options.selected_job_id = Some(job.id);
+            options.scroll_to_rect = Some(rect);
+            focused_job_id = Some(job.id);
+        }
+        ui.painter().rect_stroke(rect.expand(3.0), options.rounding, focus_stroke);
+
+        if ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+            let window = JobDetailsWindow::new(
+                job.clone(),
+                get_tree_structure_for_job(job, &app.all_clusters),
+                &app.strata_by_host,
+            );
+            open_or_focus_job_details(details_window, window);
+        }
+    }
+    focused_job_id
+}
+
+/// Largeur (px) de la hachure d'état limitée à l'instantané, juste avant la ligne « maintenant »
+const SNAPSHOT_HATCH_WIDTH: f32 = 40.0;
+
+/// Hachure des ressources `Dead` / `Absent`.
+///
+/// L'état vient du dernier instantané des ressources (OAR ne fournit pas d'historique) :
+/// hachurer tout le passé est une approximation. Sans `state_hatch_full_past`, la hachure
+/// est réduite à une bande juste avant « maintenant » (et au futur pour `Dead`).
+fn paint_resource_state_hatch(
+    info: &Info,
+    options: &mut Options,
+    top_y: f32,
+    height: f32,
+    state: ResourceState,
+    resource_label_for_state_tooltip: Option<&str>,
+) {
+    if state != ResourceState::Dead && state != ResourceState::Absent {
+        return;
+    }
+
+    let theme_colors = get_theme_colors(&info.ctx.style());
+    let chart_clip_rect = Rect::from_min_max(
+        pos2(info.canvas.min.x + info.gutter_width, info.canvas.min.y),
+        pos2(info.canvas.max.x, info.canvas.max.y),
+    );
+    let chart_painter = info.painter.with_clip_rect(chart_clip_rect);
+
+    let hachure_color = match state {
+        ResourceState::Dead => Color32::from_rgba_premultiplied(255, 0, 0, 150),
+        ResourceState::Absent => theme_colors.hatch,
+        _ => Color32::TRANSPARENT,
+    };
+
+    let hachure_spacing = 10.0;
+    let mut shapes = Vec::new();
+    let mut x = info.canvas.min.x;
+    let current_time_x = info.point_from_s(options, chrono::Utc::now().timestamp());
+
+    let hatch_y = top_y;
+    let hatch_start_x = if options.state_hatch_full_past {
+        info.canvas.min.x
+    } else {
+        current_time_x - SNAPSHOT_HATCH_WIDTH
+    };
+
+    let hover_rect = match state {
+        ResourceState::Dead => Rect::from_min_max(
+            pos2(hatch_start_x, hatch_y),
+            pos2(info.canvas.max.x, hatch_y + height),
+        ),
+        ResourceState::Absent => Rect::from_min_max(
+            pos2(hatch_start_x, hatch_y),
+            pos2(current_time_x, hatch_y + height),
+        ),
+        _ => Rect::from_min_max(pos2(0.0, 0.0), pos2(0.0, 0.0)),
+    };
+
+    let hover_rect = hover_rect.intersect(chart_clip_rect);
+
+    let is_hachure_hovered = info
+        .response
+        .hover_pos()
+        .map_or(false, |mouse_pos| hover_rect.contains(mouse_pos));
+
+    let final_hachure_color = if is_hachure_hovered {
+        hachure_color.gamma_multiply(1.5)
+    } else {
+        hachure_color
+    };
+
+    while x < info.canvas.max.x {
+        if state == ResourceState::Absent && x >= current_time_x {
+            break;
+        }
+        // Le motif reste calé sur le bord du canvas, seuls les traits avant le début sont omis
+        if x + hachure_spacing < hatch_start_x {
             x += hachure_spacing;
+            continue;
         }
+        shapes.push(Shape::line_segment(
+            [pos2(x, hatch_y), pos2(x + hachure_spacing, hatch_y + height)],
+            Stroke::new(2.0, final_hachure_color),
+        ));
+        x += hachure_spacing;
+    }
 
-        chart_painter.extend(shapes);
+    chart_painter
+        .with_clip_rect(chart_clip_rect.intersect(Rect::everything_right_of(hatch_start_x)))
+        .extend(shapes);
 
-        if is_hachure_hovered {
-            options.current_hovered_resource_state = Some(state.clone());
-            if let Some(label) = resource_label_for_state_tooltip {
-                if !label.trim().is_empty() {
-                    options.current_hovered_resource_label = Some(label.to_string());
-                }
+    if is_hachure_hovered {
+        options.current_hovered_resource_state = Some(state.clone());
+        if let Some(label) = resource_label_for_state_tooltip {
+            if !label.trim().is_empty() {
+                options.current_hovered_resource_label = Some(label.to_string());
             }
         }
     }
+}
 
-    if is_job_hovered {
-        PaintResult::Hovered
-    } else {
-        PaintResult::Painted
+/// Ligne « Afficher N de plus… » sous le dernier groupe dessiné quand `max_groups` en masque :
+/// un clic relève le plafond de `max_groups` groupes. Renvoie le curseur vertical suivant.
+fn paint_show_more_groups(info: &Info, options: &mut Options, cursor_y: f32, hidden_groups: usize) -> f32 {
+    let theme_colors = get_theme_colors(&info.ctx.style());
+    let step = options.max_groups.min(hidden_groups);
+    let text = t!("app.gantt.show_more_groups", count = step, hidden = hidden_groups).to_string();
+    let galley = info
+        .ctx
+        .fonts(|f| f.layout_no_wrap(text, info.font_id.clone(), theme_colors.text));
+    let rect = Rect::from_min_size(pos2(info.canvas.min.x + 6.0, cursor_y + 4.0), galley.size());
+
+    let is_hovered = info.response.hover_pos().is_some_and(|mouse_pos| rect.contains(mouse_pos));
+    if is_hovered {
+        info.ctx.set_cursor_icon(CursorIcon::PointingHand);
+        if info.response.clicked() {
+            options.extra_groups = options.extra_groups.saturating_add(options.max_groups);
+        }
+    }
+    let color = if is_hovered { theme_colors.text } else { theme_colors.text_dim };
+    info.painter.rect_filled(rect.expand(2.0), 2.0, theme_colors.background_timeline);
+    info.painter.galley(rect.min, galley, color);
+    if is_hovered {
+        info.painter.line_segment(
+            [pos2(rect.min.x, rect.max.y), rect.max],
+            Stroke::new(1.0, color),
+        );
+    }
+
+    rect.max.y + 8.0
+}
+
+/// Applique l'action choisie pour un clic sur un libellé de la gouttière : replier / déplier le
+/// groupe, ou demander un filtre sur le propriétaire ou l'hôte, ou un recadrage sur l'étendue
+/// des jobs du groupe (appliqués par `GanttChart`).
+fn handle_gutter_click(options: &mut Options, collapsed: &mut bool, filter: Option<GutterFilter>, group_jobs: &[&Job]) {
+    match options.gutter_click_action.action {
+        GutterClickActionEnum::None => {}
+        GutterClickActionEnum::Filter => {
+            if filter.is_some() {
+                options.gutter_filter = filter;
+            }
+        }
+        GutterClickActionEnum::Collapse => *collapsed = !*collapsed,
+        GutterClickActionEnum::ZoomToJobs => {
+            let time_basis = &options.time_basis;
+            options.gutter_zoom = group_jobs
+                .iter()
+                .filter(|job| job.id != 0)
+                .map(|job| time_basis.job_span(job))
+                .reduce(|(start_a, end_a), (start_b, end_b)| (start_a.min(start_b), end_a.max(end_b)));
+        }
+    }
+}
+
+/// Tronque `text` avec « … » pour qu'il tienne dans `max_width` px avec `font`.
+/// Renvoie le texte affiché et vrai s'il a été tronqué (le texte complet va alors au tooltip).
+fn elide_to_width(ctx: &egui::Context, text: &str, font: &FontId, max_width: f32) -> (String, bool) {
+    let width_of = |s: String| ctx.fonts(|f| f.layout_no_wrap(s, font.clone(), Color32::WHITE).size().x);
+    if width_of(text.to_string()) <= max_width {
+        return (text.to_string(), false);
+    }
+
+    // Plus long préfixe qui tient avec l'ellipse (recherche dichotomique sur les caractères)
+    let chars: Vec<char> = text.chars().collect();
+    let (mut low, mut high) = (0, chars.len());
+    while low < high {
+        let mid = (low + high).div_ceil(2);
+        let candidate: String = chars[..mid].iter().collect::<String>() + "…";
+        if width_of(candidate) <= max_width {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
     }
+    (chars[..low].iter().collect::<String>() + "…", true)
 }
 
 fn paint_job_info(
@@ -1181,7 +2137,9 @@ fn paint_job_info(
     compact: bool,
     label_meta: Option<LabelMeta>,
     app: &ApplicationContext,
-) {
+    options: &Options,
+    host_jobs: &[&Job],
+) -> bool {
     let theme_colors = get_theme_colors(&info.ctx.style());
     let gutter_painter = info.painter.clone();
 
@@ -1189,16 +2147,19 @@ fn paint_job_info(
     let selection_stroke = visuals.selection.stroke.color;
     let _selection_fill = visuals.selection.bg_fill;
 
-    *collapsed = false;
+    // Groupe replié par un clic (voir `handle_gutter_click`) : signalé par une flèche
+    let collapse_prefix = if *collapsed { "▶ " } else { "" };
 
     if let Some(meta) = label_meta {
         let host_full = match meta.host.as_deref() {
             Some(h) if !h.trim().is_empty() => h,
-            _ => return,
+            _ => return false,
         };
 
         let indent = if level == 1 { 0.0 } else { 8.0 };
-        let extra_pad = if compact { 2.0 } else { 10.0 };
+        // En compact, le badge a la hauteur de la ligne (voir `row_bar_top`) pour ne pas
+        // déborder sur les lignes voisines
+        let extra_pad = if compact { 0.0 } else { 10.0 };
         let bar_height = bar_height_hint.max(info.text_height + extra_pad);
         let top = pos.y - bar_height * 0.5;
         let left = info.canvas.min.x + 2.0 + indent;
@@ -1238,10 +2199,15 @@ fn paint_job_info(
         };
         gutter_painter.rect(rect, 0.0, label_bg, border);
 
-        let label_text = short_host_label(host_full);
-
         let label_x = left + 6.0;
         let label_font = FontId::proportional((info.font_id.size - 1.0).max(11.0));
+        // Le tooltip d'hôte donne déjà le nom complet : pas besoin de le signaler ici
+        let (label_text, _) = elide_to_width(
+            &info.ctx,
+            &format!("{}{}", collapse_prefix, short_host_label(host_full)),
+            &label_font,
+            rect.max.x - label_x - 6.0,
+        );
         let label_pos = pos2(label_x, rect.center().y);
         gutter_painter.text(
             label_pos,
@@ -1272,110 +2238,26 @@ fn paint_job_info(
                 layer_id,
                 Id::new(format!("gantt-label-host-{}-{}", info_label, level)),
                 |ui: &mut egui::Ui| {
-                    ui.label(format!("host: {}", host_full));
-
-                    let key_full = host_full.trim();
-                    let key_short = short_host_label(key_full);
-                    let strata = app
-                        .strata_by_host
-                        .get(key_full)
-                        .or_else(|| app.strata_by_host.get(&key_short));
-
-                    let derived_cluster = key_short.split('-').next().unwrap_or("").trim();
-                    let cluster_line = strata
-                        .and_then(|s| s.cluster.as_deref())
-                        .map(str::trim)
-                        .filter(|v| !v.is_empty())
-                        .or_else(|| if !derived_cluster.is_empty() { Some(derived_cluster) } else { None });
-                    if let Some(cluster) = cluster_line {
-                        ui.label(format!("cluster: {}", cluster));
-                    }
-
-                    let site_from_host = host_full.split('.').nth(1).unwrap_or("").trim();
-                    let site_from_net = strata
-                        .and_then(|s| s.network_address.as_deref())
-                        .and_then(|net| net.split('.').nth(1))
-                        .unwrap_or("")
-                        .trim();
-                    let site = if !site_from_host.is_empty() {
-                        site_from_host
-                    } else {
-                        site_from_net
-                    };
-                    if !site.is_empty() {
-                        ui.label(format!("site: {}", site));
-                    }
-
-                    if let Some(s) = strata {
-                        if let Some(b) =
-                            s.besteffort.as_deref().map(str::trim).filter(|v| !v.is_empty())
-                        {
-                            ui.label(format!("besteffort: {}", b));
-                        }
-                        if let Some(net) = s
-                            .network_address
-                            .as_deref()
-                            .map(str::trim)
-                            .filter(|v| !v.is_empty())
-                        {
-                            ui.label(format!("network_address: {}", net));
-                        }
-                        if let Some(comment) =
-                            s.comment.as_deref().map(str::trim).filter(|v| !v.is_empty())
-                        {
-                            ui.label(format!("comment: {}", comment));
-                        }
-                        if let Some(cpuset) = cpuset_like_grid5000(s) {
-                            let cpuset = cpuset.trim();
-                            if !cpuset.is_empty() {
-                                ui.label(format!("cpuset: {}", cpuset));
-                            }
-                        }
-                        if let Some(dep) =
-                            s.deploy.as_deref().map(str::trim).filter(|v| !v.is_empty())
-                        {
-                            ui.label(format!("deploy: {}", dep));
-                        }
-                        if let Some(dr) = s.drain.as_deref().map(str::trim).filter(|v| !v.is_empty()) {
-                            ui.label(format!("drain: {}", dr));
-                        }
-                        if let Some(g) = s.gpudevice.as_ref().and_then(json_value_to_inline) {
-                            let g = g.trim();
-                            if !g.is_empty() {
-                                ui.label(format!("gpudevice: {}", g));
-                            }
-                        }
-                        if let Some(t) =
-                            s.r#type.as_deref().map(str::trim).filter(|v| !v.is_empty())
-                        {
-                            ui.label(format!("type: {}", t));
-                        }
-                        if let Some(cpu) =
-                            s.cputype.as_deref().map(str::trim).filter(|v| !v.is_empty())
-                        {
-                            ui.label(format!("cputype: {}", cpu));
-                        }
-                        if let Some(model) =
-                            s.nodemodel.as_deref().map(str::trim).filter(|v| !v.is_empty())
-                        {
-                            ui.label(format!("nodemodel: {}", model));
-                        }
-                    }
+                    host_tooltip(ui, host_full, app, options, info.visible_range_s(options), host_jobs)
                 },
             );
         }
 
-        return;
+        return is_hovered && info.response.clicked();
     }
 
-    let label = info_label.to_string();
+    let x = pos.x.max(info.canvas.min.x + 6.0);
+    let (label, elided) = elide_to_width(
+        &info.ctx,
+        &format!("{}{}", collapse_prefix, info_label),
+        &info.font_id,
+        info.canvas.min.x + gutter_width - x - 6.0,
+    );
 
     let galley = info
         .ctx
         .fonts(|f| f.layout_no_wrap(label, info.font_id.clone(), theme_colors.text_dim));
 
-    let x = pos.x.max(info.canvas.min.x + 6.0);
-
     let top_left = pos2(x, pos.y - galley.size().y * 0.5);
     let rect = Rect::from_min_size(top_left, galley.size());
 
@@ -1407,4 +2289,16 @@ fn paint_job_info(
         gutter_painter.rect(badge, rounding, bg, Stroke::new(1.0, theme_colors.line));
     }
     gutter_painter.galley(rect.min, galley, text_color);
+
+    if elided && is_hovered {
+        let layer_id = LayerId::new(Order::Tooltip, Id::new("gantt-label-layer"));
+        egui::containers::popup::show_tooltip(
+            &info.ctx,
+            layer_id,
+            Id::new(format!("gantt-label-full-{}-{}", info_label, level)),
+            |ui: &mut egui::Ui| tooltip_body(ui, info_label),
+        );
+    }
+
+    is_hovered && info.response.clicked()
 }
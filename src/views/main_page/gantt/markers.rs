@@ -0,0 +1,41 @@
+// Repères nommés (« début maintenance », « incident »…) placés à des instants choisis.
+// Ils sont conservés dans `markers.json`, dans le répertoire de travail, comme les presets.
+
+use egui::Color32;
+
+pub(super) const MARKERS_FILE: &str = "markers.json";
+
+/// Couleurs attribuées tour à tour aux nouveaux repères.
+const MARKER_COLORS: [Color32; 4] = [
+    Color32::from_rgb(230, 140, 0),
+    Color32::from_rgb(0, 120, 220),
+    Color32::from_rgb(150, 60, 200),
+    Color32::from_rgb(0, 150, 90),
+];
+
+pub(super) fn next_marker_color(markers: &[(i64, String, Color32)]) -> Color32 {
+    MARKER_COLORS[markers.len() % MARKER_COLORS.len()]
+}
+
+/// Charge les repères, en retournant une liste vide en cas d'erreur.
+pub(super) fn load_markers(file_path: &str) -> Vec<(i64, String, Color32)> {
+    let stored: Vec<(i64, String, [u8; 4])> = match std::fs::read_to_string(file_path) {
+        Ok(contents) => serde_json::from_str(&contents).unwrap_or_default(),
+        Err(_) => Vec::new(),
+    };
+    stored
+        .into_iter()
+        .map(|(time_s, label, [r, g, b, a])| (time_s, label, Color32::from_rgba_unmultiplied(r, g, b, a)))
+        .collect()
+}
+
+/// Enregistre les repères (les erreurs d'écriture sont ignorées, comme pour les presets).
+pub(super) fn save_markers(file_path: &str, markers: &[(i64, String, Color32)]) {
+    let stored: Vec<(i64, &str, [u8; 4])> = markers
+        .iter()
+        .map(|(time_s, label, color)| (*time_s, label.as_str(), color.to_srgba_unmultiplied()))
+        .collect();
+    if let Ok(json) = serde_json::to_string(&stored) {
+        let _ = std::fs::write(file_path, json);
+    }
+}
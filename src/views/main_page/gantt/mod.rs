@@ -1,3 +1,4 @@
+mod aggregation;
 mod canvas;
 mod interaction;
 mod jobs;
@@ -5,26 +6,37 @@ mod labels;
 mod theme;
 mod timeline;
 mod types;
+mod view_state;
 mod energy_plot;
 mod energy_estimate;
+mod summary;
+mod export;
+mod export_png;
+mod export_svg;
+mod markers;
+mod stats;
 
 use crate::models::data_structure::resource::ResourceState;
-use crate::models::utils::utils::{get_all_clusters, get_all_hosts, get_all_resources};
+use crate::models::utils::date_converter::{format_duration, format_timestamp};
+use crate::models::utils::utils::{get_all_clusters, get_all_hosts, get_all_resources, lock_or_recover};
 use crate::views::view::View;
 use crate::{
     models::data_structure::{
         application_context::ApplicationContext,
+        filters::JobFilters,
         job::{Job, JobState},
     },
     views::components::{
         gantt_aggregate_by::{AggregateByLevel1Enum, AggregateByLevel2Enum},
-        job_details::JobDetailsWindow,
+        gantt_gutter_click::GutterFilter,
+        job_details::{close_job_details_on_escape, JobDetailsWindow},
     },
 };
 use chrono::{Local, TimeZone};
 use eframe::egui;
 use egui::{Color32, FontId, Frame, RichText, ScrollArea, Sense, Shape, TextStyle};
-use std::collections::BTreeMap;
+use egui_plot::Corner;
+use std::collections::{BTreeMap, HashMap};
 
 use crate::models::data_structure::application_context::ClusterPreset;
 use std::collections::HashSet as StdHashSet; // to avoid confusion with earlier import
@@ -35,8 +47,16 @@ enum AdminMode {
     Modify,
 }
 
+use self::aggregation::AggregationCache;
+use self::energy_estimate::EnergySeriesCache;
+use self::energy_plot::EnergyPlotNavigation;
+use self::export::{ExportFormat, ExportSettings, MAX_EXPORT_SIDE_PX};
+use self::summary::{PeakConcurrencyCache, UsageSortEnum, VisibleSummaryCache};
+use self::view_state::ViewState;
+#[cfg(feature = "debug-tools")]
+use crate::models::utils::synthetic::SyntheticConfig;
 use self::types::{gutter_g5k_total_w, Info, Options, GUTTER_WIDTH};
-use self::labels::short_host_label;
+use crate::models::utils::utils::short_host_label;
 
 fn compute_gutter_width(
     ctx: &egui::Context,
@@ -63,6 +83,14 @@ fn compute_gutter_width(
                 }
             }
         }
+        if options.show_all_hosts {
+            for host in app.all_clusters.iter().flat_map(|c| c.hosts.iter()) {
+                let host_short = short_host_label(&host.name);
+                if host_short.len() > max_host.len() {
+                    max_host = host_short;
+                }
+            }
+        }
 
         let label_left_pad = 4.0;
         let label_right_pad = 4.0;
@@ -118,11 +146,22 @@ fn compute_gutter_width(
     text_w.clamp(min_w, 520.0)
 }
 
+/// Groupes repliés d'un mode d'agrégation : niveau 1, puis (niveau 1, niveau 2).
+type CollapsedGroups = (BTreeMap<String, bool>, BTreeMap<(String, String), bool>);
+
 pub struct GanttChart {
     options: Options,
     job_details_windows: Vec<JobDetailsWindow>,
     collapsed_jobs_level_1: BTreeMap<String, bool>,
     collapsed_jobs_level_2: BTreeMap<(String, String), bool>,
+    aggregation_cache: AggregationCache,
+    visible_summary: VisibleSummaryCache,
+    /// Résultat du dernier export CSV de la consommation (chemin, ou message d'erreur)
+    usage_export_status: Option<Result<String, String>>,
+    /// Tri du tableau des plus gros consommateurs (colonne, décroissant)
+    usage_sort: (UsageSortEnum, bool),
+    peak_concurrency: PeakConcurrencyCache,
+    energy_series: EnergySeriesCache,
     initial_start_s: Option<i64>,
     initial_end_s: Option<i64>,
 
@@ -133,6 +172,8 @@ pub struct GanttChart {
     last_canvas_usable_width_px: f32,
 
     last_aggregate_by: (AggregateByLevel1Enum, AggregateByLevel2Enum),
+    /// Groupes repliés des modes d'agrégation quittés, repris au retour sur chaque mode
+    collapsed_by_aggregation: HashMap<(AggregateByLevel1Enum, AggregateByLevel2Enum), CollapsedGroups>,
 
     // etat du panneau admin
     admin_panel_open: bool,
@@ -143,20 +184,64 @@ pub struct GanttChart {
     admin_selected_clusters: StdHashSet<String>,
 
     pending_navigation_refresh: bool,
+
+    // Partage de vue (voir `view_state.rs`)
+    view_state_input: String,
+    view_state_invalid: bool,
+
+    // Export PNG / SVG hors écran (voir `export.rs`)
+    export: ExportSettings,
+
+    // Menu d'ajout de repère ouvert par clic droit : position du menu et instant visé
+    marker_menu: Option<(egui::Pos2, i64)>,
+    marker_label_input: String,
+
+    // Menu ouvert au relâchement d'une sous-fenêtre tracée avec Maj + glisser
+    time_selection_menu: Option<egui::Pos2>,
+    // Plages précédentes (début, fin, zoom, pan), restaurées par « Fenêtre précédente »
+    window_filter_undo: Vec<(Option<i64>, Option<i64>, f32, f32)>,
+
+    // Dernière sélection échangée avec `app.selected_job_id`
+    synced_selected_job_id: Option<u32>,
+    // Preset et génération des clusters du job "all_resources" en place
+    background_job_key: Option<(Option<Vec<String>>, u64)>,
+    // Tab (true) / Maj+Tab (false) retiré des entrées brutes, traité au prochain rendu
+    pending_job_navigation: Option<bool>,
+
+    // Filtres du rendu précédent, pour détecter un changement (voir `Options::auto_fit_on_filter`)
+    last_job_filters: Option<JobFilters>,
+    // Recadrage sur les jobs du premier chargement pas encore fait (voir `Options::auto_fit_on_load`)
+    first_fit_pending: bool,
+
+    // Overlay de diagnostic des performances (F12, voir `stats.rs`)
+    show_stats_overlay: bool,
+
+    #[cfg(feature = "debug-tools")]
+    synthetic_config: SyntheticConfig,
 }
 
 impl Default for GanttChart {
     fn default() -> Self {
         GanttChart {
-            options: Default::default(),
+            options: Options {
+                markers: markers::load_markers(markers::MARKERS_FILE),
+                ..Default::default()
+            },
             job_details_windows: Vec::new(),
             collapsed_jobs_level_1: BTreeMap::new(),
             collapsed_jobs_level_2: BTreeMap::new(),
+            aggregation_cache: AggregationCache::default(),
+            visible_summary: VisibleSummaryCache::default(),
+            usage_export_status: None,
+            usage_sort: (UsageSortEnum::CoreHours, true),
+            peak_concurrency: PeakConcurrencyCache::default(),
+            energy_series: EnergySeriesCache::default(),
             initial_start_s: None,
             initial_end_s: None,
             last_canvas_usable_width_px: 1.0,
 
             last_aggregate_by: (AggregateByLevel1Enum::Cluster, AggregateByLevel2Enum::Host),
+            collapsed_by_aggregation: HashMap::new(),
 
             admin_panel_open: false,
             admin_mode: None,
@@ -167,11 +252,840 @@ impl Default for GanttChart {
             energy_filter_cluster: None,
             energy_filter_owner: None,
             pending_navigation_refresh: false,
+
+            view_state_input: String::new(),
+            export: ExportSettings::default(),
+            marker_menu: None,
+            time_selection_menu: None,
+            window_filter_undo: Vec::new(),
+            marker_label_input: String::new(),
+            synced_selected_job_id: None,
+            background_job_key: None,
+            pending_job_navigation: None,
+            last_job_filters: None,
+            first_fit_pending: true,
+            show_stats_overlay: false,
+            view_state_invalid: false,
+
+            #[cfg(feature = "debug-tools")]
+            synthetic_config: SyntheticConfig::default(),
         }
     }
 }
 
 impl GanttChart {
+    /// Plage de temps chargée dans le Gantt (`initial_start_s..initial_end_s`), une fois connue.
+    pub fn loaded_window(&self) -> Option<(i64, i64)> {
+        self.initial_start_s.zip(self.initial_end_s)
+    }
+
+    /// Fenêtre de temps visible [début, fin] (secondes), d'après le pan et le zoom courants.
+    ///
+    /// Inverse de `set_visible_window` : source unique pour la barre d'état, le cache de la
+    /// série énergie et l'état de vue partageable.
+    pub fn visible_window(&self) -> (i64, i64) {
+        let (start_s, end_s) = self.visible_range_s();
+        (start_s.round() as i64, end_s.round() as i64)
+    }
+
+    /// Fenêtre visible sans arrondi à la seconde, pour les vues liées au Gantt (graphe d'énergie).
+    fn visible_range_s(&self) -> (f64, f64) {
+        let start_s = self.initial_start_s.unwrap_or(0);
+        let usable_width = self.last_canvas_usable_width_px.max(1.0) as f64;
+        let width_s = self.options.canvas_width_s as f64;
+        let visible_start_s =
+            start_s as f64 - self.options.sideways_pan_in_points as f64 / usable_width * width_s;
+        (visible_start_s, visible_start_s + width_s)
+    }
+
+    /// Cadre le Gantt sur [start_s, end_s] et demande un rafraîchissement des données.
+    ///
+    /// Point d'entrée unique pour fixer la fenêtre visible (navigation, graphe d'énergie,
+    /// centrage sur un job, état de vue) : le zoom et le pan sont calculés sur la largeur
+    /// utile du dernier rendu, gouttière exclue, comme pour l'animation de zoom.
+    pub fn set_visible_window(&mut self, start_s: i64, end_s: i64) {
+        self.set_visible_range_s(start_s as f64, end_s as f64);
+    }
+
+    /// Variante de `set_visible_window` en secondes fractionnaires : un zoom continu (molette sur
+    /// le graphe d'énergie) n'est pas saccadé par l'arrondi quand la fenêtre ne fait que quelques secondes.
+    fn set_visible_range_s(&mut self, start_s: f64, end_s: f64) {
+        self.options.canvas_width_s = (end_s - start_s).max(1.0) as f32;
+        self.options.zoom_to_relative_s_range = None;
+
+        let initial_start_s = self.initial_start_s.map_or(start_s, |s| s as f64);
+        self.options.sideways_pan_in_points = interaction::pan_for_window_start(
+            start_s - initial_start_s,
+            self.options.canvas_width_s,
+            self.last_canvas_usable_width_px.max(1.0),
+        );
+        self.pending_navigation_refresh = true;
+    }
+
+    /// Durée visible affichée dans la barre d'outils ; le menu fixe une durée usuelle en gardant
+    /// le centre de la fenêtre, pour un zoom reproductible (captures, comparaisons).
+    fn render_span_menu(&mut self, ui: &mut egui::Ui) {
+        const SPANS: [(&str, i64); 8] = [
+            ("1m", 60),
+            ("5m", 5 * 60),
+            ("15m", 15 * 60),
+            ("1h", 60 * 60),
+            ("6h", 6 * 60 * 60),
+            ("1d", 24 * 60 * 60),
+            ("1w", 7 * 24 * 60 * 60),
+            ("30d", 30 * 24 * 60 * 60),
+        ];
+
+        let (start_s, end_s) = self.visible_window();
+        let span_label = t!("app.gantt.span", span = format_duration(end_s - start_s));
+        ui.menu_button(RichText::new(span_label).text_style(TextStyle::Small), |ui| {
+            ui.label(RichText::new(t!("app.gantt.span_hint")).weak());
+            ui.horizontal_wrapped(|ui| {
+                for (label, span_s) in SPANS {
+                    if ui.small_button(label).clicked() {
+                        let center_s = (start_s + end_s) / 2;
+                        self.set_visible_window(center_s - span_s / 2, center_s + span_s - span_s / 2);
+                        ui.close_menu();
+                    }
+                }
+            });
+        });
+    }
+
+    /// Heures-cœurs et heures-GPU consommées dans la fenêtre visible, puis le tableau repliable des
+    /// plus gros consommateurs (tri par colonne, export CSV dans le répertoire courant, export JSON
+    /// du résumé par groupe d'agrégation).
+    fn render_usage_row(&mut self, ui: &mut egui::Ui, app: &ApplicationContext) {
+        let usage = self.visible_summary.usage();
+        if usage.is_empty() {
+            return;
+        }
+        let core_hours: f64 = usage.iter().map(|u| u.core_hours).sum();
+        let gpu_hours: f64 = usage.iter().map(|u| u.gpu_hours).sum();
+        let (start_s, end_s) = self.visible_window();
+        ui.label(
+            RichText::new(t!(
+                "app.gantt.usage.total",
+                core_hours = format!("{:.1}", core_hours),
+                gpu_hours = format!("{:.1}", gpu_hours)
+            ))
+            .small(),
+        );
+
+        egui::CollapsingHeader::new(RichText::new(t!("app.gantt.usage.leaderboard")).small())
+            .id_salt("gantt_usage_leaderboard")
+            .show(ui, |ui| {
+                let (sort, descending) = &mut self.usage_sort;
+                let sorted = sort.sorted(self.visible_summary.usage(), *descending);
+                let export_status = &mut self.usage_export_status;
+                let options = &self.options;
+
+                egui::Grid::new("gantt_usage_leaderboard_grid")
+                    .striped(true)
+                    .num_columns(UsageSortEnum::ALL.len())
+                    .show(ui, |ui| {
+                        for column in UsageSortEnum::ALL {
+                            let arrow = match (*sort == column, *descending) {
+                                (true, true) => " ⏷",
+                                (true, false) => " ⏶",
+                                (false, _) => "",
+                            };
+                            if ui.small_button(format!("{}{}", column.label(), arrow)).clicked() {
+                                if *sort == column {
+                                    *descending = !*descending;
+                                } else {
+                                    *sort = column;
+                                    *descending = column != UsageSortEnum::Owner;
+                                }
+                            }
+                        }
+                        ui.end_row();
+
+                        for owner_usage in sorted.iter() {
+                            ui.label(&owner_usage.owner);
+                            ui.label(owner_usage.jobs.to_string());
+                            ui.label(format!("{:.1}", owner_usage.core_hours));
+                            ui.label(format!("{:.1}", owner_usage.gpu_hours));
+                            ui.label(format!("{:.2}", owner_usage.energy_kwh));
+                            ui.end_row();
+                        }
+                    });
+
+                ui.horizontal_wrapped(|ui| {
+                    let export_path = |prefix: &str, extension: &str| {
+                        let format = "%Y%m%d-%H%M";
+                        format!(
+                            "{}_{}_{}.{}",
+                            prefix,
+                            Local.timestamp_opt(start_s, 0).unwrap().format(format),
+                            Local.timestamp_opt(end_s, 0).unwrap().format(format),
+                            extension
+                        )
+                    };
+                    let mut export = |path: String, contents: String| {
+                        *export_status = Some(
+                            std::fs::write(&path, contents)
+                                .map(|_| path)
+                                .map_err(|error| error.to_string()),
+                        );
+                    };
+                    if ui.small_button(t!("app.gantt.usage.export_csv")).clicked() {
+                        export(export_path("usage", "csv"), summary::usage_csv(&sorted));
+                    }
+                    if ui.small_button(t!("app.gantt.usage.export_json")).clicked() {
+                        export(
+                            export_path("aggregation", "json"),
+                            summary::aggregation_summary_json(app, options, (start_s, end_s)),
+                        );
+                    }
+                    match export_status {
+                        Some(Ok(path)) => {
+                            ui.label(RichText::new(t!("app.gantt.export.saved", path = path)).small());
+                        }
+                        Some(Err(error)) => {
+                            ui.colored_label(Color32::RED, t!("app.gantt.export.failed", error = error));
+                        }
+                        None => {}
+                    }
+                });
+            });
+    }
+
+    /// Décale la fenêtre visible de `delta_s` secondes sans changer le zoom.
+    fn shift_visible_window(&mut self, delta_s: i64) {
+        let (start_s, _) = self.visible_window();
+        let width_s = self.options.canvas_width_s.round() as i64;
+        self.set_visible_window(start_s + delta_s, start_s + delta_s + width_s);
+    }
+
+    /// Cadre la fenêtre visible sur l'étendue des jobs filtrés (hors `all_resources`), avec une
+    /// marge de 5 % de chaque côté. Sans job, la vue est laissée telle quelle.
+    fn fit_to_jobs(&mut self, app: &ApplicationContext) {
+        let extent = app
+            .filtered_jobs
+            .iter()
+            .filter(|job| job.id != 0)
+            .map(|job| self.options.time_basis.job_span(job))
+            .reduce(|(start_a, end_a), (start_b, end_b)| (start_a.min(start_b), end_a.max(end_b)));
+        if let Some((start_s, end_s)) = extent {
+            self.fit_to_extent(start_s, end_s);
+        }
+    }
+
+    /// Garde la fenêtre de départ au premier chargement de données, sans recadrage sur les jobs :
+    /// pour une fenêtre demandée explicitement au lancement.
+    pub fn keep_initial_window(&mut self) {
+        self.first_fit_pending = false;
+    }
+
+    /// Ajuste la fenêtre visible sur [start_s, end_s], avec une marge de 5 % (au moins une minute).
+    fn fit_to_extent(&mut self, start_s: i64, end_s: i64) {
+        let margin_s = ((end_s - start_s) / 20).max(60);
+        self.set_visible_window(start_s - margin_s, end_s + margin_s);
+    }
+
+    /// À un changement de mode d'agrégation, range les groupes repliés du mode quitté et reprend
+    /// ceux du nouveau mode (vides s'il n'a jamais été affiché), puis oublie les survols.
+    fn switch_aggregation_state(&mut self) {
+        let current = (self.options.aggregate_by.level_1, self.options.aggregate_by.level_2);
+        if current == self.last_aggregate_by {
+            return;
+        }
+        let left = (
+            std::mem::take(&mut self.collapsed_jobs_level_1),
+            std::mem::take(&mut self.collapsed_jobs_level_2),
+        );
+        self.collapsed_by_aggregation.insert(self.last_aggregate_by, left);
+        (self.collapsed_jobs_level_1, self.collapsed_jobs_level_2) =
+            self.collapsed_by_aggregation.remove(&current).unwrap_or_default();
+        self.last_aggregate_by = current;
+
+        // Les fenêtres de détails (une par job) ne dépendent pas de l'agrégation : elles restent ouvertes
+        self.options.current_hovered_job = None;
+        self.options.previous_hovered_job = None;
+        self.options.current_hovered_resource_state = None;
+        self.options.current_hovered_resource_label = None;
+    }
+
+    /// Replie (ou déplie) d'un coup tous les groupes de l'agrégation courante, sur les deux niveaux.
+    fn set_all_collapsed(&mut self, collapsed: bool) {
+        let (level_1_keys, level_2_keys) = self.aggregation_cache.group_keys();
+        for key in level_1_keys {
+            self.collapsed_jobs_level_1.insert(key, collapsed);
+        }
+        for key in level_2_keys {
+            self.collapsed_jobs_level_2.insert(key, collapsed);
+        }
+    }
+
+    /// Centre un job en gardant le zoom, élargi si le job ne tient pas dans la vue.
+    fn center_on_job(&mut self, job: &Job) {
+        let (start_s, end_s) = self.visible_window();
+        let (job_start_s, job_end_s) = self.options.time_basis.job_span(job);
+        let duration_s = (job_end_s - job_start_s).max(1);
+        let width_s = (end_s - start_s).max(duration_s + duration_s / 5);
+        let center_s = job_start_s + duration_s / 2;
+        self.set_visible_window(center_s - width_s / 2, center_s + width_s / 2);
+    }
+
+    /// Retire Tab / Maj+Tab des entrées brutes, avant que egui ne les utilise pour déplacer le
+    /// focus entre widgets, et les garde pour `handle_job_keyboard_navigation`.
+    /// Sans effet lorsqu'un autre widget qu'une barre de job (champ texte…) a le focus clavier.
+    pub fn intercept_job_navigation_keys(&mut self, ctx: &egui::Context, raw_input: &mut egui::RawInput) {
+        let focused = ctx.memory(|mem| mem.focused());
+        if focused.is_some() && focused != self.options.focused_job_bar {
+            return;
+        }
+        raw_input.events.retain(|event| match event {
+            egui::Event::Key { key: egui::Key::Tab, pressed, modifiers, .. }
+                if modifiers.is_none() || modifiers.matches_exact(egui::Modifiers::SHIFT) =>
+            {
+                if *pressed {
+                    self.pending_job_navigation = Some(!modifiers.shift);
+                }
+                false
+            }
+            _ => true,
+        });
+    }
+
+    /// Tab / Maj+Tab : sélectionne le job suivant / précédent (ordre de début) parmi ceux qui
+    /// coupent la fenêtre visible, et le centre.
+    fn handle_job_keyboard_navigation(&mut self, app: &ApplicationContext) {
+        let Some(next) = self.pending_job_navigation.take() else {
+            return;
+        };
+        let (start_s, end_s) = self.visible_window();
+        let mut jobs: Vec<&Job> = app
+            .filtered_jobs
+            .iter()
+            .filter(|job| job.id != 0)
+            .filter(|job| {
+                let (job_start_s, job_end_s) = self.options.time_basis.job_span(job);
+                job_start_s < end_s && job_end_s > start_s
+            })
+            .collect();
+        if jobs.is_empty() {
+            return;
+        }
+        jobs.sort_by_key(|job| (job.scheduled_start, job.id));
+
+        let current = self
+            .options
+            .selected_job_id
+            .and_then(|id| jobs.iter().position(|job| job.id == id));
+        let index = match (current, next) {
+            (Some(i), true) => (i + 1) % jobs.len(),
+            (Some(i), false) => (i + jobs.len() - 1) % jobs.len(),
+            (None, true) => 0,
+            (None, false) => jobs.len() - 1,
+        };
+        let job = jobs[index];
+        self.options.selected_job_id = Some(job.id);
+        self.options.focus_request_job_id = Some(job.id);
+        self.center_on_job(job);
+    }
+
+    /// Job "all_resources" (id 0) : toutes les ressources du preset sélectionné, dessinées en fond.
+    fn background_job(app: &ApplicationContext, selected_cluster_names: Option<Vec<String>>) -> Job {
+        let all_hosts = if let Some(cluster_names) = &selected_cluster_names {
+            app.all_clusters.iter()
+                .filter(|c| cluster_names.contains(&c.name))
+                .flat_map(|c| get_all_hosts(&vec![c.clone()]))
+                .collect()
+        } else {
+            get_all_hosts(&app.all_clusters)
+        };
+
+        let all_clusters = if let Some(cluster_names) = &selected_cluster_names {
+            cluster_names.clone()
+        } else {
+            get_all_clusters(&app.all_clusters)
+        };
+
+        let all_resources = if let Some(cluster_names) = &selected_cluster_names {
+            app.all_clusters.iter()
+                .filter(|c| cluster_names.contains(&c.name))
+                .flat_map(|c| get_all_resources(&vec![c.clone()]))
+                .collect()
+        } else {
+            get_all_resources(&app.all_clusters)
+        };
+
+        Job {
+            id: 0,
+            owner: "all_resources".to_string(),
+            state: JobState::Unknown,
+            scheduled_start: 0,
+            walltime: 0,
+            hosts: all_hosts,
+            clusters: all_clusters,
+            command: String::new(),
+            message: None,
+            queue: String::new(),
+            assigned_resources: all_resources,
+            submission_time: 0,
+            start_time: 0,
+            stop_time: 0,
+            exit_code: None,
+            gantt_color: egui::Color32::TRANSPARENT,
+            main_resource_state: ResourceState::Unknown,
+        }
+    }
+
+    /// Synchronise la sélection du Gantt avec celle partagée dans `ApplicationContext`.
+    /// Une sélection faite ailleurs (tableau des jobs) est reprise et centrée ;
+    /// une sélection faite dans le Gantt (clic, Tab) est publiée pour le tableau.
+    fn sync_selected_job(&mut self, app: &mut ApplicationContext) {
+        if app.selected_job_id != self.synced_selected_job_id {
+            self.options.selected_job_id = app.selected_job_id;
+            if let Some(job) = app
+                .selected_job_id
+                .and_then(|id| app.filtered_jobs.iter().find(|job| job.id == id))
+            {
+                self.center_on_job(job);
+            }
+        } else if self.options.selected_job_id != self.synced_selected_job_id {
+            app.selected_job_id = self.options.selected_job_id;
+        }
+        self.synced_selected_job_id = app.selected_job_id;
+    }
+
+    fn render_export_menu(&mut self, ui: &mut egui::Ui) {
+        let visible_range = self.visible_window();
+        let settings = &mut self.export;
+        if settings.start.is_empty() || settings.end.is_empty() {
+            settings.set_range(visible_range);
+        }
+
+        ui.menu_button(t!("app.gantt.export.title"), |ui| {
+            egui::Grid::new("gantt_export_grid").num_columns(2).show(ui, |ui| {
+                ui.label(t!("app.gantt.export.width"));
+                ui.add(
+                    egui::DragValue::new(&mut settings.width_px)
+                        .range(200..=MAX_EXPORT_SIDE_PX)
+                        .suffix(" px"),
+                );
+                ui.end_row();
+                ui.label(t!("app.gantt.export.start"));
+                ui.text_edit_singleline(&mut settings.start);
+                ui.end_row();
+                ui.label(t!("app.gantt.export.end"));
+                ui.text_edit_singleline(&mut settings.end);
+                ui.end_row();
+                ui.label(t!("app.gantt.export.path"));
+                ui.text_edit_singleline(&mut settings.path);
+                ui.end_row();
+            });
+            if ui.button(t!("app.gantt.export.use_visible")).clicked() {
+                settings.set_range(visible_range);
+            }
+
+            let range_valid = settings.range().is_some();
+            if !range_valid {
+                ui.colored_label(Color32::RED, t!("app.gantt.export.invalid_range"));
+            }
+            ui.horizontal(|ui| {
+                if ui
+                    .add_enabled(range_valid, egui::Button::new(t!("app.gantt.export.png")))
+                    .clicked()
+                {
+                    settings.requested = Some(ExportFormat::Png);
+                }
+                if ui
+                    .add_enabled(range_valid, egui::Button::new(t!("app.gantt.export.svg")))
+                    .clicked()
+                {
+                    settings.requested = Some(ExportFormat::Svg);
+                }
+            });
+
+            match &settings.status {
+                Some(Ok(path)) => {
+                    ui.label(t!("app.gantt.export.saved", path = path));
+                }
+                Some(Err(error)) => {
+                    ui.colored_label(Color32::RED, t!("app.gantt.export.failed", error = error));
+                }
+                None => {}
+            }
+        });
+    }
+
+    /// Peint le Gantt hors écran sur `[start_s, end_s]` avec une largeur de `width_px` et
+    /// retourne les formes egui obtenues ainsi que la taille de la figure (hauteur selon les lignes).
+    /// Les fonctions `paint_*` habituelles dessinent dans une couche cachée, vidée ensuite,
+    /// avec une réponse qui ne reçoit jamais la souris (pas de survol ni de clic).
+    fn paint_offscreen(
+        &mut self,
+        ui: &mut egui::Ui,
+        app: &ApplicationContext,
+        (start_s, end_s): (i64, i64),
+        width_px: u32,
+    ) -> (Vec<egui::epaint::ClippedShape>, [u32; 2]) {
+        let ctx = ui.ctx().clone();
+        let layer = egui::LayerId::new(egui::Order::Background, egui::Id::new("gantt_offscreen"));
+        let response = ui.interact(egui::Rect::NOTHING, ui.id().with("gantt_offscreen"), Sense::hover());
+        let base_font = TextStyle::Body.resolve(ui.style());
+        let gutter_width = compute_gutter_width(&ctx, &base_font, &self.options, app, &app.all_clusters);
+
+        // Fenêtre de temps de l'export, la vue à l'écran est rétablie ensuite
+        let saved_view = (
+            self.options.canvas_width_s,
+            self.options.sideways_pan_in_points,
+            self.options.zoom_to_relative_s_range.take(),
+            self.options.selected_job_id.take(),
+        );
+        self.options.canvas_width_s = (end_s - start_s) as f32;
+        self.options.sideways_pan_in_points = 0.0;
+
+        // 1re passe pour mesurer la hauteur, 2e passe sur un canvas de hauteur finie
+        let mut canvas = egui::Rect::from_min_max(egui::Pos2::ZERO, egui::pos2(width_px as f32, f32::INFINITY));
+        let mut shapes = Vec::new();
+        for _ in 0..2 {
+            let info = Info {
+                ctx: ctx.clone(),
+                canvas,
+                response: response.clone(),
+                painter: ctx.layer_painter(layer).with_clip_rect(canvas),
+                text_height: ui.text_style_height(&TextStyle::Body),
+                start_s,
+                stop_s: end_s,
+                font_id: base_font.clone(),
+                gutter_width,
+            };
+            let where_to_put_timeline = info.painter.add(Shape::Noop);
+            let max_y = canvas::ui_canvas(
+                &mut self.options,
+                app,
+                &info,
+                canvas.top(),
+                (start_s, end_s),
+                &mut Vec::new(),
+                &mut self.collapsed_jobs_level_1,
+                &mut self.collapsed_jobs_level_2,
+                &mut self.aggregation_cache,
+                &app.all_clusters,
+                gutter_width,
+            );
+            canvas.max.y = max_y.min(MAX_EXPORT_SIDE_PX as f32);
+
+            if canvas.max.y.is_finite() && info.canvas.max.y.is_finite() {
+                let timeline_shapes = timeline::paint_timeline(&info, canvas, &self.options, start_s, gutter_width);
+                info.painter.set(where_to_put_timeline, Shape::Vec(timeline_shapes));
+                info.painter.add(timeline::paint_current_time_line(&info, &self.options, canvas, gutter_width));
+                let label_y = canvas.top() + info.text_height + 7.0;
+                info.painter.extend(timeline::paint_markers(&info, &self.options, canvas, label_y, gutter_width));
+            }
+            shapes = ctx.graphics_mut(|layers| {
+                layers
+                    .get_mut(layer)
+                    .map(std::mem::take)
+                    .map(|list| list.all_entries().cloned().collect::<Vec<_>>())
+                    .unwrap_or_default()
+            });
+        }
+
+        self.options.canvas_width_s = saved_view.0;
+        self.options.sideways_pan_in_points = saved_view.1;
+        self.options.zoom_to_relative_s_range = saved_view.2;
+        self.options.selected_job_id = saved_view.3;
+
+        let height_px = canvas.height().ceil().max(1.0) as u32;
+        (shapes, [width_px, height_px])
+    }
+
+    /// Exporte la plage et la largeur choisies dans le menu d'export, au format demandé.
+    fn export(&mut self, ui: &mut egui::Ui, app: &ApplicationContext, format: ExportFormat) -> Result<String, String> {
+        let range = self
+            .export
+            .range()
+            .ok_or_else(|| t!("app.gantt.export.invalid_range").to_string())?;
+        let width_px = self.export.width_px.clamp(200, MAX_EXPORT_SIDE_PX);
+        let (shapes, [width_px, height_px]) = self.paint_offscreen(ui, app, range, width_px);
+        let background = ui.visuals().extreme_bg_color;
+        let path = self.export.path_for(format);
+
+        match format {
+            ExportFormat::Png => {
+                let primitives = ui.ctx().tessellate(shapes, 1.0);
+                let font_image = ui.ctx().fonts(|fonts| fonts.image());
+                let rgba = export_png::rasterize(
+                    &primitives,
+                    &font_image,
+                    [width_px as usize, height_px as usize],
+                    background,
+                );
+                export_png::write_png(&path, width_px, height_px, &rgba)?;
+            }
+            ExportFormat::Svg => {
+                let svg = export_svg::shapes_to_svg(&shapes, [width_px, height_px], background);
+                std::fs::write(&path, svg).map_err(|e| e.to_string())?;
+            }
+        }
+        Ok(format!("{} ({} × {} px)", path.display(), width_px, height_px))
+    }
+
+    /// Liste déroulante des propriétaires : en vue par propriétaire, fait défiler jusqu'à sa ligne ;
+    /// dans les autres vues, restreint le filtre des propriétaires à celui choisi.
+    fn render_owner_jump(&mut self, ui: &mut egui::Ui, app: &mut ApplicationContext) {
+        let mut chosen = None;
+        egui::ComboBox::from_id_salt("gantt_owner_jump")
+            .selected_text(t!("app.gantt.owner_jump"))
+            .height(400.0)
+            .show_ui(ui, |ui| {
+                for owner in app.get_unique_owners() {
+                    if ui.selectable_label(false, &owner).clicked() {
+                        chosen = Some(owner);
+                    }
+                }
+            });
+
+        if let Some(owner) = chosen {
+            if self.options.aggregate_by.level_1 == AggregateByLevel1Enum::Owner {
+                self.options.scroll_to_owner = Some(owner);
+            } else {
+                app.filters.set_owners(Some(vec![owner]));
+                app.filter_jobs();
+            }
+        }
+    }
+
+    /// Petit menu flottant d'ajout de repère, à l'endroit du clic droit.
+    fn show_marker_menu(&mut self, ctx: &egui::Context) {
+        let Some((pos, time_s)) = self.marker_menu else {
+            return;
+        };
+
+        let mut close = false;
+        let area = egui::Area::new(egui::Id::new("gantt_marker_menu"))
+            .order(egui::Order::Foreground)
+            .fixed_pos(pos)
+            .show(ctx, |ui| {
+                Frame::menu(ui.style()).show(ui, |ui| {
+                    ui.label(t!("app.gantt.markers.add_at", time = format_timestamp(time_s)));
+                    let response = ui.add(
+                        egui::TextEdit::singleline(&mut self.marker_label_input)
+                            .id(egui::Id::new("gantt_marker_label"))
+                            .hint_text(t!("app.gantt.markers.label_hint")),
+                    );
+                    let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                    ui.horizontal(|ui| {
+                        if ui.button(t!("app.gantt.markers.add")).clicked() || submitted {
+                            let color = markers::next_marker_color(&self.options.markers);
+                            let label = self.marker_label_input.trim().to_string();
+                            self.options.markers.push((time_s, label, color));
+                            self.options.markers.sort_by_key(|(time_s, _, _)| *time_s);
+                            markers::save_markers(markers::MARKERS_FILE, &self.options.markers);
+                            close = true;
+                        }
+                        if ui.button(t!("app.gantt.markers.cancel")).clicked() {
+                            close = true;
+                        }
+                    });
+                });
+            });
+
+        // Un clic ailleurs ou Échap referme le menu sans ajouter de repère
+        let clicked_outside = ctx.input(|i| {
+            i.pointer.any_pressed()
+                && i.pointer.interact_pos().is_some_and(|p| !area.response.rect.contains(p))
+        });
+        if close || clicked_outside || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+            self.marker_menu = None;
+        }
+    }
+
+    /// Menu de la sous-fenêtre tracée : restreindre le Gantt à cette fenêtre ou annuler.
+    fn show_time_selection_menu(&mut self, ctx: &egui::Context) {
+        let (Some(pos), Some((origin_s, current_s))) = (self.time_selection_menu, self.options.time_selection) else {
+            return;
+        };
+        let (start_s, end_s) = (origin_s.min(current_s), origin_s.max(current_s));
+
+        let mut close = false;
+        let area = egui::Area::new(egui::Id::new("gantt_time_selection_menu"))
+            .order(egui::Order::Foreground)
+            .fixed_pos(pos)
+            .show(ctx, |ui| {
+                Frame::menu(ui.style()).show(ui, |ui| {
+                    ui.label(t!(
+                        "app.gantt.time_selection.range",
+                        start = format_timestamp(start_s),
+                        end = format_timestamp(end_s),
+                        duration = format_duration(end_s - start_s)
+                    ));
+                    ui.horizontal(|ui| {
+                        if ui.button(t!("app.gantt.time_selection.filter")).clicked() {
+                            self.filter_to_window(start_s, end_s);
+                            close = true;
+                        }
+                        if ui.button(t!("app.gantt.time_selection.cancel")).clicked() {
+                            close = true;
+                        }
+                    });
+                });
+            });
+
+        // Un clic ailleurs ou Échap abandonne la sélection
+        let clicked_outside = ctx.input(|i| {
+            i.pointer.any_pressed()
+                && i.pointer.interact_pos().is_some_and(|p| !area.response.rect.contains(p))
+        });
+        if close || clicked_outside || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+            self.time_selection_menu = None;
+            self.options.time_selection = None;
+        }
+    }
+
+    /// Anime le zoom vers la plage de référence, élargie au besoin pour contenir l'heure courante
+    /// (avec une marge de 10 % de la plage, au moins une heure) : « maintenant » reste visible
+    /// même après navigation dans l'historique ou un filtrage sur une fenêtre passée.
+    fn jump_to_now(&mut self, animation_start_time: f64) {
+        let now_s = Local::now().timestamp();
+        let mut start_s = self.initial_start_s.unwrap_or(now_s);
+        let mut end_s = self.initial_end_s.unwrap_or(now_s);
+        let margin_s = ((end_s - start_s) / 10).max(3600);
+
+        if now_s >= end_s {
+            end_s = now_s + margin_s;
+        }
+        if now_s <= start_s {
+            start_s = now_s - margin_s;
+        }
+
+        // Le pan est relatif à initial_start_s : on le recale avant de changer l'origine
+        if let Some(old_start_s) = self.initial_start_s.filter(|&s| s != start_s) {
+            self.options.sideways_pan_in_points += interaction::pan_for_window_start(
+                (old_start_s - start_s) as f64,
+                self.options.canvas_width_s,
+                self.last_canvas_usable_width_px.max(1.0),
+            );
+        }
+        self.initial_start_s = Some(start_s);
+        self.initial_end_s = Some(end_s);
+
+        self.options.zoom_to_relative_s_range =
+            Some((animation_start_time, (0., (end_s - start_s) as f64)));
+        self.pending_navigation_refresh = true;
+    }
+
+    /// Restreint le Gantt à [start_s, end_s] : la plage de référence (double clic, « maintenant »)
+    /// et la fenêtre visible, donc les dates de l'application et les jobs filtrés au rafraîchissement
+    /// suivant. La plage précédente est empilée pour `undo_window_filter`.
+    fn filter_to_window(&mut self, start_s: i64, end_s: i64) {
+        self.window_filter_undo.push((
+            self.initial_start_s,
+            self.initial_end_s,
+            self.options.canvas_width_s,
+            self.options.sideways_pan_in_points,
+        ));
+        self.initial_start_s = Some(start_s);
+        self.initial_end_s = Some(end_s);
+        self.set_visible_window(start_s, end_s);
+    }
+
+    /// Revient à la plage d'avant le dernier « Filtrer sur cette fenêtre ».
+    fn undo_window_filter(&mut self) {
+        if let Some((initial_start_s, initial_end_s, canvas_width_s, pan)) = self.window_filter_undo.pop() {
+            self.initial_start_s = initial_start_s;
+            self.initial_end_s = initial_end_s;
+            self.options.canvas_width_s = canvas_width_s;
+            self.options.sideways_pan_in_points = pan;
+            self.options.zoom_to_relative_s_range = None;
+            self.pending_navigation_refresh = true;
+        }
+    }
+
+    /// Liste des repères dans les paramètres : libellé, couleur et suppression.
+    fn render_markers_settings(&mut self, ui: &mut egui::Ui) {
+        ui.collapsing(t!("app.gantt.markers.title"), |ui| {
+            if self.options.markers.is_empty() {
+                ui.label(RichText::new(t!("app.gantt.markers.empty")).weak());
+            }
+            let mut changed = false;
+            let mut removed = None;
+            for (index, (time_s, label, color)) in self.options.markers.iter_mut().enumerate() {
+                ui.horizontal(|ui| {
+                    changed |= ui.color_edit_button_srgba(color).changed();
+                    changed |= ui
+                        .add(egui::TextEdit::singleline(label).desired_width(140.0))
+                        .changed();
+                    ui.label(RichText::new(format_timestamp(*time_s)).small());
+                    if ui.small_button("🗑").clicked() {
+                        removed = Some(index);
+                    }
+                });
+            }
+            if let Some(index) = removed {
+                self.options.markers.remove(index);
+                changed = true;
+            }
+            if changed {
+                markers::save_markers(markers::MARKERS_FILE, &self.options.markers);
+            }
+        });
+    }
+
+    fn render_share_view_menu(&mut self, ui: &mut egui::Ui, app: &mut ApplicationContext) {
+        ui.menu_button(t!("app.gantt.share.title"), |ui| {
+            if ui.button(t!("app.gantt.share.copy")).clicked() {
+                ui.ctx().copy_text(ViewState::capture(self, app).encode());
+                ui.close_menu();
+            }
+            ui.separator();
+            ui.add(
+                egui::TextEdit::singleline(&mut self.view_state_input)
+                    .hint_text(t!("app.gantt.share.paste_hint")),
+            );
+            if ui.button(t!("app.gantt.share.restore")).clicked() {
+                match ViewState::decode(&self.view_state_input) {
+                    Some(state) => {
+                        state.restore(self, app);
+                        self.view_state_invalid = false;
+                        self.view_state_input.clear();
+                        ui.close_menu();
+                    }
+                    None => self.view_state_invalid = true,
+                }
+            }
+            if self.view_state_invalid {
+                ui.colored_label(Color32::RED, t!("app.gantt.share.invalid"));
+            }
+        });
+    }
+
+    /// Section de développement : remplace les données par un jeu synthétique.
+    #[cfg(feature = "debug-tools")]
+    fn render_synthetic_data_tools(&mut self, ui: &mut egui::Ui, app: &mut ApplicationContext) {
+        ui.collapsing("Debug: synthetic data", |ui| {
+            let config = &mut self.synthetic_config;
+            egui::Grid::new("synthetic_data_grid").show(ui, |ui| {
+                ui.label("Jobs");
+                ui.add(egui::DragValue::new(&mut config.jobs).range(1..=500_000));
+                ui.end_row();
+                ui.label("Clusters");
+                ui.add(egui::DragValue::new(&mut config.clusters).range(1..=200));
+                ui.end_row();
+                ui.label("Hosts / cluster");
+                ui.add(egui::DragValue::new(&mut config.hosts_per_cluster).range(1..=1_000));
+                ui.end_row();
+                ui.label("Cores / host");
+                ui.add(egui::DragValue::new(&mut config.cores_per_host).range(1..=256));
+                ui.end_row();
+            });
+            if ui.button("Generate").clicked() {
+                app.load_synthetic_data(config);
+            }
+        });
+    }
+
+    /// Nombre maximal de jobs simultanés dans la fenêtre visible (affiché dans l'en-tête).
+    pub fn visible_peak_concurrency(&mut self, app: &ApplicationContext) -> usize {
+        let visible_range = self.visible_window();
+        self.peak_concurrency.get(app, visible_range)
+    }
+
     pub fn render_compact_toolbar(&mut self, ui: &mut egui::Ui, app: &mut ApplicationContext) {
         // Initialise les bornes temporelles
         if self.initial_start_s.is_none() {
@@ -182,25 +1096,157 @@ impl GanttChart {
         ui.menu_button(t!("app.gantt.settings.title"), |ui| {
             ui.set_max_height(500.0);
 
-            let before = (self.options.aggregate_by.level_1, self.options.aggregate_by.level_2);
             self.options.aggregate_by.ui(ui);
-            let after = (self.options.aggregate_by.level_1, self.options.aggregate_by.level_2);
-            if after != before || after != self.last_aggregate_by {
-                self.last_aggregate_by = after;
-                self.collapsed_jobs_level_1.clear();
-                self.collapsed_jobs_level_2.clear();
-                self.job_details_windows.clear();
-                self.options.current_hovered_job = None;
-                self.options.previous_hovered_job = None;
-                self.options.current_hovered_resource_state = None;
-                self.options.current_hovered_resource_label = None;
-            }
+            self.switch_aggregation_state();
             ui.separator();
 
             // En vue Grid5000, on force le mode compact
             self.options.compact_rows = true;
 
             self.options.job_color.ui(ui);
+            self.options.host_sort.ui(ui);
+            self.options.time_basis.ui(ui);
+            self.options.zoom_anchor.ui(ui);
+            self.options.tooltip_mode.ui(ui);
+            self.options.gutter_click_action.ui(ui);
+            ui.menu_button(t!("app.gantt.settings.tooltip_fields"), |ui| {
+                self.options.strata_fields.ui(ui);
+            });
+            ui.checkbox(
+                &mut self.options.show_all_hosts,
+                t!("app.gantt.settings.show_all_hosts"),
+            );
+            ui.checkbox(
+                &mut self.options.hide_idle_rows,
+                t!("app.gantt.settings.hide_idle_rows"),
+            );
+            let mut include_orphan_jobs = app.include_orphan_jobs;
+            if ui
+                .checkbox(&mut include_orphan_jobs, t!("app.gantt.settings.include_orphan_jobs"))
+                .on_hover_text(t!("app.gantt.settings.include_orphan_jobs_hint"))
+                .changed()
+            {
+                app.set_include_orphan_jobs(include_orphan_jobs);
+            }
+            ui.checkbox(
+                &mut self.options.auto_fit_on_filter,
+                t!("app.gantt.settings.auto_fit_on_filter"),
+            );
+            ui.checkbox(
+                &mut self.options.auto_fit_on_load,
+                t!("app.gantt.settings.auto_fit_on_load"),
+            )
+            .on_hover_text(t!("app.gantt.settings.auto_fit_on_load_hint"));
+            ui.horizontal(|ui| {
+                ui.label(t!("app.gantt.settings.max_groups"));
+                let response = ui
+                    .add(egui::DragValue::new(&mut self.options.max_groups).range(0..=100_000).speed(1.0))
+                    .on_hover_text(t!("app.gantt.settings.max_groups_hint"));
+                if response.changed() {
+                    self.options.extra_groups = 0;
+                }
+            });
+            ui.checkbox(
+                &mut self.options.show_reserved_vs_actual,
+                t!("app.gantt.settings.show_reserved_vs_actual"),
+            );
+            ui.checkbox(
+                &mut self.options.show_progress,
+                t!("app.gantt.settings.show_progress"),
+            );
+            ui.checkbox(
+                &mut self.options.show_idle_gaps,
+                t!("app.gantt.settings.show_idle_gaps"),
+            );
+            ui.checkbox(
+                &mut self.options.stack_shared_jobs,
+                t!("app.gantt.settings.stack_shared_jobs"),
+            )
+            .on_hover_text(t!("app.gantt.settings.stack_shared_jobs_hint"));
+            ui.checkbox(
+                &mut self.options.show_idle,
+                t!("app.gantt.settings.show_idle"),
+            );
+            ui.checkbox(
+                &mut self.options.show_submission,
+                t!("app.gantt.settings.show_submission"),
+            );
+            ui.checkbox(
+                &mut self.options.show_queue_tag,
+                t!("app.gantt.settings.show_queue_tag"),
+            );
+            ui.checkbox(
+                &mut self.options.shade_future,
+                t!("app.gantt.settings.shade_future"),
+            );
+            ui.checkbox(
+                &mut self.options.show_owner_heat_strip,
+                t!("app.gantt.settings.show_owner_heat_strip"),
+            );
+            ui.checkbox(
+                &mut self.options.zebra_rows,
+                t!("app.gantt.settings.zebra_rows"),
+            );
+            ui.checkbox(
+                &mut self.options.state_hatch_full_past,
+                t!("app.gantt.settings.state_hatch_full_past"),
+            )
+            .on_hover_text(t!("app.gantt.settings.state_hatch_full_past_hint"));
+            ui.horizontal(|ui| {
+                ui.label(t!("app.gantt.settings.separators"));
+                ui.add(
+                    egui::DragValue::new(&mut self.options.separator_scale)
+                        .range(0.5..=4.0)
+                        .speed(0.05)
+                        .prefix("× "),
+                );
+                ui.checkbox(
+                    &mut self.options.dashed_level_2_separators,
+                    t!("app.gantt.settings.dashed_level_2_separators"),
+                );
+            });
+            ui.horizontal(|ui| {
+                ui.label(t!("app.gantt.settings.current_time_line"));
+                ui.color_edit_button_srgba(&mut self.options.current_time_color);
+                ui.add(
+                    egui::DragValue::new(&mut self.options.current_time_width)
+                        .range(0.5..=6.0)
+                        .speed(0.1)
+                        .suffix(" px"),
+                );
+            });
+            ui.horizontal(|ui| {
+                ui.label(t!("app.gantt.settings.energy_series"));
+                ui.add(
+                    egui::DragValue::new(&mut self.options.energy_step_s)
+                        .range(energy_estimate::MIN_ENERGY_STEP_S..=3600)
+                        .suffix(" s"),
+                )
+                .on_hover_text(t!("app.gantt.settings.energy_step_hint"));
+                ui.add(
+                    egui::DragValue::new(&mut self.options.energy_watts_per_unit)
+                        .range(1.0..=5000.0)
+                        .speed(5.0)
+                        .suffix(" W"),
+                )
+                .on_hover_text(t!("app.gantt.settings.energy_watts_hint"));
+            });
+            self.render_markers_settings(ui);
+
+            ui.separator();
+            if ui
+                .button(t!("app.gantt.settings.reset_defaults"))
+                .on_hover_text(t!("app.gantt.settings.reset_defaults_hint"))
+                .clicked()
+            {
+                self.options.reset_to_defaults();
+            }
+
+            #[cfg(feature = "debug-tools")]
+            {
+                ui.separator();
+                self.render_synthetic_data_tools(ui, app);
+            }
         });
 
         let is_admin = app.is_admin();
@@ -213,6 +1259,10 @@ impl GanttChart {
                 .stroke(egui::Stroke::new(1.0, Color32::from_gray(170)))
         };
 
+        self.render_share_view_menu(ui, app);
+        self.render_export_menu(ui);
+        self.render_owner_jump(ui, app);
+
         let response = ui.add(admin_button);
 
         if !is_admin {
@@ -228,50 +1278,71 @@ impl GanttChart {
         ui.add_space(6.0);
 
         // Navigation rapide dans la timeline
-        let base_font = TextStyle::Body.resolve(ui.style());
-        let gutter_width =
-            compute_gutter_width(ui.ctx(), &base_font, &self.options, app, &app.all_clusters);
-        let fallback_usable_width = (ui.available_width() - gutter_width).max(1.0);
-        let canvas_usable_width = if self.last_canvas_usable_width_px > 1.0 {
-            self.last_canvas_usable_width_px
-        } else {
-            fallback_usable_width
-        };
-        let points_per_second = canvas_usable_width / self.options.canvas_width_s;
         let day_delta_s: i64 = 24 * 60 * 60;
         let week_delta_s: i64 = 7 * day_delta_s;
 
+        self.render_span_menu(ui);
+
         ui.label(RichText::new("Nav:").text_style(TextStyle::Small));
         if ui.small_button("◀ 1w").clicked() {
-            self.options.sideways_pan_in_points += week_delta_s as f32 * points_per_second;
-            self.options.zoom_to_relative_s_range = None;
-            self.pending_navigation_refresh = true;
+            self.shift_visible_window(-week_delta_s);
         }
         if ui.small_button("◀ 1d").clicked() {
-            self.options.sideways_pan_in_points += day_delta_s as f32 * points_per_second;
-            self.options.zoom_to_relative_s_range = None;
-            self.pending_navigation_refresh = true;
+            self.shift_visible_window(-day_delta_s);
         }
         if ui.small_button("1d ▶").clicked() {
-            self.options.sideways_pan_in_points -= day_delta_s as f32 * points_per_second;
-            self.options.zoom_to_relative_s_range = None;
-            self.pending_navigation_refresh = true;
+            self.shift_visible_window(day_delta_s);
         }
         if ui.small_button("1w ▶").clicked() {
-            self.options.sideways_pan_in_points -= week_delta_s as f32 * points_per_second;
-            self.options.zoom_to_relative_s_range = None;
-            self.pending_navigation_refresh = true;
+            self.shift_visible_window(week_delta_s);
         }
 
         if ui.small_button(t!("app.gantt.now")).clicked() {
-            self.options.zoom_to_relative_s_range = Some((
-                ui.ctx().input(|i| i.time),
-                (
-                    0.,
-                    (self.initial_end_s.unwrap() - self.initial_start_s.unwrap()) as f64,
-                ),
-            ));
-            self.pending_navigation_refresh = true;
+            self.jump_to_now(ui.ctx().input(|i| i.time));
+        }
+
+        if !self.window_filter_undo.is_empty() && ui.small_button(t!("app.gantt.time_selection.undo")).clicked() {
+            self.undo_window_filter();
+        }
+
+        // Filtre d'hôte posé depuis la gouttière : il n'a pas d'équivalent dans le menu des filtres
+        if let Some(hosts) = app.filters.hosts.clone() {
+            if ui
+                .small_button(t!("app.gantt.clear_host_filter", hosts = hosts.join(", ")))
+                .clicked()
+            {
+                app.filters.set_hosts(None);
+                app.filter_jobs();
+            }
+        }
+
+        // Pas d'en-tête repliable en vue Cluster → Hôte (Grid5000) : un groupe replié n'y
+        // pourrait plus être déplié
+        let is_grid5000 = self.options.aggregate_by.level_1 == AggregateByLevel1Enum::Cluster
+            && self.options.aggregate_by.level_2 == AggregateByLevel2Enum::Host;
+        if !is_grid5000 {
+            ui.add_space(6.0);
+            if ui
+                .small_button(t!("app.gantt.collapse_all"))
+                .on_hover_text(t!("app.gantt.collapse_all_hint"))
+                .clicked()
+            {
+                self.set_all_collapsed(true);
+            }
+            if ui
+                .small_button(t!("app.gantt.expand_all"))
+                .on_hover_text(t!("app.gantt.expand_all_hint"))
+                .clicked()
+            {
+                self.set_all_collapsed(false);
+            }
+        }
+
+        if !self.job_details_windows.is_empty() {
+            ui.add_space(6.0);
+            if ui.small_button(t!("app.gantt.close_all_details")).clicked() {
+                self.job_details_windows.clear();
+            }
         }
     }
 }
@@ -283,56 +1354,60 @@ impl View for GanttChart {
             self.initial_start_s = Some(app.get_start_date().timestamp());
             self.initial_end_s = Some(app.get_end_date().timestamp());
         }
-        // On régénère toujours le job "all_resources" en fonction du preset sélectionné
-        app.all_jobs.retain(|j| j.id != 0);
+        self.options.command_highlight = app.command_preview.clone();
+        // Agrégation changée hors du menu (vue partagée restaurée…)
+        self.switch_aggregation_state();
+
+        // Filtre demandé par un clic sur un libellé de la gouttière à la frame précédente
+        if let Some(filter) = self.options.gutter_filter.take() {
+            match filter {
+                GutterFilter::Owner(owner) => app.filters.set_owners(Some(vec![owner])),
+                GutterFilter::Host(host) => app.filters.set_hosts(Some(vec![host])),
+            }
+            app.filter_jobs();
+        }
+        if let Some((start_s, end_s)) = self.options.gutter_zoom.take() {
+            self.fit_to_extent(start_s, end_s);
+        }
+
+        // Filtre modifié (hors fenêtre de temps) : recadrage optionnel sur les jobs restants
+        let filters_changed = self
+            .last_job_filters
+            .as_ref()
+            .is_some_and(|last| !last.same_selection(&app.filters));
+        if filters_changed && self.options.auto_fit_on_filter {
+            self.fit_to_jobs(app);
+        }
+        self.last_job_filters = Some(app.filters.clone());
+
+        // Premier chargement de données : la fenêtre par défaut (maintenant ± 1 h) peut ne montrer
+        // aucun job, on la recadre une fois sur leur étendue. On attend un premier rendu pour que
+        // le pan soit calculé sur la vraie largeur du canvas.
+        if self.first_fit_pending
+            && self.last_canvas_usable_width_px > 1.0
+            && app.filtered_jobs.iter().any(|job| job.id != 0)
+        {
+            self.first_fit_pending = false;
+            if self.options.auto_fit_on_load {
+                self.fit_to_jobs(app);
+            }
+        }
+
+        self.handle_job_keyboard_navigation(app);
+        self.sync_selected_job(app);
 
         let selected_cluster_names: Option<Vec<String>> = app.filters.selected_preset.as_ref()
             .and_then(|preset_name| app.cluster_presets.iter().find(|p| p.name == *preset_name))
             .map(|preset| preset.clusters.clone());
 
-        let all_hosts = if let Some(cluster_names) = &selected_cluster_names {
-            app.all_clusters.iter()
-                .filter(|c| cluster_names.contains(&c.name))
-                .flat_map(|c| get_all_hosts(&vec![c.clone()]))
-                .collect()
-        } else {
-            get_all_hosts(&app.all_clusters)
-        };
-
-        let all_clusters = if let Some(cluster_names) = &selected_cluster_names {
-            cluster_names.clone()
-        } else {
-            get_all_clusters(&app.all_clusters)
-        };
-
-        let all_resources = if let Some(cluster_names) = &selected_cluster_names {
-            app.all_clusters.iter()
-                .filter(|c| cluster_names.contains(&c.name))
-                .flat_map(|c| get_all_resources(&vec![c.clone()]))
-                .collect()
-        } else {
-            get_all_resources(&app.all_clusters)
-        };
-
-        app.all_jobs.push(Job {
-            id: 0,
-            owner: "all_resources".to_string(),
-            state: JobState::Unknown,
-            scheduled_start: 0,
-            walltime: 0,
-            hosts: all_hosts,
-            clusters: all_clusters,
-            command: String::new(),
-            message: None,
-            queue: String::new(),
-            assigned_resources: all_resources,
-            submission_time: 0,
-            start_time: 0,
-            stop_time: 0,
-            exit_code: None,
-            gantt_color: egui::Color32::TRANSPARENT,
-            main_resource_state: ResourceState::Unknown,
-        });
+        // Le job "all_resources" n'est régénéré que si le preset ou les clusters ont changé
+        let background_job_key = (selected_cluster_names.clone(), app.clusters_generation);
+        if self.background_job_key.as_ref() != Some(&background_job_key)
+            || !app.all_jobs.iter().any(|job| job.id == 0)
+        {
+            self.background_job_key = Some(background_job_key);
+            app.replace_background_job(Self::background_job(app, selected_cluster_names));
+        }
 
 
         // Panneau d’administration pour gérer les presets de clusters
@@ -452,17 +1527,21 @@ impl View for GanttChart {
             self.admin_panel_open = open;
         }
 
+        if let Some(format) = self.export.requested.take() {
+            self.export.status = Some(self.export(ui, app, format));
+        }
+
         let mut visible_range: Option<(i64, i64)> = None;
-        let mut energy_points: Vec<(i64, f64)> = Vec::new();
-        let mut last_gantt_usable_width_px: f32 = 1.0;
         let mut last_gantt_gutter_width_px: f32 = GUTTER_WIDTH;
 
         let plot_h = 270.0;
-        let sep_h = 12.0;
+        // séparateur + ligne de résumé textuel
+        let sep_h = 12.0 + ui.text_style_height(&TextStyle::Small) + 4.0;
 
         // réserve une hauteur pour le gantt = hauteur restante - plot
         let gantt_h = (ui.available_height() - plot_h - sep_h).max(100.0);
 
+        let gantt_render_start = std::time::Instant::now();
         ui.allocate_ui(egui::vec2(ui.available_width(), gantt_h), |ui| {
             Frame::canvas(ui.style()).show(ui, |ui| {
                 ui.visuals_mut().clip_rect_margin = 0.0;
@@ -479,9 +1558,11 @@ impl View for GanttChart {
                     let min_s = self.initial_start_s.unwrap();
                     let max_s = self.initial_end_s.unwrap();
 
+                    self.options.frame_stats.start_frame();
                     let base_font = TextStyle::Body.resolve(ui.style());
                     let gutter_width =
                         compute_gutter_width(ui.ctx(), &base_font, &self.options, app, &app.all_clusters);
+                    self.options.frame_stats.gutter_width_computes += 1;
 
                     let info = Info {
                         ctx: ui.ctx().clone(),
@@ -495,7 +1576,6 @@ impl View for GanttChart {
                         gutter_width,
                     };
 
-                    last_gantt_usable_width_px = info.usable_width();
                     self.last_canvas_usable_width_px = info.usable_width();
                     last_gantt_gutter_width_px = gutter_width;
 
@@ -512,10 +1592,27 @@ impl View for GanttChart {
                         &mut self.job_details_windows,
                         &mut self.collapsed_jobs_level_1,
                         &mut self.collapsed_jobs_level_2,
+                        &mut self.aggregation_cache,
                         &app.all_clusters,
                         gutter_width,
                     );
 
+                    let focused_job_id = jobs::interact_with_focusable_jobs(
+                        ui,
+                        &mut self.options,
+                        app,
+                        &mut self.job_details_windows,
+                    );
+                    if let Some(job) =
+                        focused_job_id.and_then(|id| app.filtered_jobs.iter().find(|job| job.id == id))
+                    {
+                        self.center_on_job(job);
+                    }
+
+                    if let Some(rect) = self.options.scroll_to_rect.take() {
+                        ui.scroll_to_rect(rect, Some(egui::Align::Center));
+                    }
+
                     let mut used_rect = canvas;
                     used_rect.max.y = max_y;
                     used_rect.max.y = used_rect.max.y.max(used_rect.min.y + available_height);
@@ -528,58 +1625,65 @@ impl View for GanttChart {
                         timeline::paint_current_time_line(&info, &self.options, used_rect, gutter_width);
                     info.painter.add(current_time_line);
 
+                    // Repères nommés, libellés juste sous la frise fixe du haut
+                    let marker_label_y = fixed_timeline_y + info.text_height + 7.0;
+                    let marker_shapes =
+                        timeline::paint_markers(&info, &self.options, used_rect, marker_label_y, gutter_width);
+                    info.painter.extend(marker_shapes);
+
+                    // Sous-fenêtre tracée avec Maj + glisser ; au relâchement, menu pour filtrer dessus
+                    if let Some((origin_s, current_s)) = self.options.time_selection {
+                        info.painter.extend(timeline::paint_time_selection(
+                            &info,
+                            &self.options,
+                            used_rect,
+                            marker_label_y,
+                            gutter_width,
+                            (origin_s, current_s),
+                        ));
+                        if info.response.drag_stopped() && self.time_selection_menu.is_none() {
+                            if origin_s == current_s {
+                                self.options.time_selection = None;
+                            } else {
+                                self.time_selection_menu = ui.input(|i| i.pointer.latest_pos());
+                            }
+                        }
+                    }
+
+                    // Clic droit hors job dans la zone du graphe : proposer un repère à cet instant
+                    // (le clic droit sur un job reste réservé à ses détails)
+                    if info.response.secondary_clicked() && self.options.previous_hovered_job.is_none() {
+                        if let Some(pos) = info.response.interact_pointer_pos() {
+                            if pos.x > info.canvas.min.x + gutter_width {
+                                self.marker_menu = Some((pos, info.s_from_point(&self.options, pos.x)));
+                                self.marker_label_input.clear();
+                                ui.memory_mut(|mem| mem.request_focus(egui::Id::new("gantt_marker_label")));
+                            }
+                        }
+                    }
+
                     ui.allocate_rect(used_rect, Sense::hover());
 
                     // calcul fenêtre visible + énergie
-                    let visible_start_s = info.start_s
-                        - ((self.options.sideways_pan_in_points / info.usable_width())
-                            * self.options.canvas_width_s) as i64;
-                    let visible_end_s = visible_start_s + self.options.canvas_width_s as i64;
+                    let (visible_start_s, visible_end_s) = self.visible_window();
 
                     visible_range = Some((visible_start_s, visible_end_s));
 
                     // Le graphe énergie à ses propres filtres (cluster / owner), distincts des filtres visuels du Gantt.
-                    let energy_jobs: Vec<Job> = app
-                        .filtered_jobs
-                        .iter()
-                        .filter(|job| {
-                            let cluster_ok = match &self.energy_filter_cluster {
-                                Some(cluster) => job.clusters.iter().any(|c| c == cluster),
-                                None => true,
-                            };
-
-                            let owner_ok = match &self.energy_filter_owner {
-                                Some(owner) => &job.owner == owner,
-                                None => true,
-                            };
-
-                            cluster_ok && owner_ok
-                        })
-                        .cloned()
-                        .collect();
-
-                    energy_points = energy_estimate::estimate_global_energy_series(
-                        &energy_jobs,
-                        visible_start_s,
-                        visible_end_s,
-                        10,
-                        300.0,
+                    self.energy_series.update(
+                        app,
+                        (self.energy_filter_cluster.as_ref(), self.energy_filter_owner.as_ref()),
+                        (visible_start_s, visible_end_s),
+                        self.options.energy_step_s,
+                        self.options.energy_watts_per_unit,
                     );
 
-                    let (mut mn, mut mx) = (f64::INFINITY, f64::NEG_INFINITY);
-                    for (_, w) in &energy_points {
-                        mn = mn.min(*w);
-                        mx = mx.max(*w);
-                    }
                     let start = Local.timestamp_opt(visible_start_s, 0).unwrap();
                     let end = Local.timestamp_opt(visible_end_s, 0).unwrap();
                     app.set_localdate(start, end);
 
                     if self.pending_navigation_refresh {
-                        let refreshing = *app
-                            .is_refreshing
-                            .lock()
-                            .unwrap_or_else(|poisoned| poisoned.into_inner());
+                        let refreshing = *lock_or_recover(&app.is_refreshing);
 
                         if !refreshing {
                             app.instant_update();
@@ -590,12 +1694,33 @@ impl View for GanttChart {
             });
         });
 
+        if ui.input_mut(|i| i.consume_key(egui::Modifiers::NONE, stats::STATS_OVERLAY_KEY)) {
+            self.show_stats_overlay = !self.show_stats_overlay;
+        }
+        if self.show_stats_overlay {
+            stats::show_stats_overlay(
+                ui.ctx(),
+                &self.options.frame_stats,
+                gantt_render_start.elapsed().as_secs_f64(),
+            );
+        }
+
+        self.show_marker_menu(ui.ctx());
+        self.show_time_selection_menu(ui.ctx());
+
         // zone plot FIXE en dessous : filtres du graphe + graphe énergie.
         ui.add_space(6.0);
         ui.separator();
         ui.add_space(2.0);
 
-        if let Some((vs, ve)) = visible_range {
+        // Résumé textuel de la fenêtre visible, exposé aux lecteurs d'écran via AccessKit
+        if let Some(range) = visible_range {
+            let now = ui.input(|i| i.time);
+            let summary = self.visible_summary.get(now, app, &self.options, range);
+            ui.add(egui::Label::new(RichText::new(summary).small().weak()).truncate());
+        }
+
+        if visible_range.is_some() {
             let now_s = Local::now().timestamp();
         
             ui.horizontal_wrapped(|ui| {
@@ -657,36 +1782,97 @@ impl View for GanttChart {
                     self.energy_filter_cluster = None;
                     self.energy_filter_owner = None;
                 }
+
+                ui.checkbox(&mut self.options.energy_lock_y_axis, t!("app.gantt.energy_lock_y_axis"))
+                    .on_hover_text(t!("app.gantt.energy_lock_y_axis_hint"));
+
+                // Légende (clic sur une série pour la masquer) : coin d'affichage ou masquée
+                let legend_label = |corner: Option<Corner>| match corner {
+                    Some(Corner::LeftTop) => t!("app.gantt.energy_legend_position.left_top"),
+                    Some(Corner::RightTop) => t!("app.gantt.energy_legend_position.right_top"),
+                    Some(Corner::LeftBottom) => t!("app.gantt.energy_legend_position.left_bottom"),
+                    Some(Corner::RightBottom) => t!("app.gantt.energy_legend_position.right_bottom"),
+                    None => t!("app.gantt.energy_legend_position.hidden"),
+                };
+                egui::ComboBox::from_id_salt("energy_legend")
+                    .selected_text(format!(
+                        "{}: {}",
+                        t!("app.gantt.energy_legend"),
+                        legend_label(self.options.energy_legend)
+                    ))
+                    .show_ui(ui, |ui| {
+                        for corner in std::iter::once(None).chain(Corner::all().map(Some)) {
+                            ui.selectable_value(&mut self.options.energy_legend, corner, legend_label(corner));
+                        }
+                    })
+                    .response
+                    .on_hover_text(t!("app.gantt.energy_legend_hint"));
+
+                // Budget de puissance (saisi en kW), tracé en ligne horizontale sur le graphe
+                let mut has_budget = self.options.power_budget_w.is_some();
+                if ui
+                    .checkbox(&mut has_budget, t!("app.gantt.energy_budget"))
+                    .on_hover_text(t!("app.gantt.energy_budget_hint"))
+                    .changed()
+                {
+                    self.options.power_budget_w = has_budget.then_some(50_000.0);
+                }
+                if let Some(budget_w) = &mut self.options.power_budget_w {
+                    let mut budget_kw = *budget_w / 1000.0;
+                    if ui
+                        .add(egui::DragValue::new(&mut budget_kw).range(0.1..=1_000_000.0).speed(1.0).suffix(" kW"))
+                        .changed()
+                    {
+                        *budget_w = budget_kw * 1000.0;
+                    }
+                }
+
+                // Fenêtre trop large pour le pas demandé : la série a été sous-échantillonnée
+                let energy_step_s = self.energy_series.step_s();
+                if energy_step_s > self.options.energy_step_s {
+                    ui.weak(t!("app.gantt.energy_resolution_reduced", step = energy_step_s));
+                }
             });
         
             ui.add_space(4.0);
         
-            let maybe_new_range = energy_plot::ui_energy_global(
+            let navigation = energy_plot::ui_energy_global(
                 ui,
-                &energy_points,
-                vs,
-                ve,
+                self.energy_series.points(),
+                self.visible_range_s(),
                 now_s,
                 last_gantt_gutter_width_px,
+                &mut self.options,
             );
         
             // Si l’utilisateur navigue dans le graphe, on resynchronise le Gantt
-            if let Some((new_vs, new_ve)) = maybe_new_range {
-                let new_width_s = (new_ve - new_vs).max(1) as f32;
-        
-                self.options.canvas_width_s = new_width_s;
-        
-                let start_s = self.initial_start_s.unwrap();
-                let canvas_w_px = last_gantt_usable_width_px.max(1.0);
-        
-                let pan_px =
-                    -(((new_vs - start_s) as f32) / self.options.canvas_width_s) * canvas_w_px;
-        
-                self.options.sideways_pan_in_points = pan_px;
-                self.pending_navigation_refresh = true;
+            match navigation {
+                Some(EnergyPlotNavigation::Window(new_vs, new_ve)) => self.set_visible_range_s(new_vs, new_ve),
+                Some(EnergyPlotNavigation::Reset) => {
+                    if let (Some(min_s), Some(max_s)) = (self.initial_start_s, self.initial_end_s) {
+                        // Même animation que le double clic sur le Gantt
+                        self.options.zoom_to_relative_s_range =
+                            Some((ui.input(|i| i.time), (0., (max_s - min_s) as f64)));
+                    }
+                }
+                None => {}
+            }
+
+            // Répartition par état des jobs visibles, reprise du résumé de la fenêtre visible
+            let state_counts = self.visible_summary.state_counts();
+            if !state_counts.is_empty() {
+                ui.horizontal_wrapped(|ui| {
+                    ui.label(RichText::new(t!("app.gantt.visible_states")).small());
+                    for (state, count) in state_counts {
+                        ui.label(RichText::new("■").color(state.get_color().0));
+                        ui.label(RichText::new(format!("{} {}", state.get_label(), count)).small());
+                    }
+                });
             }
+            self.render_usage_row(ui, app);
         }
 
+        close_job_details_on_escape(ui.ctx(), &mut self.job_details_windows);
         self.job_details_windows.retain(|w| w.is_open());
         for window in self.job_details_windows.iter_mut() {
             window.ui(ui);
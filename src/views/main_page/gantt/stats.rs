@@ -0,0 +1,71 @@
+// Compteurs de diagnostic des performances du Gantt, affichés dans un overlay basculé avec F12.
+// Ils sont accumulés pendant `ui_canvas` / `paint_aggregated_jobs_*` et remis à zéro à chaque image.
+
+use egui::{Align2, Context, Frame, Id, Order, RichText};
+
+/// Touche qui affiche / masque l'overlay de diagnostic.
+pub(super) const STATS_OVERLAY_KEY: egui::Key = egui::Key::F12;
+
+#[derive(Clone, Copy, Default)]
+pub struct FrameStats {
+    pub jobs_painted: usize,
+    pub jobs_culled: usize,
+    pub groups_level_1: usize,
+    pub groups_level_2: usize,
+    /// Nombre de calculs de la largeur de la gouttière, cumulé depuis le lancement
+    pub gutter_width_computes: usize,
+}
+
+impl FrameStats {
+    /// Remet à zéro les compteurs de l'image, en gardant les compteurs cumulés.
+    pub(super) fn start_frame(&mut self) {
+        *self = Self {
+            gutter_width_computes: self.gutter_width_computes,
+            ..Default::default()
+        };
+    }
+
+    pub(super) fn record_job(&mut self, painted: bool) {
+        if painted {
+            self.jobs_painted += 1;
+        } else {
+            self.jobs_culled += 1;
+        }
+    }
+}
+
+/// Overlay en bas à droite de la fenêtre : temps d'image, temps de dessin du Gantt et compteurs.
+pub(super) fn show_stats_overlay(ctx: &Context, stats: &FrameStats, gantt_render_s: f64) {
+    let frame_dt = ctx.input(|i| i.stable_dt).max(f32::EPSILON);
+    egui::Area::new(Id::new("gantt_stats_overlay"))
+        .order(Order::Foreground)
+        .anchor(Align2::RIGHT_BOTTOM, egui::vec2(-8.0, -26.0))
+        .interactable(false)
+        .show(ctx, |ui| {
+            Frame::popup(ui.style()).show(ui, |ui| {
+                ui.label(RichText::new(t!("app.gantt.stats.title")).strong());
+                let lines = [
+                    t!(
+                        "app.gantt.stats.frame",
+                        fps = format!("{:.0}", 1.0 / frame_dt),
+                        ms = format!("{:.1}", frame_dt * 1000.0)
+                    ),
+                    t!("app.gantt.stats.render", ms = format!("{:.1}", gantt_render_s * 1000.0)),
+                    t!(
+                        "app.gantt.stats.jobs",
+                        painted = stats.jobs_painted,
+                        culled = stats.jobs_culled
+                    ),
+                    t!(
+                        "app.gantt.stats.groups",
+                        level_1 = stats.groups_level_1,
+                        level_2 = stats.groups_level_2
+                    ),
+                    t!("app.gantt.stats.gutter", count = stats.gutter_width_computes),
+                ];
+                for line in lines {
+                    ui.label(RichText::new(line).monospace().small());
+                }
+            });
+        });
+}
@@ -0,0 +1,390 @@
+use super::aggregation::{build_aggregation, preset_clusters, Aggregation};
+use super::energy_estimate::energy_units;
+use super::types::Options;
+use crate::models::data_structure::application_context::ApplicationContext;
+use crate::models::data_structure::job::{Job, JobState};
+use crate::models::utils::date_converter::format_timestamp;
+use chrono::Local;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Fin effective d'un job : fin réelle, sinon fin prévue par le walltime.
+/// Un job démarré mais pas encore terminé court au moins jusqu'à `now_s`.
+fn job_end_s(job: &Job, now_s: i64) -> i64 {
+    if job.stop_time > 0 {
+        job.stop_time
+    } else if job.start_time > 0 {
+        (job.scheduled_start + job.walltime).max(now_s)
+    } else {
+        job.scheduled_start + job.walltime
+    }
+}
+
+/// Nombre maximal de jobs exécutés simultanément dans [start_s, end_s], par balayage
+/// des débuts / fins triés. À instant égal, les fins passent avant les débuts : deux jobs
+/// qui se suivent ne se chevauchent pas. Le job synthétique `all_resources` (id 0) est ignoré.
+pub(super) fn max_concurrency(jobs: &[Job], start_s: i64, end_s: i64) -> usize {
+    let now_s = Local::now().timestamp();
+    let mut events: Vec<(i64, i32)> = Vec::new();
+    for job in jobs.iter().filter(|job| job.id != 0) {
+        let job_start_s = job.scheduled_start.max(start_s);
+        let job_end_s = job_end_s(job, now_s).min(end_s);
+        if job_start_s < job_end_s {
+            events.push((job_start_s, 1));
+            events.push((job_end_s, -1));
+        }
+    }
+    events.sort_unstable();
+
+    let mut current = 0i32;
+    let mut peak = 0i32;
+    for (_, delta) in events {
+        current += delta;
+        peak = peak.max(current);
+    }
+    peak as usize
+}
+
+/// Jobs réels (hors `all_resources`) qui intersectent la fenêtre [start_s, end_s].
+fn visible_jobs(app: &ApplicationContext, (start_s, end_s): (i64, i64)) -> Vec<&Job> {
+    let now_s = Local::now().timestamp();
+    app.filtered_jobs
+        .iter()
+        .filter(|job| job.id != 0 && job.scheduled_start <= end_s && job_end_s(job, now_s) >= start_s)
+        .collect()
+}
+
+/// Nombre de jobs par état, dans l'ordre de `JobState`.
+fn count_states(visible: &[&Job]) -> Vec<(JobState, usize)> {
+    let mut states: BTreeMap<JobState, usize> = BTreeMap::new();
+    for job in visible.iter() {
+        *states.entry(job.state.clone()).or_default() += 1;
+    }
+    states.into_iter().collect()
+}
+
+/// Durée (heures) d'un job comptée dans la fenêtre [start_s, end_s], sans dépasser `now_s`.
+fn clipped_hours(job: &Job, (start_s, end_s): (i64, i64), now_s: i64) -> Option<f64> {
+    let clipped_start_s = job.scheduled_start.max(start_s);
+    let clipped_end_s = job_end_s(job, now_s).min(end_s).min(now_s);
+    (clipped_start_s < clipped_end_s).then(|| (clipped_end_s - clipped_start_s) as f64 / 3600.0)
+}
+
+/// Consommation d'un propriétaire sur la fenêtre visible.
+#[derive(Default)]
+pub(super) struct OwnerUsage {
+    pub(super) owner: String,
+    pub(super) jobs: usize,
+    pub(super) core_hours: f64,
+    pub(super) gpu_hours: f64,
+    pub(super) energy_kwh: f64,
+}
+
+/// Colonne de tri du tableau des plus gros consommateurs.
+#[derive(Clone, Copy, PartialEq)]
+pub(super) enum UsageSortEnum {
+    Owner,
+    Jobs,
+    CoreHours,
+    GpuHours,
+    Energy,
+}
+
+impl UsageSortEnum {
+    pub(super) const ALL: [UsageSortEnum; 5] = [
+        UsageSortEnum::Owner,
+        UsageSortEnum::Jobs,
+        UsageSortEnum::CoreHours,
+        UsageSortEnum::GpuHours,
+        UsageSortEnum::Energy,
+    ];
+
+    pub(super) fn label(&self) -> String {
+        match self {
+            UsageSortEnum::Owner => t!("app.gantt.usage.column_owner"),
+            UsageSortEnum::Jobs => t!("app.gantt.usage.column_jobs"),
+            UsageSortEnum::CoreHours => t!("app.gantt.usage.column_core_hours"),
+            UsageSortEnum::GpuHours => t!("app.gantt.usage.column_gpu_hours"),
+            UsageSortEnum::Energy => t!("app.gantt.usage.column_energy"),
+        }
+        .to_string()
+    }
+
+    /// Propriétaires triés selon la colonne ; à valeur égale, par nom pour un ordre stable.
+    pub(super) fn sorted<'a>(&self, usage: &'a [OwnerUsage], descending: bool) -> Vec<&'a OwnerUsage> {
+        let mut sorted: Vec<&OwnerUsage> = usage.iter().collect();
+        sorted.sort_by(|a, b| {
+            let ordering = match self {
+                UsageSortEnum::Owner => a.owner.cmp(&b.owner),
+                UsageSortEnum::Jobs => a.jobs.cmp(&b.jobs),
+                UsageSortEnum::CoreHours => a.core_hours.total_cmp(&b.core_hours),
+                UsageSortEnum::GpuHours => a.gpu_hours.total_cmp(&b.gpu_hours),
+                UsageSortEnum::Energy => a.energy_kwh.total_cmp(&b.energy_kwh),
+            };
+            let ordering = if descending { ordering.reverse() } else { ordering };
+            ordering.then_with(|| a.owner.cmp(&b.owner))
+        });
+        sorted
+    }
+}
+
+/// Consommation par propriétaire dans [start_s, end_s], en un seul passage sur les jobs visibles,
+/// triée par propriétaire. Chaque job est tronqué à la fenêtre et à `now` (seul le temps écoulé
+/// compte), mais compte dans le nombre de jobs même sans temps écoulé.
+/// - cœurs : ressources OAR assignées ;
+/// - GPU : ceux d'un hôte (`gpu_count_by_host`) répartis au prorata des ressources de l'hôte
+///   assignées au job, faute de lien ressource → GPU ;
+/// - énergie : unités de la série énergie (`energy_units`) × `watts_per_unit`.
+fn usage_by_owner(
+    app: &ApplicationContext,
+    visible: &[&Job],
+    (start_s, end_s): (i64, i64),
+    watts_per_unit: f64,
+) -> Vec<OwnerUsage> {
+    let now_s = Local::now().timestamp();
+    let hosts: HashMap<&str, &[u32]> = app
+        .all_clusters
+        .iter()
+        .flat_map(|cluster| cluster.hosts.iter())
+        .map(|host| (host.name.as_str(), host.resource_ids.as_slice()))
+        .collect();
+
+    let mut usage: BTreeMap<&str, OwnerUsage> = BTreeMap::new();
+    for job in visible.iter() {
+        let entry = usage.entry(job.owner.as_str()).or_default();
+        entry.jobs += 1;
+
+        let Some(hours) = clipped_hours(job, (start_s, end_s), now_s) else {
+            continue;
+        };
+
+        let assigned: HashSet<u32> = job.assigned_resources.iter().copied().collect();
+        let gpus: f64 = job
+            .hosts
+            .iter()
+            .filter_map(|host| {
+                let gpu_count = *app.gpu_count_by_host.get(host)?;
+                let resource_ids = hosts.get(host.as_str()).filter(|ids| !ids.is_empty())?;
+                let used = resource_ids.iter().filter(|id| assigned.contains(id)).count();
+                Some(gpu_count as f64 * used as f64 / resource_ids.len() as f64)
+            })
+            .sum();
+
+        let entry = usage.entry(job.owner.as_str()).or_default();
+        entry.core_hours += job.assigned_resources.len() as f64 * hours;
+        entry.gpu_hours += gpus * hours;
+        entry.energy_kwh += energy_units(job) as f64 * watts_per_unit * hours / 1000.0;
+    }
+
+    usage
+        .into_iter()
+        .map(|(owner, owner_usage)| OwnerUsage {
+            owner: owner.to_string(),
+            ..owner_usage
+        })
+        .collect()
+}
+
+/// Consommation par propriétaire au format CSV, dans l'ordre donné
+/// (`owner,jobs,core_hours,gpu_hours,energy_kwh`).
+pub(super) fn usage_csv(usage: &[&OwnerUsage]) -> String {
+    let mut csv = String::from("owner,jobs,core_hours,gpu_hours,energy_kwh\n");
+    for owner_usage in usage {
+        csv.push_str(&format!(
+            "{},{},{:.2},{:.2},{:.2}\n",
+            owner_usage.owner,
+            owner_usage.jobs,
+            owner_usage.core_hours,
+            owner_usage.gpu_hours,
+            owner_usage.energy_kwh
+        ));
+    }
+    csv
+}
+
+/// Totaux d'un groupe d'agrégation sur la fenêtre visible, pour l'export JSON.
+fn group_summary_json(jobs: &[Job], indices: &[usize], window: (i64, i64), now_s: i64, watts_per_unit: f64) -> Value {
+    let mut job_ids: HashSet<u32> = HashSet::new();
+    let mut core_hours = 0.0;
+    let mut energy_kwh = 0.0;
+    let mut states: BTreeMap<String, usize> = BTreeMap::new();
+    for job in indices.iter().map(|&index| &jobs[index]) {
+        if job.id == 0
+            || job.scheduled_start > window.1
+            || job_end_s(job, now_s) < window.0
+            || !job_ids.insert(job.id)
+        {
+            continue;
+        }
+        *states.entry(job.state.to_string()).or_default() += 1;
+        if let Some(hours) = clipped_hours(job, window, now_s) {
+            core_hours += job.assigned_resources.len() as f64 * hours;
+            energy_kwh += energy_units(job) as f64 * watts_per_unit * hours / 1000.0;
+        }
+    }
+    json!({
+        "jobs": job_ids.len(),
+        "core_hours": core_hours,
+        "energy_kwh": energy_kwh,
+        "states": states,
+    })
+}
+
+/// Résumé de l'agrégation courante (`options.aggregate_by`) sur la fenêtre visible, au format
+/// JSON : par groupe, nombre de jobs, cœurs-heures, énergie estimée et nombre de jobs par état.
+/// Les groupes sont ceux du canvas (même regroupement, mêmes clusters du preset) ; un groupe de
+/// niveau 1 contient ses sous-groupes de niveau 2 dans `groups`.
+pub(super) fn aggregation_summary_json(app: &ApplicationContext, options: &Options, visible_range: (i64, i64)) -> String {
+    let now_s = Local::now().timestamp();
+    let jobs = &app.filtered_jobs;
+    let watts = options.energy_watts_per_unit;
+    let summary = |name: &str, indices: &[usize]| {
+        let mut group = group_summary_json(jobs, indices, visible_range, now_s, watts);
+        group["name"] = json!(name);
+        group
+    };
+
+    let aggregation = build_aggregation(
+        jobs,
+        &preset_clusters(app),
+        &app.all_clusters,
+        &options.aggregate_by,
+        options.show_all_hosts,
+    );
+    let groups: Vec<Value> = match aggregation {
+        Aggregation::Empty => Vec::new(),
+        Aggregation::Level1(groups) => groups.iter().map(|(name, indices)| summary(name, indices)).collect(),
+        Aggregation::Level2(groups) => groups
+            .iter()
+            .map(|(name, level_2)| {
+                let all_indices: Vec<usize> = level_2.values().flatten().copied().collect();
+                let mut group = summary(name, &all_indices);
+                group["groups"] = level_2.iter().map(|(name, indices)| summary(name, indices)).collect();
+                group
+            })
+            .collect(),
+    };
+
+    let document = json!({
+        "start": visible_range.0,
+        "end": visible_range.1,
+        "aggregate_by": {
+            "level_1": options.aggregate_by.level_1,
+            "level_2": options.aggregate_by.level_2,
+        },
+        "groups": groups,
+    });
+    serde_json::to_string_pretty(&document).unwrap_or_default()
+}
+
+/// Résumé textuel de la fenêtre visible du Gantt (lecteurs d'écran, rapports) :
+/// nombre de jobs, répartition par état, propriétaire le plus actif, pic de concurrence.
+fn describe_visible(
+    app: &ApplicationContext,
+    options: &Options,
+    visible: &[&Job],
+    state_counts: &[(JobState, usize)],
+    (start_s, end_s): (i64, i64),
+) -> String {
+    let start = format_timestamp(start_s);
+    let end = format_timestamp(end_s);
+    let mut text = if visible.is_empty() {
+        t!("app.gantt.visible_summary.none", start = start, end = end).to_string()
+    } else {
+        let mut owners: HashMap<&str, usize> = HashMap::new();
+        for job in visible.iter() {
+            *owners.entry(job.owner.as_str()).or_default() += 1;
+        }
+        let states = state_counts
+            .iter()
+            .map(|(state, count)| format!("{} {}", count, state.get_label()))
+            .collect::<Vec<_>>()
+            .join(", ");
+        // Le plus de jobs, puis ordre alphabétique pour un texte stable d'une frame à l'autre
+        let (owner, owner_jobs) = owners
+            .into_iter()
+            .max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(a.0)))
+            .unwrap_or_default();
+
+        t!(
+            "app.gantt.visible_summary.text",
+            jobs = visible.len(),
+            start = start,
+            end = end,
+            states = states,
+            owner = owner,
+            owner_jobs = owner_jobs,
+            peak = max_concurrency(&app.filtered_jobs, start_s, end_s)
+        )
+        .to_string()
+    };
+
+    if let Some(job_id) = options.selected_job_id {
+        text.push(' ');
+        text.push_str(&t!("app.gantt.visible_summary.selected", id = job_id));
+    }
+    text
+}
+
+/// Dernier résumé calculé, avec le nombre de jobs visibles par état et la consommation par
+/// propriétaire : il n'est recalculé que si
+/// la fenêtre visible, les jobs filtrés (`filtered_jobs_generation`) ou la sélection changent,
+/// et au plus une fois par seconde sinon.
+#[derive(Default)]
+pub(super) struct VisibleSummaryCache {
+    key: Option<((i64, i64), u64, Option<u32>)>,
+    computed_at: f64,
+    text: String,
+    state_counts: Vec<(JobState, usize)>,
+    usage: Vec<OwnerUsage>,
+}
+
+impl VisibleSummaryCache {
+    pub(super) fn get(
+        &mut self,
+        now: f64,
+        app: &ApplicationContext,
+        options: &Options,
+        visible_range: (i64, i64),
+    ) -> &str {
+        let key = Some((visible_range, app.filtered_jobs_generation, options.selected_job_id));
+        if self.key != key || now - self.computed_at >= 1.0 {
+            let visible = visible_jobs(app, visible_range);
+            self.state_counts = count_states(&visible);
+            self.usage = usage_by_owner(app, &visible, visible_range, options.energy_watts_per_unit);
+            self.text = describe_visible(app, options, &visible, &self.state_counts, visible_range);
+            self.key = key;
+            self.computed_at = now;
+        }
+        &self.text
+    }
+
+    /// Jobs visibles par état, calculés avec le dernier résumé (voir `get`).
+    pub(super) fn state_counts(&self) -> &[(JobState, usize)] {
+        &self.state_counts
+    }
+
+    /// Consommation par propriétaire, calculée avec le dernier résumé (voir `get`).
+    pub(super) fn usage(&self) -> &[OwnerUsage] {
+        &self.usage
+    }
+}
+
+/// Pic de concurrence de la fenêtre visible. Recalculé quand la fenêtre ou les jobs filtrés
+/// (`filtered_jobs_generation`) changent, et chaque minute pour suivre les jobs en cours.
+#[derive(Default)]
+pub(super) struct PeakConcurrencyCache {
+    key: Option<((i64, i64), u64, i64)>,
+    peak: usize,
+}
+
+impl PeakConcurrencyCache {
+    pub(super) fn get(&mut self, app: &ApplicationContext, (start_s, end_s): (i64, i64)) -> usize {
+        let key = Some(((start_s, end_s), app.filtered_jobs_generation, Local::now().timestamp() / 60));
+        if self.key != key {
+            self.peak = max_concurrency(&app.filtered_jobs, start_s, end_s);
+            self.key = key;
+        }
+        self.peak
+    }
+}
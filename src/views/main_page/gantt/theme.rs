@@ -6,9 +6,22 @@ pub(super) struct ThemeColors {
     pub(super) line: Color32,
     pub(super) aggregated_line_level_1: Rgba,
     pub(super) aggregated_line_level_2: Rgba,
+    /// Épaisseur de base des séparateurs de groupes (niveau 1, niveau 2)
+    pub(super) aggregated_line_width_level_1: f32,
+    pub(super) aggregated_line_width_level_2: f32,
     pub(super) background: Color32,
     pub(super) background_timeline: Color32,
+    /// Fond opaque de la gouttière des libellés, distinct du fond du graphe
+    /// (vue Cluster → Hôte à part, où la gouttière porte les bandes site / cluster / hôte)
+    pub(super) gutter_background: Color32,
+    pub(super) gutter_background_grid5000: Color32,
     pub(super) hatch: Color32,
+    pub(super) idle_gap: Color32,
+    /// Teinte des lignes d'hôtes Alive sans job dans la vue (capacité libre)
+    pub(super) idle: Color32,
+    pub(super) future: Color32,
+    /// Fond d'une ligne sur deux (`zebra_rows`)
+    pub(super) zebra: Color32,
 }
 
 pub(super) fn get_theme_colors(style: &egui::Style) -> ThemeColors {
@@ -19,9 +32,17 @@ pub(super) fn get_theme_colors(style: &egui::Style) -> ThemeColors {
             line: Color32::WHITE,
             aggregated_line_level_1: Rgba::from_white_alpha(0.4),
             aggregated_line_level_2: Rgba::from_white_alpha(0.5),
+            aggregated_line_width_level_1: 1.5,
+            aggregated_line_width_level_2: 0.5,
             background: Color32::from_black_alpha(100),
             background_timeline: Color32::from_black_alpha(150),
+            gutter_background: Color32::from_rgb(52, 48, 34),
+            gutter_background_grid5000: Color32::from_gray(34),
             hatch: Color32::from_rgba_premultiplied(0, 150, 150, 150),
+            idle_gap: Color32::from_white_alpha(24),
+            idle: Color32::from_rgba_unmultiplied(80, 200, 120, 28),
+            future: Color32::from_rgba_unmultiplied(110, 150, 255, 18),
+            zebra: Color32::from_white_alpha(10),
         }
     } else {
         ThemeColors {
@@ -30,9 +51,17 @@ pub(super) fn get_theme_colors(style: &egui::Style) -> ThemeColors {
             line: Color32::BLACK,
             aggregated_line_level_1: Rgba::from_black_alpha(0.5),
             aggregated_line_level_2: Rgba::from_black_alpha(0.7),
+            aggregated_line_width_level_1: 1.5,
+            aggregated_line_width_level_2: 0.5,
             background: Color32::from_black_alpha(50),
             background_timeline: Color32::from_black_alpha(20),
+            gutter_background: Color32::from_rgb(252, 238, 170),
+            gutter_background_grid5000: Color32::from_gray(232),
             hatch: Color32::from_rgba_premultiplied(0, 0, 139, 150),
+            idle_gap: Color32::from_black_alpha(28),
+            idle: Color32::from_rgba_unmultiplied(40, 160, 80, 32),
+            future: Color32::from_rgba_unmultiplied(40, 80, 200, 20),
+            zebra: Color32::from_black_alpha(14),
         }
     }
 }
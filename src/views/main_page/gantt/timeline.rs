@@ -1,5 +1,6 @@
 use super::theme::get_theme_colors;
 use super::types::{Info, Options};
+use crate::models::utils::date_converter::format_duration;
 use chrono::{DateTime, Local};
 use egui::{pos2, remap_clamp, Align2, Color32, Rgba, Rect, Stroke};
 
@@ -184,10 +185,129 @@ pub(super) fn paint_current_time_line(
 
     egui::Shape::line_segment(
         [pos2(line_x, canvas.min.y), pos2(line_x, canvas.max.y)],
-        Stroke::new(2.0, Color32::RED),
+        Stroke::new(options.current_time_width, options.current_time_color),
     )
 }
 
+/// Voile léger sur la partie future du graphe (à droite de la ligne « maintenant »).
+pub(super) fn paint_future_shading(
+    info: &Info,
+    options: &Options,
+    canvas: Rect,
+    gutter_width: f32,
+) -> Option<egui::Shape> {
+    let theme_colors = get_theme_colors(&info.ctx.style());
+    let chart_min_x = canvas.min.x + gutter_width;
+    let now_x = info
+        .point_from_s(options, chrono::Utc::now().timestamp())
+        .max(chart_min_x);
+    if now_x >= canvas.max.x {
+        return None;
+    }
+    Some(egui::Shape::rect_filled(
+        Rect::from_min_max(pos2(now_x, canvas.min.y), canvas.max),
+        0.0,
+        theme_colors.future,
+    ))
+}
+
+/// Lignes verticales des repères nommés, avec leur libellé sous la frise du haut.
+pub(super) fn paint_markers(
+    info: &Info,
+    options: &Options,
+    canvas: Rect,
+    label_y: f32,
+    gutter_width: f32,
+) -> Vec<egui::Shape> {
+    let theme_colors = get_theme_colors(&info.ctx.style());
+    let chart_min_x = canvas.min.x + gutter_width;
+    let mut shapes = Vec::new();
+
+    for (time_s, label, color) in options.markers.iter() {
+        let line_x = info.point_from_s(options, *time_s);
+        if line_x < chart_min_x || line_x > canvas.max.x {
+            continue;
+        }
+
+        shapes.extend(egui::Shape::dashed_line(
+            &[pos2(line_x, canvas.min.y), pos2(line_x, canvas.max.y)],
+            Stroke::new(1.5, *color),
+            6.0,
+            4.0,
+        ));
+
+        if !label.is_empty() {
+            let text = info.ctx.fonts(|fonts| {
+                egui::Shape::text(
+                    fonts,
+                    pos2(line_x + 3.0, label_y),
+                    Align2::LEFT_TOP,
+                    label,
+                    info.font_id.clone(),
+                    *color,
+                )
+            });
+            shapes.push(egui::Shape::rect_filled(
+                text.visual_bounding_rect().expand(2.0),
+                2.0,
+                theme_colors.background_timeline,
+            ));
+            shapes.push(text);
+        }
+    }
+
+    shapes
+}
+
+/// Sous-fenêtre de temps tracée avec Maj + glisser : voile, bords et durée.
+pub(super) fn paint_time_selection(
+    info: &Info,
+    options: &Options,
+    canvas: Rect,
+    label_y: f32,
+    gutter_width: f32,
+    (start_s, end_s): (i64, i64),
+) -> Vec<egui::Shape> {
+    let theme_colors = get_theme_colors(&info.ctx.style());
+    let selection = info.ctx.style().visuals.selection;
+    let chart_min_x = canvas.min.x + gutter_width;
+    let x0 = info.point_from_s(options, start_s.min(end_s)).max(chart_min_x);
+    let x1 = info.point_from_s(options, start_s.max(end_s)).min(canvas.max.x);
+    if x1 <= x0 {
+        return Vec::new();
+    }
+
+    let mut shapes = vec![egui::Shape::rect_filled(
+        Rect::from_min_max(pos2(x0, canvas.min.y), pos2(x1, canvas.max.y)),
+        0.0,
+        selection.bg_fill.gamma_multiply(0.25),
+    )];
+    for x in [x0, x1] {
+        shapes.push(egui::Shape::line_segment(
+            [pos2(x, canvas.min.y), pos2(x, canvas.max.y)],
+            Stroke::new(1.5, selection.stroke.color),
+        ));
+    }
+
+    let text = info.ctx.fonts(|fonts| {
+        egui::Shape::text(
+            fonts,
+            pos2(x0 + 3.0, label_y),
+            Align2::LEFT_TOP,
+            format_duration((end_s - start_s).abs()),
+            info.font_id.clone(),
+            theme_colors.text,
+        )
+    });
+    shapes.push(egui::Shape::rect_filled(
+        text.visual_bounding_rect().expand(2.0),
+        2.0,
+        theme_colors.background_timeline,
+    ));
+    shapes.push(text);
+    shapes
+}
+
 fn grid_text(ts: i64) -> String {
     if ts == 0 {
         "N/A".to_string()
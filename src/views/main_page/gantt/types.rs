@@ -1,8 +1,17 @@
+use super::stats::FrameStats;
 use crate::models::data_structure::job::Job;
 use crate::models::data_structure::resource::ResourceState;
 use crate::views::components::gantt_aggregate_by::AggregateBy;
+use crate::views::components::gantt_gutter_click::{GutterClickAction, GutterFilter};
+use crate::views::components::gantt_host_sort::HostSort;
 use crate::views::components::gantt_job_color::JobColor;
-use egui::{FontId, Rect, Response};
+use crate::views::components::gantt_strata_fields::StrataFields;
+use crate::views::components::gantt_time_basis::TimeBasis;
+use crate::views::components::gantt_tooltip_mode::TooltipMode;
+use crate::views::components::gantt_zoom_anchor::ZoomAnchor;
+use egui::{Color32, FontId, Id, Pos2, Rect, Response};
+use egui_plot::Corner;
+use std::collections::HashSet;
 
 pub(super) const GUTTER_WIDTH: f32 = 200.0;
 
@@ -37,6 +46,21 @@ impl Info {
             + options.sideways_pan_in_points
             + self.usable_width() * ((ns - self.start_s) as f32) / options.canvas_width_s
     }
+
+    /// Inverse de `point_from_s` : instant correspondant à une abscisse du canvas.
+    pub(super) fn s_from_point(&self, options: &Options, x: f32) -> i64 {
+        let offset_points = x - self.canvas.min.x - self.gutter_width - options.sideways_pan_in_points;
+        self.start_s + (offset_points as f64 * options.canvas_width_s as f64 / self.usable_width() as f64) as i64
+    }
+
+    /// Fenêtre de temps (en secondes) actuellement visible à droite de la gouttière.
+    pub(super) fn visible_range_s(&self, options: &Options) -> (i64, i64) {
+        let s_per_point = options.canvas_width_s as f64 / self.usable_width() as f64;
+        let pan_s = options.sideways_pan_in_points as f64 * s_per_point;
+        let start = self.start_s as f64 - pan_s;
+        let end = start + options.canvas_width_s as f64;
+        (start.floor() as i64, end.ceil() as i64)
+    }
 }
 
 pub struct Options {
@@ -49,6 +73,11 @@ pub struct Options {
     pub rounding: f32,
     pub aggregate_by: AggregateBy,
     pub job_color: JobColor,
+    pub host_sort: HostSort,
+    pub time_basis: TimeBasis,
+    pub tooltip_mode: TooltipMode,
+    pub strata_fields: StrataFields,
+    pub zoom_anchor: ZoomAnchor,
     pub current_hovered_job: Option<Job>,
     pub previous_hovered_job: Option<Job>,
     pub current_hovered_resource_state: Option<ResourceState>,
@@ -56,8 +85,120 @@ pub struct Options {
     #[cfg_attr(feature = "serde", serde(skip))]
     pub hovered_grid5000_host: Option<String>,
     pub compact_rows: bool,
+    /// Masque les lignes sans aucun job dans la fenêtre visible.
+    /// Les lignes créées uniquement par le job synthétique `all_resources` (id 0)
+    /// sont donc masquées elles aussi : cette option l'emporte sur `see_all_jobs`.
+    pub hide_idle_rows: bool,
+    /// Après un changement de filtre (propriétaire, état, hôte, commande, preset), recadre la
+    /// fenêtre visible sur l'étendue des jobs filtrés.
+    pub auto_fit_on_filter: bool,
+    /// Au premier chargement de données, recadre une fois la fenêtre visible sur l'étendue des
+    /// jobs, sauf fenêtre demandée au lancement (`--since`, `--start`, `--end`).
+    pub auto_fit_on_load: bool,
+    /// Nombre maximal de groupes de niveau 1 dessinés, dans l'ordre de tri (0 = tous).
+    pub max_groups: usize,
+    /// Groupes supplémentaires affichés via « Afficher N de plus… », en plus de `max_groups`.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub extra_groups: usize,
+    /// En vue Cluster → Host, affiche tous les hôtes connus, y compris ceux sans job.
+    pub show_all_hosts: bool,
+    /// Pour les jobs terminés, dessine le walltime réservé en contour léger derrière la durée réelle.
+    pub show_reserved_vs_actual: bool,
+    /// Grise les périodes sans job de chaque ligne d'hôte dans la fenêtre visible.
+    pub show_idle_gaps: bool,
+    /// Avancement des jobs en cours (Running) : part écoulée du walltime assombrie dans la barre.
+    pub show_progress: bool,
+    /// Sur les lignes d'hôtes et de ressources, empile en sous-lignes les jobs qui s'exécutent en
+    /// même temps (nœud partagé, ordonnancement par cœur) au lieu de les superposer.
+    pub stack_shared_jobs: bool,
+    /// Teinte les lignes d'hôtes dont les ressources sont Alive mais sans job dans la fenêtre
+    /// visible : la capacité libre ressort, à la différence des hôtes Absent ou Dead (hachurés).
+    pub show_idle: bool,
+    /// Affiche l'attente en file : graduation à la soumission reliée au début de la barre.
+    pub show_submission: bool,
+    /// Étiquette de la file (ex. « besteffort ») au bord gauche des barres assez larges.
+    pub show_queue_tag: bool,
+    /// Voile léger sur la partie future du graphe (après la ligne « maintenant »).
+    pub shade_future: bool,
+    /// Vue par propriétaire : bande d'activité sur toute la plage chargée dans la gouttière.
+    pub show_owner_heat_strip: bool,
+    /// Fond léger une ligne sur deux, pour suivre une ligne sur un axe de temps large.
+    pub zebra_rows: bool,
+    /// Hachure Dead / Absent sur tout le passé visible, sinon limitée à l'instantané courant.
+    pub state_hatch_full_past: bool,
+    /// Multiplicateur des épaisseurs de séparateurs du thème, et niveau 2 en tirets.
+    pub separator_scale: f32,
+    pub dashed_level_2_separators: bool,
+    /// Couleur et épaisseur de la ligne « maintenant »
+    pub current_time_color: Color32,
+    pub current_time_width: f32,
+    /// Série énergie : pas demandé (s, voir `energy_estimate::effective_step_s`) et puissance par unité (W)
+    pub energy_step_s: i64,
+    pub energy_watts_per_unit: f64,
+    /// Axe Y du graphe énergie fixé au maximum global de la série, au lieu d'être recalé sur la
+    /// fenêtre visible (qui exagère les petites variations).
+    pub energy_lock_y_axis: bool,
+    /// Budget de puissance (W, ex. puissance contractée) tracé sur le graphe énergie, avec
+    /// l'ombre des dépassements ; `None` : pas de budget.
+    pub power_budget_w: Option<f64>,
+    /// Coin de la légende du graphe énergie ; `None` : légende masquée.
+    pub energy_legend: Option<Corner>,
+    /// Séries du graphe énergie masquées d'un clic dans la légende (par nom de série)
+    pub energy_hidden_series: HashSet<String>,
+    /// Repères nommés : instant, libellé, couleur (voir `markers.rs`)
+    pub markers: Vec<(i64, String, Color32)>,
+    /// Propriétaire dont la ligne doit être amenée à l'écran (vue par propriétaire)
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub scroll_to_owner: Option<String>,
+    /// Rectangle à faire défiler à l'écran, relevé pendant le dessin
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub scroll_to_rect: Option<Rect>,
+    /// Texte du tooltip survolé et instant de début du survol (mode avec délai)
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub tooltip_hover: Option<(String, f64)>,
+    /// Tooltip épinglé par un clic et sa position (mode au clic)
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub tooltip_pinned: Option<(String, Pos2)>,
+    /// Sous-fenêtre de temps tracée avec Maj + glisser : (instant d'origine, instant courant)
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub time_selection: Option<(i64, i64)>,
+    /// Job sélectionné au clavier (Tab / Maj+Tab), mis en évidence avec son tooltip.
+    pub selected_job_id: Option<u32>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub selected_job_rect: Option<Rect>,
     #[cfg_attr(feature = "serde", serde(skip))]
     pub zoom_to_relative_s_range: Option<(f64, (f64, f64))>,
+    /// Jobs du groupe de niveau 1 en cours de dessin qui chevauchent un autre job sur une même
+    /// ressource (voir `overlapping_jobs`), entourés d'un contour d'alerte.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub overlapping_jobs: HashSet<u32>,
+    /// Recherche de commande en cours de saisie (aperçu, avant application du filtre) : les jobs
+    /// dont la commande la contient sont entourés.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub command_highlight: Option<String>,
+    /// Action d'un clic sur un libellé de la gouttière (rien, filtrer, replier).
+    pub gutter_click_action: GutterClickAction,
+    /// Filtre demandé par un clic dans la gouttière, appliqué aux filtres de l'application
+    /// après le dessin.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub gutter_filter: Option<GutterFilter>,
+    /// Étendue (début, fin) des jobs d'un groupe de la gouttière cliqué, sur laquelle
+    /// `GanttChart` recadre la fenêtre visible après le dessin.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub gutter_zoom: Option<(i64, i64)>,
+    /// Barres de jobs dessinées dans la vue (id, rectangle visible), dans l'ordre de dessin :
+    /// elles deviennent des widgets focalisables au clavier après le dessin du canvas.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub focusable_jobs: Vec<(u32, Rect)>,
+    /// Barre de job qui avait le focus clavier à l'image précédente : Tab y reste géré par le Gantt.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub focused_job_bar: Option<Id>,
+    /// Job choisi par Tab / Maj+Tab dont la barre doit prendre le focus dès qu'elle est dessinée.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub focus_request_job_id: Option<u32>,
+    /// Compteurs de diagnostic de l'image en cours (overlay F12).
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub frame_stats: FrameStats,
 }
 
 impl Default for Options {
@@ -72,13 +213,89 @@ impl Default for Options {
             rounding: 4.0,
             aggregate_by: Default::default(),
             job_color: Default::default(),
+            host_sort: Default::default(),
+            time_basis: Default::default(),
+            tooltip_mode: Default::default(),
+            strata_fields: Default::default(),
+            zoom_anchor: Default::default(),
             zoom_to_relative_s_range: None,
             current_hovered_job: None,
             previous_hovered_job: None,
             current_hovered_resource_state: None,
             current_hovered_resource_label: None,
             hovered_grid5000_host: None,
+            hide_idle_rows: false,
+            auto_fit_on_filter: false,
+            auto_fit_on_load: true,
+            max_groups: 100,
+            extra_groups: 0,
+            show_all_hosts: false,
+            show_reserved_vs_actual: false,
+            show_idle_gaps: false,
+            show_progress: false,
+            stack_shared_jobs: false,
+            show_idle: false,
+            show_submission: false,
+            show_queue_tag: false,
+            shade_future: false,
+            show_owner_heat_strip: false,
+            zebra_rows: false,
+            state_hatch_full_past: true,
+            separator_scale: 1.0,
+            dashed_level_2_separators: false,
+            current_time_color: Color32::RED,
+            current_time_width: 2.0,
+            energy_step_s: 10,
+            energy_watts_per_unit: 300.0,
+            energy_lock_y_axis: false,
+            energy_legend: Some(Corner::RightTop),
+            energy_hidden_series: HashSet::new(),
+            power_budget_w: None,
+            markers: Vec::new(),
+            scroll_to_owner: None,
+            scroll_to_rect: None,
+            tooltip_hover: None,
+            tooltip_pinned: None,
+            time_selection: None,
+            selected_job_id: None,
+            selected_job_rect: None,
+            overlapping_jobs: HashSet::new(),
+            command_highlight: None,
+            gutter_click_action: GutterClickAction::default(),
+            gutter_filter: None,
+            gutter_zoom: None,
+            focusable_jobs: Vec::new(),
+            focused_job_bar: None,
+            focus_request_job_id: None,
+            frame_stats: FrameStats::default(),
             compact_rows: true,
         }
     }
 }
+
+impl Options {
+    /// Remet les préférences d'affichage à leurs valeurs par défaut, en conservant l'agrégation,
+    /// la fenêtre visible, les repères et l'état d'interaction (sélection, survol).
+    pub(super) fn reset_to_defaults(&mut self) {
+        let current = std::mem::take(self);
+        *self = Self {
+            canvas_width_s: current.canvas_width_s,
+            sideways_pan_in_points: current.sideways_pan_in_points,
+            aggregate_by: current.aggregate_by,
+            markers: current.markers,
+            current_hovered_job: current.current_hovered_job,
+            previous_hovered_job: current.previous_hovered_job,
+            selected_job_id: current.selected_job_id,
+            ..Self::default()
+        };
+    }
+
+    /// Nombre de groupes de niveau 1 à dessiner (`max_groups` relevé par `extra_groups`).
+    pub(super) fn group_cap(&self) -> usize {
+        if self.max_groups == 0 {
+            usize::MAX
+        } else {
+            self.max_groups.saturating_add(self.extra_groups)
+        }
+    }
+}
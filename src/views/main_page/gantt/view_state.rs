@@ -0,0 +1,76 @@
+use super::GanttChart;
+use crate::models::data_structure::application_context::ApplicationContext;
+use crate::models::data_structure::filters::BesteffortFilter;
+use crate::models::data_structure::job::JobState;
+use crate::views::components::gantt_aggregate_by::{AggregateByLevel1Enum, AggregateByLevel2Enum};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use serde::{Deserialize, Serialize};
+
+/// État de vue partageable : agrégation, filtres, fenêtre de temps visible et zoom vertical.
+/// Sérialisé en JSON puis encodé en base64 (URL-safe) pour être copié/collé entre utilisateurs.
+/// Un filtre absent d'un état plus ancien est remis à sa valeur par défaut à la restauration.
+#[derive(Serialize, Deserialize)]
+pub(super) struct ViewState {
+    aggregate_by: (AggregateByLevel1Enum, AggregateByLevel2Enum),
+    owners: Option<Vec<String>>,
+    states: Option<Vec<JobState>>,
+    hosts: Option<Vec<String>>,
+    sites: Option<Vec<String>>,
+    command: Option<String>,
+    #[serde(default)]
+    besteffort: BesteffortFilter,
+    preset: Option<String>,
+    visible_range: (i64, i64),
+    rect_height: f32,
+}
+
+impl ViewState {
+    pub(super) fn capture(chart: &GanttChart, app: &ApplicationContext) -> Self {
+        Self {
+            aggregate_by: (
+                chart.options.aggregate_by.level_1,
+                chart.options.aggregate_by.level_2,
+            ),
+            owners: app.filters.owners.clone(),
+            states: app.filters.states.clone(),
+            hosts: app.filters.hosts.clone(),
+            sites: app.filters.sites.clone(),
+            command: app.filters.command.clone(),
+            besteffort: app.filters.besteffort,
+            preset: app.filters.selected_preset.clone(),
+            visible_range: chart.visible_window(),
+            rect_height: chart.options.rect_height,
+        }
+    }
+
+    pub(super) fn restore(&self, chart: &mut GanttChart, app: &mut ApplicationContext) {
+        chart.options.aggregate_by.level_1 = self.aggregate_by.0;
+        chart.options.aggregate_by.level_2 = self.aggregate_by.1;
+        chart.options.rect_height = self.rect_height.clamp(8.0, 80.0);
+
+        app.filters.set_owners(self.owners.clone());
+        app.filters.set_states(self.states.clone());
+        app.filters.set_hosts(self.hosts.clone());
+        app.filters.set_sites(self.sites.clone());
+        app.filters.set_command(self.command.clone());
+        app.filters.set_besteffort(self.besteffort);
+        // Un preset inconnu de ce poste est ignoré plutôt que de masquer tous les clusters
+        let preset = self
+            .preset
+            .clone()
+            .filter(|name| app.cluster_presets.iter().any(|p| p.name == *name));
+        app.filters.set_selected_preset(preset);
+
+        chart.set_visible_window(self.visible_range.0, self.visible_range.1);
+    }
+
+    pub(super) fn encode(&self) -> String {
+        let json = serde_json::to_vec(self).unwrap_or_default();
+        URL_SAFE_NO_PAD.encode(json)
+    }
+
+    pub(super) fn decode(text: &str) -> Option<Self> {
+        let bytes = URL_SAFE_NO_PAD.decode(text.trim()).ok()?;
+        serde_json::from_slice(&bytes).ok()
+    }
+}
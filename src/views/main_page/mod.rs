@@ -1,3 +1,4 @@
 pub mod dashboard;
 pub mod gantt;
+pub mod resources_panel;
 pub mod anthentification;
\ No newline at end of file
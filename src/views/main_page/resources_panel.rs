@@ -0,0 +1,102 @@
+use crate::models::data_structure::cluster::Cluster;
+use crate::models::data_structure::strata::Strata;
+use crate::models::utils::utils::{cluster_resource_summary, compare_string_with_number};
+use crate::{models::data_structure::application_context::ApplicationContext, views::view::View};
+use eframe::egui::{self, Grid, RichText};
+use std::collections::HashMap;
+
+/*
+ * Static inventory of the clusters, shown in a side panel next to the Gantt or the Dashboard:
+ * size, dominant hardware and current resource states of each cluster of `all_clusters`.
+ */
+#[derive(Default)]
+pub struct ResourcesPanel;
+
+impl View for ResourcesPanel {
+    fn render(&mut self, ui: &mut egui::Ui, app: &mut ApplicationContext) {
+        ui.heading(t!("app.resources_panel.title"));
+        ui.separator();
+
+        if app.all_clusters.is_empty() {
+            ui.weak(t!("app.resources_panel.empty"));
+            return;
+        }
+
+        let mut clusters: Vec<&Cluster> = app.all_clusters.iter().collect();
+        clusters.sort_by(|a, b| compare_string_with_number(&a.name, &b.name));
+
+        egui::ScrollArea::vertical().auto_shrink([false, false]).show(ui, |ui| {
+            for cluster in clusters {
+                let (hosts, cores, threads, gpus) = cluster_resource_summary(cluster, &app.gpu_count_by_host);
+                let header = format!("{} ({})", cluster.name, t!("app.resources_panel.host_count", count = hosts));
+
+                egui::CollapsingHeader::new(RichText::new(header).strong())
+                    .id_salt(("resources_panel", &cluster.name))
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        let cputype = dominant_value(cluster, &app.strata_by_host, |strata| strata.cputype.as_ref());
+                        let nodemodel = dominant_value(cluster, &app.strata_by_host, |strata| strata.nodemodel.as_ref());
+                        let states = cluster.resource_state_counts();
+
+                        Grid::new(("resources_panel_grid", &cluster.name))
+                            .num_columns(2)
+                            .striped(true)
+                            .show(ui, |ui| {
+                                let rows = [
+                                    (t!("app.dashboard.clusters.cores"), cores.to_string()),
+                                    (t!("app.dashboard.clusters.threads"), threads.to_string()),
+                                    (t!("app.dashboard.clusters.gpus"), gpus.to_string()),
+                                    (t!("app.resources_panel.cputype"), cputype),
+                                    (t!("app.resources_panel.nodemodel"), nodemodel),
+                                    (
+                                        t!("app.resources_panel.states"),
+                                        t!(
+                                            "app.resources_panel.state_counts",
+                                            alive = states.alive,
+                                            dead = states.dead,
+                                            absent = states.absent
+                                        )
+                                        .to_string(),
+                                    ),
+                                ];
+                                for (name, value) in rows {
+                                    ui.label(name);
+                                    ui.label(value);
+                                    ui.end_row();
+                                }
+                            });
+                    });
+            }
+        });
+    }
+}
+
+/*
+ * Most frequent value of a strata property over the hosts of the cluster, followed by the
+ * number of other distinct values when the cluster is heterogeneous ("n/a" when no host has it).
+ * Ties go to the smallest value, so the readout does not flicker between refreshes.
+ */
+fn dominant_value(
+    cluster: &Cluster,
+    strata_by_host: &HashMap<String, Strata>,
+    property: impl Fn(&Strata) -> Option<&String>,
+) -> String {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for strata in cluster.hosts.iter().filter_map(|host| strata_by_host.get(&host.name)) {
+        if let Some(value) = property(strata).map(|value| value.trim()).filter(|value| !value.is_empty()) {
+            *counts.entry(value).or_default() += 1;
+        }
+    }
+
+    let others = counts.len().saturating_sub(1);
+    let dominant = counts
+        .into_iter()
+        .max_by(|(value_a, count_a), (value_b, count_b)| count_a.cmp(count_b).then(value_b.cmp(value_a)));
+    match dominant {
+        Some((value, _)) if others > 0 => {
+            format!("{} {}", value, t!("app.resources_panel.other_values", count = others))
+        }
+        Some((value, _)) => value.to_string(),
+        None => "n/a".to_string(),
+    }
+}
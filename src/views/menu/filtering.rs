@@ -1,5 +1,7 @@
 use crate::models::data_structure::{
-    application_context::ApplicationContext, filters::JobFilters, job::JobState,
+    application_context::ApplicationContext,
+    filters::{BesteffortFilter, JobFilters},
+    job::JobState,
 };
 use eframe::egui::{self, Grid, Stroke};
 use strum::IntoEnumIterator;
@@ -64,6 +66,19 @@ impl Filtering {
                         });
                     ui.add_space(10.0);
 
+                    egui::CollapsingHeader::new(t!("app.filter.site"))
+                        .default_open(false)
+                        .show(ui, |ui| {
+                            self.render_sites_selector(ui, app);
+                        });
+                    ui.add_space(10.0);
+
+                    self.render_besteffort_selector(ui);
+                    ui.add_space(10.0);
+
+                    self.render_command_search(ui, app);
+                    ui.add_space(10.0);
+
                     ui.label("Cluster Presets");
                     ui.horizontal_wrapped(|ui| {
                         let none_selected = self.temp_filters.selected_preset.is_none();
@@ -107,12 +122,61 @@ impl Filtering {
                 });
         }
         self.open = open;
+        // The preview only lives while the window is open: the applied filter takes over
+        if !self.open {
+            app.command_preview = None;
+        }
+    }
+
+    /* Renders the command search field
+     *
+     * The query is part of the temporary filters, applied with the other ones. Meanwhile it is
+     * published as a preview so the Gantt outlines the jobs it would keep while typing.
+     */
+    fn render_command_search(&mut self, ui: &mut egui::Ui, app: &mut ApplicationContext) {
+        let mut query = self.temp_filters.command.clone().unwrap_or_default();
+        ui.horizontal(|ui| {
+            ui.label(t!("app.filter.command"));
+            if ui
+                .add(egui::TextEdit::singleline(&mut query).hint_text(t!("app.filter.command_hint")))
+                .changed()
+            {
+                let query = query.trim();
+                self.temp_filters
+                    .set_command(if query.is_empty() { None } else { Some(query.to_string()) });
+            }
+        });
+        app.command_preview = self.temp_filters.command.clone();
     }
 
     pub fn reset_filters(&mut self) {
         self.temp_filters = JobFilters::default();
     }
 
+    // Keeps the window in line with the besteffort quick toggles of the tool bar, which are
+    // applied at once: otherwise the next Apply would undo them
+    pub fn set_besteffort(&mut self, besteffort: BesteffortFilter) {
+        self.temp_filters.set_besteffort(besteffort);
+    }
+
+    /* Renders the besteffort selector
+     *
+     * All jobs, only the besteffort (preemptible) ones, or all but them.
+     */
+    fn render_besteffort_selector(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(t!("app.filter.besteffort.title"));
+            let choices = [
+                (BesteffortFilter::All, t!("app.filter.besteffort.all")),
+                (BesteffortFilter::Only, t!("app.filter.besteffort.only")),
+                (BesteffortFilter::Exclude, t!("app.filter.besteffort.exclude")),
+            ];
+            for (value, label) in choices {
+                ui.radio_value(&mut self.temp_filters.besteffort, value, label);
+            }
+        });
+    }
+
     /* Renders the job owner selection grid
      *
      * This selector displays a grid of checkboxes for all unique job owners,
@@ -147,6 +211,39 @@ impl Filtering {
             });
     }
 
+    /* Renders the site selection grid
+     *
+     * A job is kept when any of its clusters belongs to one of the selected sites.
+     */
+    fn render_sites_selector(&mut self, ui: &mut egui::Ui, app: &mut ApplicationContext) {
+        let unique_sites = app.get_unique_sites();
+        let mut selected_sites = self.temp_filters.sites.clone().unwrap_or_default();
+
+        Grid::new("sites_grid")
+            .num_columns(2)
+            .spacing([10.0, 5.0])
+            .show(ui, |ui| {
+                for (i, site) in unique_sites.iter().enumerate() {
+                    let mut is_selected = selected_sites.contains(site);
+                    if ui.checkbox(&mut is_selected, site).changed() {
+                        if is_selected {
+                            selected_sites.push(site.clone());
+                        } else {
+                            selected_sites.retain(|s| s != site);
+                        }
+                        self.temp_filters.set_sites(if selected_sites.is_empty() {
+                            None
+                        } else {
+                            Some(selected_sites.clone())
+                        });
+                    }
+                    if i % 2 == 1 {
+                        ui.end_row();
+                    }
+                }
+            });
+    }
+
     /*
      * Render the states selector
      * This selector is used to select the states of the jobs on which the jobs will be filtered
@@ -1,9 +1,13 @@
+use crate::models::data_structure::filters::BesteffortFilter;
 use crate::models::data_structure::job::Job;
 use crate::models::data_structure::job::JobState;
 use crate::models::data_structure::resource::ResourceState;
 use crate::models::utils::utils::get_all_clusters;
 use crate::models::utils::utils::get_all_hosts;
 use crate::models::utils::utils::get_all_resources;
+use crate::models::utils::utils::lock_or_recover;
+use crate::models::utils::date_converter::format_timestamp;
+use crate::models::utils::parser::data_source_name;
 use crate::views::menu::tools::egui::Color32;
 use crate::{
     models::data_structure::application_context::ApplicationContext,
@@ -115,6 +119,25 @@ impl Tools {
                 self.filtering_pane.open();
             }
 
+            // Besteffort quick toggles, applied at once; clicking the active one shows all jobs
+            let besteffort = app.filters.besteffort;
+            let toggles = [
+                (BesteffortFilter::Only, t!("app.filter.besteffort.only_chip"), t!("app.filter.besteffort.only_hint")),
+                (
+                    BesteffortFilter::Exclude,
+                    t!("app.filter.besteffort.exclude_chip"),
+                    t!("app.filter.besteffort.exclude_hint"),
+                ),
+            ];
+            for (value, label, hint) in toggles {
+                if ui.selectable_label(besteffort == value, label).on_hover_text(hint).clicked() {
+                    let besteffort = besteffort.toggled(value);
+                    app.filters.set_besteffort(besteffort);
+                    self.filtering_pane.set_besteffort(besteffort);
+                    app.filter_jobs();
+                }
+            }
+
             // Gantt-specific controls are part of this 2nd line.
             if let Some(gantt) = gantt.as_deref_mut() {
                 gantt.render_compact_toolbar(ui, app);
@@ -151,7 +174,7 @@ impl Tools {
                         ];
 
                         for (rate, label) in refresh_rates {
-                            let selected = *app.refresh_rate.lock().unwrap() == rate;
+                            let selected = *lock_or_recover(&app.refresh_rate) == rate;
                             let display_label = if selected {
                                 format!("{} ✔", label)
                             } else {
@@ -166,7 +189,7 @@ impl Tools {
                 );
 
                 let refresh_btn = egui::Button::new("⟳");
-                let refresh_btn_response = if *app.is_refreshing.lock().unwrap() {
+                let refresh_btn_response = if *lock_or_recover(&app.is_refreshing) {
                     ui.add_enabled(false, refresh_btn)
                 } else {
                     ui.add(refresh_btn)
@@ -174,6 +197,21 @@ impl Tools {
                 if refresh_btn_response.clicked() {
                     app.instant_update();
                 }
+
+                // Cluster inventory side panel
+                ui.toggle_value(
+                    &mut app.resources_panel_open,
+                    "🖥 ".to_string() + &t!("app.resources_panel.button"),
+                )
+                .on_hover_text(t!("app.resources_panel.hint"));
+
+                // Data provenance: the source is always visible, details on click
+                let loaded_window = gantt.as_deref().and_then(|gantt| gantt.loaded_window());
+                ui.menu_button(format!("ℹ {}", data_source_name()), |ui| {
+                    provenance_ui(ui, app, loaded_window);
+                })
+                .response
+                .on_hover_text(t!("app.menu.provenance.hint"));
             });
 
             });
@@ -200,7 +238,7 @@ impl Tools {
                     }
                 }
 
-                let refreshing = *app.is_refreshing.lock().unwrap_or_else(|p| p.into_inner());
+                let refreshing = *lock_or_recover(&app.is_refreshing);
                 let status = if refreshing {
                     "refreshing"
                 } else if app.is_loading {
@@ -209,18 +247,38 @@ impl Tools {
                     "ready"
                 };
 
+                // Jobs ignorés car leurs dates sont invalides (voir `Job::has_plausible_times`)
+                let implausible = if app.implausible_jobs > 0 {
+                    format!(" (ignorés, dates invalides : {})", app.implausible_jobs)
+                } else {
+                    String::new()
+                };
+                // Jobs dont des ressources manquent dans l'arbre des clusters
+                let orphans = if app.orphan_jobs > 0 {
+                    format!(" (ressources inconnues : {})", app.orphan_jobs)
+                } else {
+                    String::new()
+                };
+
+                let peak = gantt
+                    .as_deref_mut()
+                    .map_or(0, |gantt| gantt.visible_peak_concurrency(app));
+
                 ui.horizontal(|ui| {
                     ui.with_layout(
                         egui::Layout::centered_and_justified(egui::Direction::LeftToRight),
                         |ui| {
                             let label = egui::Label::new(
                                 egui::RichText::new(format!(
-                                    "Data: jobs={} | clusters affichés {}/{} | hosts affichés {}/{} | {}",
+                                    "Data: jobs={}{}{} | clusters affichés {}/{} | hosts affichés {}/{} | pic simultané {} | {}",
                                     app.filtered_jobs.len(),
+                                    implausible,
+                                    orphans,
                                     displayed_clusters.len(),
                                     total_clusters,
                                     displayed_hosts.len(),
                                     total_hosts,
+                                    peak,
                                     status
                                 ))
                                 .text_style(egui::TextStyle::Small),
@@ -237,3 +295,53 @@ impl Tools {
         });
     }
 }
+
+/*
+ * Where the displayed data comes from and what was loaded, to tell apart several instances
+ * pointing at different OAR deployments.
+ * The loaded window is the one of the Gantt when it is shown, the requested period otherwise.
+ */
+fn provenance_ui(ui: &mut egui::Ui, app: &ApplicationContext, loaded_window: Option<(i64, i64)>) {
+    let (start_s, end_s) = loaded_window.unwrap_or_else(|| {
+        (
+            lock_or_recover(&app.start_date).timestamp(),
+            lock_or_recover(&app.end_date).timestamp(),
+        )
+    });
+    let source = if cfg!(target_arch = "wasm32") {
+        t!("app.menu.provenance.mock").to_string()
+    } else {
+        format!("ssh {} (oarstat -J)", data_source_name())
+    };
+    let last_update = app
+        .last_data_update
+        .map(|date| date.format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_else(|| t!("app.menu.provenance.never").to_string());
+    // The synthetic job 0 only paints the resource states in the Gantt
+    let jobs = app.all_jobs.iter().filter(|job| job.id != 0).count();
+    let hosts: usize = app.all_clusters.iter().map(|cluster| cluster.hosts.len()).sum();
+
+    ui.label(egui::RichText::new(t!("app.menu.provenance.title")).strong());
+    egui::Grid::new("provenance_grid").num_columns(2).show(ui, |ui| {
+        let rows = [
+            (t!("app.menu.provenance.source"), source),
+            (
+                t!("app.menu.provenance.window"),
+                format!("{} → {}", format_timestamp(start_s), format_timestamp(end_s)),
+            ),
+            (t!("app.menu.provenance.last_update"), last_update),
+            (t!("app.menu.provenance.jobs"), jobs.to_string()),
+            (t!("app.menu.provenance.clusters"), app.all_clusters.len().to_string()),
+            (t!("app.menu.provenance.hosts"), hosts.to_string()),
+            (
+                t!("app.menu.provenance.resources"),
+                get_all_resources(&app.all_clusters).len().to_string(),
+            ),
+        ];
+        for (name, value) in rows {
+            ui.label(name);
+            ui.label(value);
+            ui.end_row();
+        }
+    });
+}